@@ -8,8 +8,8 @@ use std::iter::FusedIterator;
 use std::mem::{needs_drop, MaybeUninit};
 use std::panic::UnwindSafe;
 use std::ptr::drop_in_place;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release, SeqCst};
+use std::sync::atomic::{fence, AtomicUsize};
 
 /// [`Bag`] is a lock-free concurrent unordered instance container.
 ///
@@ -151,6 +151,36 @@ impl<T, const ARRAY_LEN: usize> Bag<T, ARRAY_LEN> {
         self.primary_storage.pop().0
     }
 
+    /// Pops up to `max_entries` instances in the [`Bag`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Bag;
+    ///
+    /// let bag: Bag<usize> = Bag::default();
+    ///
+    /// for v in 0..64 {
+    ///     bag.push(v);
+    /// }
+    ///
+    /// assert_eq!(bag.pop_many(48).len(), 48);
+    /// assert_eq!(bag.pop_many(48).len(), 16);
+    /// assert!(bag.pop_many(48).is_empty());
+    /// ```
+    #[inline]
+    pub fn pop_many(&self, max_entries: usize) -> Vec<T> {
+        let mut popped = Vec::new();
+        while popped.len() < max_entries {
+            if let Some(val) = self.pop() {
+                popped.push(val);
+            } else {
+                break;
+            }
+        }
+        popped
+    }
+
     /// Pops all the entries at once, and folds them into an accumulator.
     ///
     /// # Examples
@@ -176,6 +206,9 @@ impl<T, const ARRAY_LEN: usize> Bag<T, ARRAY_LEN> {
         let mut acc = init;
         let popped = self.stack.pop_all();
         while let Some(storage) = popped.pop() {
+            // Instances being scanned are moved out by the scanner once the `Storage` is marked
+            // deleted, otherwise they are popped here.
+            fence(SeqCst);
             acc = storage.pop_all(acc, &mut fold);
         }
         self.primary_storage.pop_all(acc, &mut fold)
@@ -237,6 +270,49 @@ impl<T, const ARRAY_LEN: usize> Bag<T, ARRAY_LEN> {
         }
     }
 
+    /// Scans all the instances in the [`Bag`] without popping them.
+    ///
+    /// An instance being scanned cannot be popped until the supplied closure returns, and
+    /// instances pushed or popped during the scan may or may not be scanned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Bag;
+    ///
+    /// let bag: Bag<usize> = Bag::default();
+    ///
+    /// for v in 0..64 {
+    ///     bag.push(v);
+    /// }
+    ///
+    /// let mut sum = 0;
+    /// bag.scan(|v| sum += *v);
+    /// assert_eq!(sum, (0..64).sum());
+    /// assert_eq!(bag.len(), 64);
+    /// ```
+    #[inline]
+    pub fn scan<F: FnMut(&T)>(&self, mut scanner: F) {
+        let mut reclaimed = Vec::new();
+        self.primary_storage
+            .scan(&mut scanner, || false, &mut reclaimed);
+        debug_assert!(reclaimed.is_empty());
+
+        self.stack.peek_with(|e| {
+            let guard = Guard::new();
+            let mut current = e;
+            while let Some(storage) = current {
+                storage.scan(&mut scanner, || storage.is_deleted(Relaxed), &mut reclaimed);
+                current = storage.next_ptr(Acquire, &guard).as_ref();
+            }
+        });
+
+        // Instances that were left in detached storage are pushed back.
+        for val in reclaimed {
+            self.push(val);
+        }
+    }
+
     /// Iterates over contained instances for modifying them.
     ///
     /// # Examples
@@ -518,6 +594,80 @@ impl<T, const ARRAY_LEN: usize> Storage<T, ARRAY_LEN> {
         }
     }
 
+    /// Passes each of the valid values to the supplied closure.
+    ///
+    /// A value is owned by the scanner while the closure is running, and if the [`Storage`] turns
+    /// out to be detached when the value is released, the value is moved into `reclaimed`.
+    fn scan<F: FnMut(&T), D: Fn() -> bool>(
+        &self,
+        scanner: &mut F,
+        is_detached: D,
+        reclaimed: &mut Vec<T>,
+    ) {
+        let mut visited = 0_u32;
+        let mut metadata = self.metadata.load(Acquire);
+        loop {
+            let instances_to_scan =
+                Self::instance_bitmap(metadata) & !Self::owned_bitmap(metadata) & !visited;
+            if instances_to_scan == 0 {
+                return;
+            }
+            let index = instances_to_scan.trailing_zeros() as usize;
+            if let Err(actual) = self.metadata.compare_exchange_weak(
+                metadata,
+                metadata | (1_usize << index),
+                Acquire,
+                Relaxed,
+            ) {
+                metadata = actual;
+                continue;
+            }
+
+            // Now the slot is owned by the thread.
+            visited |= 1_u32 << index;
+            {
+                let _guard = ExitGuard::new((), |()| {
+                    metadata =
+                        self.metadata.fetch_and(!(1_usize << index), AcqRel) & !(1_usize << index);
+                });
+                scanner(unsafe { &*(*self.storage.get())[index].as_ptr() });
+            }
+            fence(SeqCst);
+            if is_detached() {
+                if let Some(val) = self.take(index) {
+                    reclaimed.push(val);
+                }
+            }
+        }
+    }
+
+    /// Moves out the value at the specified index if it is not owned.
+    fn take(&self, index: usize) -> Option<T> {
+        let mut metadata = self.metadata.load(Relaxed);
+        loop {
+            if Self::instance_bitmap(metadata) & !Self::owned_bitmap(metadata) & (1_u32 << index)
+                == 0
+            {
+                return None;
+            }
+            match self.metadata.compare_exchange_weak(
+                metadata,
+                metadata | (1_usize << index),
+                Acquire,
+                Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(actual) => metadata = actual,
+            }
+        }
+        let val = unsafe { (*self.storage.get())[index].as_ptr().read() };
+        self.metadata.fetch_and(
+            !((1_usize << index) | (1_usize << (index + ARRAY_LEN))),
+            Release,
+        );
+        Some(val)
+    }
+
     /// Pops all the values, and folds them.
     #[allow(clippy::cast_possible_truncation)]
     fn pop_all<B, F: FnMut(B, T) -> B>(&self, init: B, fold: &mut F) -> B {
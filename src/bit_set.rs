@@ -0,0 +1,453 @@
+//! [`BitSet`] is a concurrent growable set of bits.
+
+use crate::ebr::{AtomicShared, Guard, Shared, Tag};
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::panic::UnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
+
+/// Scalable concurrent growable set of bits.
+///
+/// [`BitSet`] stores bits in segments of words; the number of words in a segment doubles with
+/// each segment. A new segment is allocated and published when a bit beyond the capacity is set,
+/// and existing segments are never moved, therefore concurrent operations on bits are never lost
+/// while the [`BitSet`] grows.
+///
+/// ## Notes
+///
+/// Setting, resetting, and testing a bit are linearizable, however [`BitSet::rank`] and [`Iter`]
+/// are not; they are only guaranteed to observe events happened before their invocation.
+///
+/// ## The key features of [`BitSet`]
+///
+/// * Lock-free: every operation is either a wait-free atomic operation on a word or a lock-free
+///   allocation of a segment.
+/// * Compact: a bit occupies one bit of memory once the segment containing the bit is allocated.
+///
+/// ## The key statistics for [`BitSet`]
+///
+/// * The number of words in the first segment: 8.
+/// * The number of words in a segment: twice the number of words in the previous segment.
+pub struct BitSet {
+    /// The segments of words.
+    segments: [AtomicShared<Segment>; NUM_SEGMENTS],
+
+    /// The number of set bits.
+    count: AtomicUsize,
+}
+
+/// An iterator over the indexes of set bits in a [`BitSet`].
+///
+/// An [`Iter`] yields the indexes in ascending order.
+pub struct Iter<'b, 'g> {
+    bit_set: &'b BitSet,
+    word_index: usize,
+    bits: usize,
+    guard: &'g Guard,
+}
+
+/// A segment of words.
+type Segment = Box<[AtomicUsize]>;
+
+/// The number of bits in a word.
+const WORD_BITS: usize = usize::BITS as usize;
+
+/// The number of words in the first segment.
+const BASE_WORDS: usize = 8;
+
+/// The maximum number of segments.
+const NUM_SEGMENTS: usize = usize::BITS as usize;
+
+impl BitSet {
+    /// Creates an empty [`BitSet`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    /// assert_eq!(bitset.capacity(), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            segments: std::array::from_fn(|_| AtomicShared::null()),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Creates an empty [`BitSet`] that can hold at least the specified number of bits without
+    /// allocating memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::with_capacity(1000);
+    /// assert!(bitset.capacity() >= 1000);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let bitset = Self::new();
+        if capacity != 0 {
+            bitset.word_or_grow((capacity - 1) / WORD_BITS, &Guard::new());
+        }
+        bitset
+    }
+
+    /// Sets the bit at the index.
+    ///
+    /// Returns the previous value of the bit. The [`BitSet`] grows if the index is beyond the
+    /// capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(11));
+    /// assert!(bitset.set(11));
+    /// assert!(bitset.test(11));
+    /// ```
+    #[inline]
+    pub fn set(&self, index: usize) -> bool {
+        let guard = Guard::new();
+        let word = self.word_or_grow(index / WORD_BITS, &guard);
+        let mask = 1_usize << (index % WORD_BITS);
+
+        // The counter is incremented in advance so that it never underflows.
+        self.count.fetch_add(1, Relaxed);
+        let set = word.fetch_or(mask, AcqRel) & mask != 0;
+        if set {
+            self.count.fetch_sub(1, Relaxed);
+        }
+        set
+    }
+
+    /// Resets the bit at the index.
+    ///
+    /// Returns the previous value of the bit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.reset(11));
+    /// assert!(!bitset.set(11));
+    /// assert!(bitset.reset(11));
+    /// assert!(!bitset.test(11));
+    /// ```
+    #[inline]
+    pub fn reset(&self, index: usize) -> bool {
+        let guard = Guard::new();
+        let Some(word) = self.word(index / WORD_BITS, &guard) else {
+            return false;
+        };
+        let mask = 1_usize << (index % WORD_BITS);
+        let set = word.fetch_and(!mask, AcqRel) & mask != 0;
+        if set {
+            self.count.fetch_sub(1, Relaxed);
+        }
+        set
+    }
+
+    /// Returns `true` if the bit at the index is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.test(11));
+    /// assert!(!bitset.set(11));
+    /// assert!(bitset.test(11));
+    /// ```
+    #[inline]
+    pub fn test(&self, index: usize) -> bool {
+        let guard = Guard::new();
+        self.word(index / WORD_BITS, &guard).map_or(false, |word| {
+            word.load(Acquire) & (1_usize << (index % WORD_BITS)) != 0
+        })
+    }
+
+    /// Returns the number of set bits.
+    ///
+    /// It reads an internal counter, and therefore the time complexity is O(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(1));
+    /// assert!(!bitset.set(1000));
+    /// assert_eq!(bitset.count_ones(), 2);
+    /// ```
+    #[inline]
+    pub fn count_ones(&self) -> usize {
+        self.count.load(Relaxed)
+    }
+
+    /// Returns the number of set bits whose indexes are less than the specified index.
+    ///
+    /// It scans every word below the index, and therefore the time complexity is O(N).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(1));
+    /// assert!(!bitset.set(64));
+    /// assert!(!bitset.set(1000));
+    /// assert_eq!(bitset.rank(1), 0);
+    /// assert_eq!(bitset.rank(2), 1);
+    /// assert_eq!(bitset.rank(1000), 2);
+    /// assert_eq!(bitset.rank(usize::MAX), 3);
+    /// ```
+    #[inline]
+    pub fn rank(&self, index: usize) -> usize {
+        let guard = Guard::new();
+        let mut rank = 0;
+        for word_index in 0..=index / WORD_BITS {
+            let Some(word) = self.word(word_index, &guard) else {
+                break;
+            };
+            let mut bits = word.load(Acquire);
+            if word_index == index / WORD_BITS {
+                bits &= (1_usize << (index % WORD_BITS)) - 1;
+            }
+            rank += bits.count_ones() as usize;
+        }
+        rank
+    }
+
+    /// Returns the number of bits that the [`BitSet`] can hold without allocating memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(1000));
+    /// assert!(bitset.capacity() > 1000);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        let num_segments = self
+            .segments
+            .iter()
+            .take_while(|segment| !segment.is_null(Relaxed))
+            .count();
+        Self::segment_start(num_segments).saturating_mul(WORD_BITS)
+    }
+
+    /// Resets every bit.
+    ///
+    /// Words are reset one by one, therefore bits set concurrently may survive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(11));
+    /// bitset.clear();
+    /// assert_eq!(bitset.count_ones(), 0);
+    /// assert!(!bitset.test(11));
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        let guard = Guard::new();
+        for segment in &self.segments {
+            let Some(words) = segment.load(Acquire, &guard).as_ref() else {
+                break;
+            };
+            for word in words.iter() {
+                let bits = word.swap(0, AcqRel);
+                if bits != 0 {
+                    self.count.fetch_sub(bits.count_ones() as usize, Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Returns an [`Iter`] over the indexes of set bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::new();
+    ///
+    /// assert!(!bitset.set(1000));
+    /// assert!(!bitset.set(3));
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = bitset.iter(&guard);
+    /// assert_eq!(iter.next(), Some(3));
+    /// assert_eq!(iter.next(), Some(1000));
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn iter<'b, 'g>(&'b self, guard: &'g Guard) -> Iter<'b, 'g> {
+        Iter {
+            bit_set: self,
+            word_index: 0,
+            bits: self.word(0, guard).map_or(0, |word| word.load(Acquire)),
+            guard,
+        }
+    }
+
+    /// Returns a reference to the word at the index if the word is allocated.
+    fn word<'g>(&self, word_index: usize, guard: &'g Guard) -> Option<&'g AtomicUsize> {
+        let (segment, offset) = Self::locate(word_index);
+        let words = self.segments[segment].load(Acquire, guard).as_ref()?;
+        Some(&words[offset])
+    }
+
+    /// Returns a reference to the word at the index, allocating segments if necessary.
+    fn word_or_grow<'g>(&self, word_index: usize, guard: &'g Guard) -> &'g AtomicUsize {
+        let (segment, offset) = Self::locate(word_index);
+        if let Some(words) = self.segments[segment].load(Acquire, guard).as_ref() {
+            return &words[offset];
+        }
+
+        // Segments are allocated in order so that the allocated segments are contiguous.
+        for (index, link) in self.segments.iter().enumerate().take(segment + 1) {
+            let current = link.load(Acquire, guard);
+            if current.is_null() {
+                let len = BASE_WORDS << index;
+                let new_segment: Segment = (0..len).map(|_| AtomicUsize::new(0)).collect();
+                let _result = link.compare_exchange(
+                    current,
+                    (Some(Shared::new(new_segment)), Tag::None),
+                    AcqRel,
+                    Acquire,
+                    guard,
+                );
+            }
+        }
+        let words = self.segments[segment].load(Acquire, guard).as_ref();
+        // The segment has just been allocated.
+        let words = unsafe { words.unwrap_unchecked() };
+        &words[offset]
+    }
+
+    /// Returns the segment containing the word at the index, and the offset of the word in the
+    /// segment.
+    fn locate(word_index: usize) -> (usize, usize) {
+        let segment = (usize::BITS - 1 - (word_index / BASE_WORDS + 1).leading_zeros()) as usize;
+        (segment, word_index - Self::segment_start(segment))
+    }
+
+    /// Returns the index of the first word in the segment.
+    fn segment_start(segment: usize) -> usize {
+        BASE_WORDS.saturating_mul((1_usize << segment.min(NUM_SEGMENTS - 1)) - 1)
+    }
+}
+
+impl Clone for BitSet {
+    #[inline]
+    fn clone(&self) -> Self {
+        let self_clone = Self::with_capacity(self.capacity());
+        for index in self.iter(&Guard::new()) {
+            self_clone.set(index);
+        }
+        self_clone
+    }
+}
+
+impl Debug for BitSet {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = Guard::new();
+        f.debug_set().entries(self.iter(&guard)).finish()
+    }
+}
+
+impl Default for BitSet {
+    /// Creates an empty [`BitSet`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::BitSet;
+    ///
+    /// let bitset = BitSet::default();
+    /// assert_eq!(bitset.count_ones(), 0);
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for BitSet {
+    /// Compares two [`BitSet`] instances regardless of their capacity.
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        let guard = Guard::new();
+        Iterator::eq(self.iter(&guard), other.iter(&guard))
+    }
+}
+
+impl UnwindSafe for BitSet {}
+
+impl Debug for Iter<'_, '_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("word_index", &self.word_index)
+            .finish()
+    }
+}
+
+impl Iterator for Iter<'_, '_> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits == 0 {
+            if self.word_index == usize::MAX {
+                return None;
+            }
+            self.word_index += 1;
+            let Some(word) = self.bit_set.word(self.word_index, self.guard) else {
+                // Words allocated afterwards are not visited.
+                self.word_index = usize::MAX;
+                return None;
+            };
+            self.bits = word.load(Acquire);
+        }
+        let bit = self.bits.trailing_zeros() as usize;
+        self.bits &= self.bits - 1;
+        Some(self.word_index * WORD_BITS + bit)
+    }
+}
+
+impl FusedIterator for Iter<'_, '_> {}
+
+impl UnwindSafe for Iter<'_, '_> {}
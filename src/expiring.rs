@@ -0,0 +1,240 @@
+//! [`Expiring`] is a value that expires after a time-to-live or time-to-idle duration.
+
+use std::time::{Duration, Instant};
+
+/// [`Expiring`] is a value associated with optional time-to-live and time-to-idle timers.
+///
+/// * [`HashIndex`](super::HashIndex): once an entry has been inserted with
+///   [`insert_with_ttl`](super::HashIndex::insert_with_ttl), expired entries are treated as
+///   absent in all the read methods, e.g., [`peek`](super::HashIndex::peek),
+///   [`get`](super::HashIndex::get), and [`iter`](super::HashIndex::iter), and are lazily
+///   removed in [`insert_with_ttl`](super::HashIndex::insert_with_ttl) and
+///   [`remove_expired`](super::HashIndex::remove_expired). Values in a
+///   [`HashIndex`](super::HashIndex) are immutable, therefore the time-to-idle timer is never
+///   reset.
+/// * [`HashCache`](super::HashCache): expired entries are treated as misses in
+///   [`get_unexpired`](super::HashCache::get_unexpired), which also lazily removes them and
+///   resets the time-to-idle timer of the others, and are reclaimed in bulk in
+///   [`remove_expired`](super::HashCache::remove_expired).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Expiring<V> {
+    val: V,
+    deadline: Option<Instant>,
+    time_to_idle: Option<Duration>,
+    idle_deadline: Option<Instant>,
+}
+
+impl<V> Expiring<V> {
+    /// Creates a new [`Expiring`] that never expires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    ///
+    /// let expiring = Expiring::new(11);
+    /// assert!(!expiring.is_expired());
+    /// ```
+    #[inline]
+    pub const fn new(val: V) -> Self {
+        Self {
+            val,
+            deadline: None,
+            time_to_idle: None,
+            idle_deadline: None,
+        }
+    }
+
+    /// Creates a new [`Expiring`] that expires after the specified time-to-live.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let expiring = Expiring::with_ttl(11, Duration::ZERO);
+    /// assert!(expiring.is_expired());
+    /// ```
+    #[inline]
+    pub fn with_ttl(val: V, ttl: Duration) -> Self {
+        Self {
+            val,
+            deadline: Instant::now().checked_add(ttl),
+            time_to_idle: None,
+            idle_deadline: None,
+        }
+    }
+
+    /// Creates a new [`Expiring`] that expires if it is not accessed for the specified
+    /// time-to-idle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let expiring = Expiring::with_tti(11, Duration::from_secs(60));
+    /// assert!(!expiring.is_expired());
+    /// ```
+    #[inline]
+    pub fn with_tti(val: V, tti: Duration) -> Self {
+        Self {
+            val,
+            deadline: None,
+            time_to_idle: Some(tti),
+            idle_deadline: Instant::now().checked_add(tti),
+        }
+    }
+
+    /// Creates a new [`Expiring`] that expires after the specified time-to-live, or if it is not
+    /// accessed for the specified time-to-idle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let expiring = Expiring::with_ttl_and_tti(11, Duration::from_secs(60), Duration::ZERO);
+    /// assert!(expiring.is_expired());
+    /// ```
+    #[inline]
+    pub fn with_ttl_and_tti(val: V, ttl: Duration, tti: Duration) -> Self {
+        let now = Instant::now();
+        Self {
+            val,
+            deadline: now.checked_add(ttl),
+            time_to_idle: Some(tti),
+            idle_deadline: now.checked_add(tti),
+        }
+    }
+
+    /// Returns a reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    ///
+    /// let expiring = Expiring::new(11);
+    /// assert_eq!(*expiring.get(), 11);
+    /// ```
+    #[inline]
+    pub const fn get(&self) -> &V {
+        &self.val
+    }
+
+    /// Returns a mutable reference to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    ///
+    /// let mut expiring = Expiring::new(11);
+    /// *expiring.get_mut() += 1;
+    /// assert_eq!(*expiring.get(), 12);
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.val
+    }
+
+    /// Returns the instant at which the value expires unless it is accessed beforehand.
+    ///
+    /// Returns `None` if the value never expires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    ///
+    /// let expiring = Expiring::new(11);
+    /// assert!(expiring.deadline().is_none());
+    /// ```
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        match (self.deadline, self.idle_deadline) {
+            (Some(deadline), Some(idle_deadline)) => Some(deadline.min(idle_deadline)),
+            (deadline, idle_deadline) => deadline.or(idle_deadline),
+        }
+    }
+
+    /// Returns `true` if the value has expired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let expiring = Expiring::with_ttl(11, Duration::from_secs(60));
+    /// assert!(!expiring.is_expired());
+    /// ```
+    #[inline]
+    pub fn is_expired(&self) -> bool {
+        self.is_expired_at(Instant::now())
+    }
+
+    /// Returns `true` if the value has expired or will have expired at the specified instant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let expiring = Expiring::with_tti(11, Duration::from_secs(60));
+    /// assert!(expiring.is_expired_at(Instant::now() + Duration::from_secs(61)));
+    /// ```
+    #[inline]
+    pub fn is_expired_at(&self, instant: Instant) -> bool {
+        self.deadline()
+            .map_or(false, |deadline| deadline <= instant)
+    }
+
+    /// Resets the time-to-idle timer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mut expiring = Expiring::with_tti(11, Duration::from_secs(60));
+    /// let deadline = expiring.deadline().unwrap();
+    ///
+    /// std::thread::sleep(Duration::from_millis(1));
+    /// expiring.touch();
+    /// assert!(expiring.deadline().unwrap() > deadline);
+    /// ```
+    #[inline]
+    pub fn touch(&mut self) {
+        self.touch_at(Instant::now());
+    }
+
+    /// Consumes the [`Expiring`], returning the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    ///
+    /// let expiring = Expiring::new(11);
+    /// assert_eq!(expiring.into_inner(), 11);
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> V {
+        self.val
+    }
+
+    /// Resets the time-to-idle timer as of the specified instant.
+    #[inline]
+    pub(crate) fn touch_at(&mut self, instant: Instant) {
+        if let Some(time_to_idle) = self.time_to_idle {
+            self.idle_deadline = instant.checked_add(time_to_idle);
+        }
+    }
+}
@@ -1,20 +1,26 @@
 //! [`HashCache`] is a concurrent and asynchronous 32-way associative cache backed by
 //! [`HashMap`](super::HashMap).
 
-use super::ebr::{AtomicShared, Guard, Shared, Tag};
-use super::hash_table::bucket::{DoublyLinkedList, EntryPtr, Locker, Reader, CACHE};
+use super::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
+use super::hash_table::bucket::{
+    DataBlock, DoublyLinkedList, EntryPtr, Locker, Reader, BUCKET_LEN, CACHE,
+};
 use super::hash_table::bucket_array::BucketArray;
 use super::hash_table::{HashTable, LockedEntry};
-use super::wait_queue::AsyncWait;
-use super::Equivalent;
+use super::wait_queue::{AsyncWait, DeriveAsyncWait, WaitQueue};
+use super::{CountMinSketch, Equivalent, Expiring, HashMap};
 use std::collections::hash_map::RandomState;
 use std::fmt::{self, Debug};
+use std::future::Future;
 use std::hash::{BuildHasher, Hash};
-use std::mem::replace;
+use std::mem::{replace, take, transmute, ManuallyDrop};
 use std::ops::{Deref, DerefMut, RangeInclusive};
+use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::pin::Pin;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::{Acquire, Relaxed};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize};
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Scalable concurrent 32-way associative cache backed by [`HashMap`](super::HashMap).
 ///
@@ -33,7 +39,7 @@ use std::sync::atomic::Ordering::{Acquire, Relaxed};
 /// ### Unwind safety
 ///
 /// [`HashCache`] is impervious to out-of-memory errors and panics in user specified code on one
-/// condition; `H::Hasher::hash`, `K::drop` and `V::drop` must not panic.
+/// condition; `H::Hasher::hash`, `K::drop`, `V::drop`, and the eviction listener must not panic.
 pub struct HashCache<K, V, H = RandomState>
 where
     H: BuildHasher,
@@ -42,6 +48,11 @@ where
     minimum_capacity: AtomicUsize,
     maximum_capacity: usize,
     build_hasher: H,
+    eviction_listener: Option<Box<EvictionListener<K, V>>>,
+    frequency_sketch: Option<Box<CountMinSketch<RandomState, AtomicU8>>>,
+    counters: AtomicShared<Counters>,
+    in_flight: AtomicShared<HashMap<K, Arc<Flight>>>,
+    weights: Option<Box<Weights<Weigher<K, V>>>>,
 }
 
 /// The default maximum capacity of a [`HashCache`] is `256`.
@@ -50,6 +61,49 @@ pub const DEFAULT_MAXIMUM_CAPACITY: usize = 256;
 /// [`EvictedEntry`] is a type alias for `Option<(K, V)>`.
 pub type EvictedEntry<K, V> = Option<(K, V)>;
 
+/// [`EvictionCause`] describes why an entry left a [`HashCache`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum EvictionCause {
+    /// The entry was the least recently used one in a full bucket.
+    Capacity,
+
+    /// The entry was explicitly removed.
+    Removed,
+
+    /// The entry was removed after its [`Expiring`] value had expired.
+    Expired,
+}
+
+/// [`AdmissionPolicy`] decides whether a new entry is admitted to a full bucket.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum AdmissionPolicy {
+    /// New entries are always admitted by evicting the least recently used entry.
+    #[default]
+    Always,
+
+    /// A new entry is admitted only if it has been accessed more frequently than the least
+    /// recently used entry in the bucket, as estimated by a [`CountMinSketch`] with `u8` counters.
+    TinyLfu,
+}
+
+/// [`Statistics`] is a snapshot of the cache performance counters of a [`HashCache`].
+///
+/// The counters are updated without synchronizing with each other, and therefore the snapshot
+/// may be slightly inconsistent if the [`HashCache`] is being accessed concurrently.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    hits: usize,
+    misses: usize,
+    insertions: usize,
+    evictions: usize,
+}
+
+/// [`EvictionListener`] is invoked on each entry leaving a [`HashCache`].
+type EvictionListener<K, V> = dyn Fn(&K, &V, EvictionCause) + Send + Sync + RefUnwindSafe;
+
+/// [`Weigher`] computes the weight of an entry in a [`HashCache`].
+type Weigher<K, V> = dyn Fn(&K, &V) -> usize + Send + Sync + RefUnwindSafe;
+
 /// [`Entry`] represents a single cache entry in a [`HashCache`].
 pub enum Entry<'h, K, V, H = RandomState>
 where
@@ -68,7 +122,15 @@ where
     H: BuildHasher,
 {
     hashcache: &'h HashCache<K, V, H>,
-    locked_entry: LockedEntry<'h, K, V, DoublyLinkedList, CACHE>,
+    locked_entry: ManuallyDrop<LockedEntry<'h, K, V, DoublyLinkedList, CACHE>>,
+
+    /// `true` if the entry is heavier than the maximum weight, and is to be evicted when the
+    /// [`OccupiedEntry`] is dropped.
+    rejected: bool,
+
+    /// Entries evicted while the bucket is locked, which are passed to the eviction listener
+    /// when the [`OccupiedEntry`] is dropped.
+    evicted: Vec<(K, V)>,
 }
 
 /// [`VacantEntry`] is a view into a vacant cache entry in a [`HashCache`].
@@ -105,6 +167,11 @@ where
             minimum_capacity: AtomicUsize::new(0),
             maximum_capacity: DEFAULT_MAXIMUM_CAPACITY,
             build_hasher,
+            eviction_listener: None,
+            frequency_sketch: None,
+            counters: AtomicShared::null(),
+            in_flight: AtomicShared::null(),
+            weights: None,
         }
     }
 
@@ -155,6 +222,334 @@ where
             minimum_capacity,
             maximum_capacity,
             build_hasher,
+            eviction_listener: None,
+            frequency_sketch: None,
+            counters: AtomicShared::null(),
+            in_flight: AtomicShared::null(),
+            weights: None,
+        }
+    }
+
+    /// Sets the listener to be invoked on each entry leaving the [`HashCache`].
+    ///
+    /// The listener is invoked along with the [`EvictionCause`] when an entry is evicted to make
+    /// room for a new entry, or when an entry is explicitly removed by methods such as
+    /// [`remove`](Self::remove), [`retain`](Self::retain), and [`clear`](Self::clear). The listener
+    /// is not invoked on entries that are dropped along with the [`HashCache`].
+    ///
+    /// ## Locking behavior
+    ///
+    /// The listener is invoked after the bucket that contained the entry is unlocked, therefore
+    /// the listener can access the [`HashCache`], unless the thread invoking the listener holds
+    /// an [`OccupiedEntry`] or a [`VacantEntry`] of the bucket the listener accesses.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::hash_cache::EvictionCause;
+    /// use std::sync::atomic::AtomicUsize;
+    /// use std::sync::atomic::Ordering::Relaxed;
+    /// use std::sync::Arc;
+    ///
+    /// let num_removed = Arc::new(AtomicUsize::new(0));
+    /// let num_removed_clone = num_removed.clone();
+    ///
+    /// let mut hashcache: HashCache<u64, u32> = HashCache::default();
+    /// hashcache.set_eviction_listener(move |_, _, cause| {
+    ///     if cause == EvictionCause::Removed {
+    ///         num_removed_clone.fetch_add(1, Relaxed);
+    ///     }
+    /// });
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(hashcache.remove(&1).is_some());
+    /// assert_eq!(num_removed.load(Relaxed), 1);
+    /// ```
+    #[inline]
+    pub fn set_eviction_listener<F>(&mut self, listener: F)
+    where
+        F: Fn(&K, &V, EvictionCause) + Send + Sync + RefUnwindSafe + 'static,
+    {
+        self.eviction_listener = Some(Box::new(listener));
+    }
+
+    /// Sets the [`AdmissionPolicy`] of the [`HashCache`].
+    ///
+    /// The admission policy is only consulted by [`put`](Self::put) and
+    /// [`put_async`](Self::put_async) when the bucket for the new entry is full; a new entry
+    /// rejected by the policy is returned as the evicted entry. Entries inserted through
+    /// [`entry`](Self::entry) are always admitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::hash_cache::AdmissionPolicy;
+    ///
+    /// let mut hashcache: HashCache<u64, u32> = HashCache::with_capacity(0, 64);
+    /// hashcache.set_admission_policy(AdmissionPolicy::TinyLfu);
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert_eq!(hashcache.read(&1, |_, v| *v), Some(0));
+    /// ```
+    #[inline]
+    pub fn set_admission_policy(&mut self, policy: AdmissionPolicy) {
+        self.frequency_sketch = match policy {
+            AdmissionPolicy::Always => None,
+            AdmissionPolicy::TinyLfu => {
+                let capacity = self.maximum_capacity.max(1);
+                Some(Box::new(
+                    CountMinSketch::with_counter(
+                        capacity.saturating_mul(4).next_power_of_two(),
+                        4,
+                        RandomState::new(),
+                    )
+                    .with_halving(capacity.saturating_mul(10)),
+                ))
+            }
+        };
+    }
+
+    /// Records an access to the key having the specified hash value.
+    #[inline]
+    fn record_access(&self, hash: u64) {
+        if let Some(frequency_sketch) = self.frequency_sketch.as_ref() {
+            frequency_sketch.add_hash(hash, 1);
+        }
+    }
+
+    /// Returns the total weight of the entries in the [`HashCache`].
+    ///
+    /// Returns `0` if no weigher has been set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let mut hashcache: HashCache<u64, String> = HashCache::default();
+    /// hashcache.set_weigher(1024, |_, v| v.len());
+    ///
+    /// assert!(hashcache.put(1, "cache".to_string()).is_ok());
+    /// assert_eq!(hashcache.total_weight(), 5);
+    /// ```
+    #[inline]
+    pub fn total_weight(&self) -> usize {
+        self.weights
+            .as_ref()
+            .map_or(0, |weights| weights.total_weight.load(Relaxed))
+    }
+
+    /// Returns the total weight of the pinned entries in the [`HashCache`].
+    ///
+    /// The weight of pinned entries is included in [`total_weight`](Self::total_weight), however
+    /// it does not count towards the maximum weight since pinned entries cannot be evicted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let mut hashcache: HashCache<u64, String> = HashCache::default();
+    /// hashcache.set_weigher(1024, |_, v| v.len());
+    ///
+    /// assert!(hashcache.put(1, "cache".to_string()).is_ok());
+    /// assert!(hashcache.put(2, "pinned".to_string()).is_ok());
+    /// assert!(hashcache.pin(&2));
+    /// assert_eq!(hashcache.total_weight(), 11);
+    /// assert_eq!(hashcache.pinned_weight(), 6);
+    /// ```
+    #[inline]
+    pub fn pinned_weight(&self) -> usize {
+        self.weights
+            .as_ref()
+            .map_or(0, |weights| weights.pinned_weight.load(Relaxed))
+    }
+
+    /// Returns the weight of the entry.
+    #[inline]
+    fn weight_of(&self, key: &K, val: &V) -> usize {
+        self.weights
+            .as_ref()
+            .map_or(0, |weights| (weights.weigher)(key, val))
+    }
+
+    /// Returns the maximum total weight of unpinned entries.
+    #[inline]
+    fn maximum_weight(&self) -> usize {
+        self.weights
+            .as_ref()
+            .map_or(usize::MAX, |weights| weights.maximum_weight)
+    }
+
+    /// Returns `true` if the total weight of unpinned entries exceeds the maximum weight.
+    #[inline]
+    fn is_overweight(&self) -> bool {
+        self.weights.as_ref().map_or(false, |weights| {
+            weights
+                .total_weight
+                .load(Relaxed)
+                .saturating_sub(weights.pinned_weight.load(Relaxed))
+                > weights.maximum_weight
+        })
+    }
+
+    /// Adds the weight of a newly pinned entry to the pinned weight.
+    #[inline]
+    fn add_pinned_weight(&self, key: &K, val: &V) {
+        if let Some(weights) = self.weights.as_ref() {
+            let weight = (weights.weigher)(key, val);
+            let _: Result<usize, usize> =
+                weights
+                    .pinned_weight
+                    .fetch_update(Relaxed, Relaxed, |w| Some(w.saturating_add(weight)));
+        }
+    }
+
+    /// Subtracts the weight of an unpinned or removed pinned entry from the pinned weight.
+    #[inline]
+    fn sub_pinned_weight(&self, key: &K, val: &V) {
+        if let Some(weights) = self.weights.as_ref() {
+            let weight = (weights.weigher)(key, val);
+            let _: Result<usize, usize> =
+                weights
+                    .pinned_weight
+                    .fetch_update(Relaxed, Relaxed, |w| Some(w.saturating_sub(weight)));
+        }
+    }
+
+    /// Returns a [`Statistics`] snapshot of the [`HashCache`].
+    ///
+    /// Lookups by [`get`](Self::get), [`read`](Self::read), [`contains`](Self::contains), and
+    /// [`entry`](Self::entry) are counted as either hits or misses, and entries evicted either
+    /// to make room for new entries or on expiration are counted as evictions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(hashcache.contains(&1));
+    /// assert!(!hashcache.contains(&2));
+    ///
+    /// let statistics = hashcache.statistics();
+    /// assert_eq!(statistics.hits(), 1);
+    /// assert_eq!(statistics.misses(), 1);
+    /// assert_eq!(statistics.insertions(), 1);
+    /// assert_eq!(statistics.evictions(), 0);
+    /// ```
+    #[inline]
+    pub fn statistics(&self) -> Statistics {
+        self.counters
+            .load(Acquire, &Guard::new())
+            .as_ref()
+            .map_or_else(Statistics::default, Counters::snapshot)
+    }
+
+    /// Returns the performance counters, allocating them on first use.
+    #[inline]
+    fn counters<'g>(&self, guard: &'g Guard) -> &'g Counters {
+        loop {
+            if let Some(counters) = self.counters.load(Acquire, guard).as_ref() {
+                return counters;
+            }
+            let _result = self.counters.compare_exchange(
+                Ptr::null(),
+                (Some(Shared::new(Counters::default())), Tag::None),
+                AcqRel,
+                Acquire,
+                guard,
+            );
+        }
+    }
+
+    /// Notifies the eviction listener of an entry leaving the [`HashCache`].
+    #[inline]
+    fn notify_eviction(&self, key: &K, val: &V, cause: EvictionCause) {
+        self.sub_weight(key, val);
+        self.notify_rejection(key, val, cause);
+    }
+
+    /// Subtracts the weight of an entry leaving the [`HashCache`] from the total weight.
+    #[inline]
+    fn sub_weight(&self, key: &K, val: &V) {
+        if let Some(weights) = self.weights.as_ref() {
+            let weight = (weights.weigher)(key, val);
+            let _: Result<usize, usize> =
+                weights
+                    .total_weight
+                    .fetch_update(Relaxed, Relaxed, |w| Some(w.saturating_sub(weight)));
+        }
+    }
+
+    /// Notifies the eviction listener of an entry without updating the total weight.
+    ///
+    /// It is directly invoked for entries rejected before being inserted.
+    #[inline]
+    fn notify_rejection(&self, key: &K, val: &V, cause: EvictionCause) {
+        if cause != EvictionCause::Removed {
+            self.counters(&Guard::new()).record_eviction();
+        }
+        if let Some(listener) = self.eviction_listener.as_ref() {
+            listener(key, val, cause);
+        }
+    }
+
+    /// Passes the entries evicted while a bucket was locked to the eviction listener, and then
+    /// evicts entries from the other buckets if the total weight still exceeds the maximum weight.
+    ///
+    /// It must be called after the bucket at the index is unlocked, and the weight of the evicted
+    /// entries must already have been subtracted from the total weight.
+    fn finish_eviction(&self, evicted: Vec<(K, V)>, index: usize) {
+        for (k, v) in &evicted {
+            self.notify_rejection(k, v, EvictionCause::Capacity);
+        }
+        drop(evicted);
+        self.shed_weight(Some(index));
+    }
+
+    /// Evicts least recently used entries from the buckets other than the excluded one, starting
+    /// from the one next to it, until the total weight does not exceed the maximum weight.
+    ///
+    /// Buckets locked by other threads are skipped.
+    fn shed_weight(&self, excluded_index: Option<usize>) {
+        if !self.is_overweight() {
+            return;
+        }
+        let guard = Guard::new();
+        let Some(current_array) = self.array.load(Acquire, &guard).as_ref() else {
+            return;
+        };
+        let num_buckets = current_array.num_buckets();
+        let start_index = excluded_index.map_or(0, |index| index + 1);
+        let mut evicted = true;
+        while evicted {
+            evicted = false;
+            for offset in 0..num_buckets {
+                let index = (start_index + offset) % num_buckets;
+                if excluded_index == Some(index) {
+                    continue;
+                }
+                let Ok(Some(mut locker)) =
+                    Locker::try_lock(current_array.bucket_mut(index), &guard)
+                else {
+                    continue;
+                };
+                let data_block_mut = current_array.data_block_mut(index);
+                let entry = locker.evict_lru_head_except(data_block_mut, &EntryPtr::new(&guard));
+                drop(locker);
+                if let Some((k, v)) = entry {
+                    self.notify_eviction(&k, &v, EvictionCause::Capacity);
+                    evicted = true;
+                }
+                if !self.is_overweight() {
+                    return;
+                }
+            }
         }
     }
 }
@@ -164,6 +559,68 @@ where
     K: Eq + Hash,
     H: BuildHasher,
 {
+    /// Sets the weigher and the maximum total weight of the [`HashCache`].
+    ///
+    /// Once a weigher is set, least recently used entries are evicted until the total weight of
+    /// the entries no longer exceeds `maximum_weight`, on top of the capacity limit of the
+    /// [`HashCache`]. Entries are preferably evicted from the bucket of the new entry, and then
+    /// from the other buckets; buckets locked by other threads are skipped, therefore the total
+    /// weight may temporarily exceed the maximum. An entry heavier than `maximum_weight` is
+    /// rejected by [`put`](Self::put) and [`put_async`](Self::put_async), and an entry heavier
+    /// than `maximum_weight` inserted through [`VacantEntry::put_entry`] is evicted as soon as the
+    /// returned [`OccupiedEntry`] is dropped without evicting any other entries.
+    ///
+    /// The weight of an entry is computed when the entry is inserted, replaced through
+    /// [`OccupiedEntry::put`], and removed, therefore the weigher must return the same weight for
+    /// the same entry: modifying a value in place in a way that changes its weight makes the total
+    /// weight inaccurate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let mut hashcache: HashCache<u64, Vec<u8>> = HashCache::default();
+    /// hashcache.set_weigher(1024, |_, v| v.len());
+    ///
+    /// assert!(hashcache.put(1, vec![0; 512]).is_ok());
+    /// assert!(hashcache.put(2, vec![0; 512]).is_ok());
+    /// assert_eq!(hashcache.total_weight(), 1024);
+    ///
+    /// assert!(hashcache.put(3, vec![0; 512]).is_ok());
+    /// assert_eq!(hashcache.len(), 2);
+    /// assert_eq!(hashcache.total_weight(), 1024);
+    ///
+    /// let (key, _) = hashcache.put(4, vec![0; 2048]).unwrap().unwrap();
+    /// assert_eq!(key, 4);
+    /// ```
+    #[inline]
+    pub fn set_weigher<F>(&mut self, maximum_weight: usize, weigher: F)
+    where
+        F: Fn(&K, &V) -> usize + Send + Sync + RefUnwindSafe + 'static,
+    {
+        let mut total_weight = 0_usize;
+        let mut pinned_weight = 0_usize;
+        self.retain_pinned(
+            |k, v, pinned| {
+                let weight = weigher(k, v);
+                total_weight = total_weight.saturating_add(weight);
+                if pinned {
+                    pinned_weight = pinned_weight.saturating_add(weight);
+                }
+                true
+            },
+            |_, _, _| (),
+        );
+        self.weights = Some(Box::new(Weights {
+            maximum_weight,
+            total_weight: AtomicUsize::new(total_weight),
+            pinned_weight: AtomicUsize::new(pinned_weight),
+            weigher,
+        }));
+        self.shed_weight(None);
+    }
+
     /// Gets the entry associated with the given key in the map for in-place manipulation.
     ///
     /// # Examples
@@ -183,8 +640,17 @@ where
     /// ```
     #[inline]
     pub fn entry(&self, key: K) -> Entry<K, V, H> {
-        let guard = Guard::new();
         let hash = self.hash(&key);
+        self.record_access(hash);
+        let entry = self.lock_entry(key, hash);
+        self.counters(&Guard::new())
+            .record_lookup(matches!(entry, Entry::Occupied(_)));
+        entry
+    }
+
+    /// Locks the entry for the key without updating the statistics.
+    fn lock_entry(&self, key: K, hash: u64) -> Entry<K, V, H> {
+        let guard = Guard::new();
         let mut locked_entry = unsafe {
             self.reserve_entry(&key, hash, &mut (), self.prolonged_guard_ref(&guard))
                 .ok()
@@ -194,7 +660,9 @@ where
             locked_entry.locker.update_lru_tail(&locked_entry.entry_ptr);
             Entry::Occupied(OccupiedEntry {
                 hashcache: self,
-                locked_entry,
+                locked_entry: ManuallyDrop::new(locked_entry),
+                rejected: false,
+                evicted: Vec::new(),
             })
         } else {
             Entry::Vacant(VacantEntry {
@@ -206,6 +674,27 @@ where
         }
     }
 
+    /// Locks the existing entry for the key without updating the statistics and the LRU list.
+    fn lock_occupied_entry<Q, D: DeriveAsyncWait>(
+        &self,
+        key: &Q,
+        hash: u64,
+        async_wait: &mut D,
+    ) -> Result<Option<OccupiedEntry<K, V, H>>, ()>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let guard = Guard::new();
+        let locked_entry =
+            self.get_entry(key, hash, async_wait, self.prolonged_guard_ref(&guard))?;
+        Ok(locked_entry.map(|locked_entry| OccupiedEntry {
+            hashcache: self,
+            locked_entry: ManuallyDrop::new(locked_entry),
+            rejected: false,
+            evicted: Vec::new(),
+        }))
+    }
+
     /// Gets the entry associated with the given key in the map for in-place manipulation.
     ///
     /// It is an asynchronous method returning an `impl Future` for the caller to await.
@@ -222,34 +711,11 @@ where
     #[inline]
     pub async fn entry_async(&self, key: K) -> Entry<K, V, H> {
         let hash = self.hash(&key);
-        loop {
-            let mut async_wait = AsyncWait::default();
-            let mut async_wait_pinned = Pin::new(&mut async_wait);
-            {
-                let guard = Guard::new();
-                if let Ok(mut locked_entry) = self.reserve_entry(
-                    &key,
-                    hash,
-                    &mut async_wait_pinned,
-                    self.prolonged_guard_ref(&guard),
-                ) {
-                    if locked_entry.entry_ptr.is_valid() {
-                        locked_entry.locker.update_lru_tail(&locked_entry.entry_ptr);
-                        return Entry::Occupied(OccupiedEntry {
-                            hashcache: self,
-                            locked_entry,
-                        });
-                    }
-                    return Entry::Vacant(VacantEntry {
-                        hashcache: self,
-                        key,
-                        hash,
-                        locked_entry,
-                    });
-                }
-            }
-            async_wait_pinned.await;
-        }
+        self.record_access(hash);
+        let entry = self.reserve_entry_async(key, hash).await;
+        self.counters(&Guard::new())
+            .record_lookup(matches!(entry, Entry::Occupied(_)));
+        entry
     }
 
     /// Puts a key-value pair into the [`HashCache`].
@@ -274,29 +740,51 @@ where
     pub fn put(&self, key: K, val: V) -> Result<EvictedEntry<K, V>, (K, V)> {
         let guard = Guard::new();
         let hash = self.hash(&key);
-        let result = match self.reserve_entry(&key, hash, &mut (), &guard) {
-            Ok(LockedEntry {
-                mut locker,
-                data_block_mut,
-                entry_ptr,
-                index: _,
-            }) => {
-                if entry_ptr.is_valid() {
-                    return Err((key, val));
-                }
-                let evicted = locker.evict_lru_head(data_block_mut);
-                let entry_ptr = locker.insert_with(
-                    data_block_mut,
-                    BucketArray::<K, V, DoublyLinkedList, CACHE>::partial_hash(hash),
-                    || (key, val),
-                    &guard,
-                );
-                locker.update_lru_tail(&entry_ptr);
-                Ok(evicted)
-            }
-            Err(()) => Err((key, val)),
+        self.record_access(hash);
+        let Ok(LockedEntry {
+            mut locker,
+            data_block_mut,
+            entry_ptr,
+            index,
+        }) = self.reserve_entry(&key, hash, &mut (), &guard)
+        else {
+            return Err((key, val));
         };
-        result
+        if entry_ptr.is_valid() {
+            return Err((key, val));
+        }
+        let weight = self.weight_of(&key, &val);
+        if weight > self.maximum_weight() || !self.admit(hash, locker.lru_head(data_block_mut)) {
+            drop(locker);
+            self.notify_rejection(&key, &val, EvictionCause::Capacity);
+            return Ok(Some((key, val)));
+        }
+        let evicted = locker.evict_lru_head(data_block_mut);
+        if let Some((k, v)) = evicted.as_ref() {
+            self.sub_weight(k, v);
+        }
+        let entry_ptr = locker.insert_with(
+            data_block_mut,
+            BucketArray::<K, V, DoublyLinkedList, CACHE>::partial_hash(hash),
+            || (key, val),
+            &guard,
+        );
+        locker.update_lru_tail(&entry_ptr);
+        self.counters(&Guard::new()).record_insertion();
+        let mut evicted_for_weight = Vec::new();
+        self.add_weight(
+            weight,
+            &mut locker,
+            data_block_mut,
+            &entry_ptr,
+            &mut evicted_for_weight,
+        );
+        drop(locker);
+        if let Some((k, v)) = evicted.as_ref() {
+            self.notify_rejection(k, v, EvictionCause::Capacity);
+        }
+        self.finish_eviction(evicted_for_weight, index);
+        Ok(evicted)
     }
 
     /// Puts a key-value pair into the [`HashCache`].
@@ -319,6 +807,7 @@ where
     #[inline]
     pub async fn put_async(&self, key: K, val: V) -> Result<EvictedEntry<K, V>, (K, V)> {
         let hash = self.hash(&key);
+        self.record_access(hash);
         loop {
             let mut async_wait = AsyncWait::default();
             let mut async_wait_pinned = Pin::new(&mut async_wait);
@@ -328,13 +817,24 @@ where
                     mut locker,
                     data_block_mut,
                     entry_ptr,
-                    index: _,
+                    index,
                 }) = self.reserve_entry(&key, hash, &mut async_wait_pinned, &guard)
                 {
                     if entry_ptr.is_valid() {
                         return Err((key, val));
                     }
+                    let weight = self.weight_of(&key, &val);
+                    if weight > self.maximum_weight()
+                        || !self.admit(hash, locker.lru_head(data_block_mut))
+                    {
+                        drop(locker);
+                        self.notify_rejection(&key, &val, EvictionCause::Capacity);
+                        return Ok(Some((key, val)));
+                    }
                     let evicted = locker.evict_lru_head(data_block_mut);
+                    if let Some((k, v)) = evicted.as_ref() {
+                        self.sub_weight(k, v);
+                    }
                     let entry_ptr = locker.insert_with(
                         data_block_mut,
                         BucketArray::<K, V, DoublyLinkedList, CACHE>::partial_hash(hash),
@@ -342,6 +842,20 @@ where
                         &guard,
                     );
                     locker.update_lru_tail(&entry_ptr);
+                    self.counters(&Guard::new()).record_insertion();
+                    let mut evicted_for_weight = Vec::new();
+                    self.add_weight(
+                        weight,
+                        &mut locker,
+                        data_block_mut,
+                        &entry_ptr,
+                        &mut evicted_for_weight,
+                    );
+                    drop(locker);
+                    if let Some((k, v)) = evicted.as_ref() {
+                        self.notify_rejection(k, v, EvictionCause::Capacity);
+                    }
+                    self.finish_eviction(evicted_for_weight, index);
                     return Ok(evicted);
                 };
             }
@@ -376,19 +890,21 @@ where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         let guard = Guard::new();
-        let mut locked_entry = self
-            .get_entry(
-                key,
-                self.hash(key),
-                &mut (),
-                self.prolonged_guard_ref(&guard),
-            )
+        let hash = self.hash(key);
+        self.record_access(hash);
+        let locked_entry = self
+            .get_entry(key, hash, &mut (), self.prolonged_guard_ref(&guard))
             .ok()
-            .flatten()?;
+            .flatten();
+        self.counters(&Guard::new())
+            .record_lookup(locked_entry.is_some());
+        let mut locked_entry = locked_entry?;
         locked_entry.locker.update_lru_tail(&locked_entry.entry_ptr);
         Some(OccupiedEntry {
             hashcache: self,
-            locked_entry,
+            locked_entry: ManuallyDrop::new(locked_entry),
+            rejected: false,
+            evicted: Vec::new(),
         })
     }
 
@@ -415,6 +931,7 @@ where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         let hash = self.hash(key);
+        self.record_access(hash);
         loop {
             let mut async_wait = AsyncWait::default();
             let mut async_wait_pinned = Pin::new(&mut async_wait);
@@ -424,11 +941,14 @@ where
                 &mut async_wait_pinned,
                 self.prolonged_guard_ref(&Guard::new()),
             ) {
+                self.counters(&Guard::new()).record_lookup(result.is_some());
                 if let Some(mut locked_entry) = result {
                     locked_entry.locker.update_lru_tail(&locked_entry.entry_ptr);
                     return Some(OccupiedEntry {
                         hashcache: self,
-                        locked_entry,
+                        locked_entry: ManuallyDrop::new(locked_entry),
+                        rejected: false,
+                        evicted: Vec::new(),
                     });
                 }
                 return None;
@@ -437,6 +957,63 @@ where
         }
     }
 
+    /// Gets an [`OccupiedEntry`] corresponding to the key, or inserts a value loaded by the
+    /// supplied loader if the key does not exist.
+    ///
+    /// Concurrent calls for the same key are deduplicated: only one of the callers runs its
+    /// loader, and the others wait for the loaded value to be inserted. If the loading caller is
+    /// cancelled, one of the waiting callers takes over. It is an asynchronous method returning
+    /// an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// let future_get_or_insert = hashcache.get_or_insert_async(11, || async { 17 });
+    /// ```
+    #[inline]
+    pub async fn get_or_insert_async<F, Fut>(&self, key: K, loader: F) -> OccupiedEntry<K, V, H>
+    where
+        K: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let flight = loop {
+            if let Some(entry) = self.get_async(&key).await {
+                return entry;
+            }
+            let in_flight = self.in_flight();
+            let flight = match in_flight.entry_async(key.clone()).await {
+                crate::hash_map::Entry::Occupied(o) => Err(o.get().clone()),
+                crate::hash_map::Entry::Vacant(v) => {
+                    let flight = Arc::new(Flight::default());
+                    v.insert_entry(flight.clone());
+                    Ok(flight)
+                }
+            };
+            match flight {
+                Ok(flight) => break FlightGuard::new(in_flight, key.clone(), flight),
+                Err(flight) => flight.wait().await,
+            }
+        };
+
+        // A previous loader may have completed in the meantime.
+        let hash = self.hash(&key);
+        if let Entry::Occupied(o) = self.reserve_entry_async(key.clone(), hash).await {
+            return o;
+        }
+        let val = loader().await;
+        let entry = match self.reserve_entry_async(key, hash).await {
+            Entry::Occupied(o) => o,
+            Entry::Vacant(v) => v.put_entry(val).1,
+        };
+        drop(flight);
+        entry
+    }
+
     /// Reads a key-value pair.
     ///
     /// Returns `None` if the key does not exist.
@@ -457,10 +1034,12 @@ where
     where
         Q: Equivalent<K> + Hash + ?Sized,
     {
-        self.read_entry(key, self.hash(key), &mut (), &Guard::new())
-            .ok()
-            .flatten()
-            .map(|(k, v)| reader(k, v))
+        let hash = self.hash(key);
+        self.record_access(hash);
+        let guard = Guard::new();
+        let result = self.read_entry(key, hash, &mut (), &guard).ok().flatten();
+        self.counters(&Guard::new()).record_lookup(result.is_some());
+        result.map(|(k, v)| reader(k, v))
     }
 
     /// Reads a key-value pair.
@@ -483,10 +1062,12 @@ where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         let hash = self.hash(key);
+        self.record_access(hash);
         loop {
             let mut async_wait = AsyncWait::default();
             let mut async_wait_pinned = Pin::new(&mut async_wait);
             if let Ok(result) = self.read_entry(key, hash, &mut async_wait_pinned, &Guard::new()) {
+                self.counters(&Guard::new()).record_lookup(result.is_some());
                 return result.map(|(k, v)| reader(k, v));
             }
             async_wait_pinned.await;
@@ -600,13 +1181,16 @@ where
     where
         Q: Equivalent<K> + Hash + ?Sized,
     {
-        self.get(key).and_then(|mut o| {
-            if condition(o.get_mut()) {
-                Some(o.remove_entry())
-            } else {
-                None
-            }
-        })
+        let hash = self.hash(key);
+        let mut entry = self
+            .lock_occupied_entry(key, hash, &mut ())
+            .ok()
+            .flatten()?;
+        if condition(entry.get_mut()) {
+            Some(entry.remove_entry())
+        } else {
+            None
+        }
     }
 
     /// Removes a key-value pair if the key exists and the given condition is met.
@@ -632,12 +1216,367 @@ where
     where
         Q: Equivalent<K> + Hash + ?Sized,
     {
-        if let Some(mut occupied_entry) = self.get_async(key).await {
-            if condition(occupied_entry.get_mut()) {
-                return Some(occupied_entry.remove_entry());
+        let hash = self.hash(key);
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Ok(result) = self.lock_occupied_entry(key, hash, &mut async_wait_pinned) {
+                let mut entry = result?;
+                if condition(entry.get_mut()) {
+                    return Some(entry.remove_entry());
+                }
+                return None;
             }
+            async_wait_pinned.await;
         }
-        None
+    }
+
+    /// Pins the entry corresponding to the key, thereby exempting it from eviction.
+    ///
+    /// Pinned entries are never evicted to make room for new entries or to reduce the total
+    /// weight, however they can still be removed or expire. If all the entries in a bucket are
+    /// pinned, new entries are stored in an overflow area of the bucket, making the [`HashCache`]
+    /// exceed its capacity. The weight of pinned entries is accounted separately, see
+    /// [`pinned_weight`](Self::pinned_weight).
+    ///
+    /// Returns `false` if the key does not exist or the entry is already pinned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(!hashcache.pin(&1));
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(hashcache.pin(&1));
+    /// assert!(!hashcache.pin(&1));
+    /// ```
+    #[inline]
+    pub fn pin<Q>(&self, key: &Q) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let guard = Guard::new();
+        let hash = self.hash(key);
+        self.get_entry(key, hash, &mut (), self.prolonged_guard_ref(&guard))
+            .ok()
+            .flatten()
+            .map_or(false, |locked_entry| {
+                OccupiedEntry {
+                    hashcache: self,
+                    locked_entry: ManuallyDrop::new(locked_entry),
+                    rejected: false,
+                    evicted: Vec::new(),
+                }
+                .pin()
+            })
+    }
+
+    /// Pins the entry corresponding to the key, thereby exempting it from eviction.
+    ///
+    /// Returns `false` if the key does not exist or the entry is already pinned. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    /// let future_put = hashcache.put_async(11, 17);
+    /// let future_pin = hashcache.pin_async(&11);
+    /// ```
+    #[inline]
+    pub async fn pin_async<Q>(&self, key: &Q) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let hash = self.hash(key);
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Ok(result) = self.get_entry(
+                key,
+                hash,
+                &mut async_wait_pinned,
+                self.prolonged_guard_ref(&Guard::new()),
+            ) {
+                return result.map_or(false, |locked_entry| {
+                    OccupiedEntry {
+                        hashcache: self,
+                        locked_entry: ManuallyDrop::new(locked_entry),
+                        rejected: false,
+                        evicted: Vec::new(),
+                    }
+                    .pin()
+                });
+            }
+            async_wait_pinned.await;
+        }
+    }
+
+    /// Unpins the entry corresponding to the key.
+    ///
+    /// The entry becomes the most recently used one in its bucket. Returns `false` if the key
+    /// does not exist or the entry is not pinned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(!hashcache.unpin(&1));
+    /// assert!(hashcache.pin(&1));
+    /// assert!(hashcache.unpin(&1));
+    /// ```
+    #[inline]
+    pub fn unpin<Q>(&self, key: &Q) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let guard = Guard::new();
+        let hash = self.hash(key);
+        self.get_entry(key, hash, &mut (), self.prolonged_guard_ref(&guard))
+            .ok()
+            .flatten()
+            .map_or(false, |locked_entry| {
+                OccupiedEntry {
+                    hashcache: self,
+                    locked_entry: ManuallyDrop::new(locked_entry),
+                    rejected: false,
+                    evicted: Vec::new(),
+                }
+                .unpin()
+            })
+    }
+
+    /// Unpins the entry corresponding to the key.
+    ///
+    /// The entry becomes the most recently used one in its bucket. Returns `false` if the key
+    /// does not exist or the entry is not pinned. It is an asynchronous method returning an
+    /// `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    /// let future_put = hashcache.put_async(11, 17);
+    /// let future_unpin = hashcache.unpin_async(&11);
+    /// ```
+    #[inline]
+    pub async fn unpin_async<Q>(&self, key: &Q) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let hash = self.hash(key);
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Ok(result) = self.get_entry(
+                key,
+                hash,
+                &mut async_wait_pinned,
+                self.prolonged_guard_ref(&Guard::new()),
+            ) {
+                return result.map_or(false, |locked_entry| {
+                    OccupiedEntry {
+                        hashcache: self,
+                        locked_entry: ManuallyDrop::new(locked_entry),
+                        rejected: false,
+                        evicted: Vec::new(),
+                    }
+                    .unpin()
+                });
+            }
+            async_wait_pinned.await;
+        }
+    }
+
+    /// Loads key-value pairs into the [`HashCache`].
+    ///
+    /// The key-value pairs are expected to be ordered from the most important one, e.g., as
+    /// yielded by [`export`](Self::export), therefore they are inserted in reverse order so that
+    /// the first key-value pairs end up being the most recently used ones in their buckets. Keys
+    /// that already exist in the [`HashCache`] are not updated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// hashcache.load_from([(1, 10), (2, 20)]);
+    /// assert_eq!(hashcache.read(&1, |_, v| *v), Some(10));
+    /// assert_eq!(hashcache.read(&2, |_, v| *v), Some(20));
+    /// ```
+    #[inline]
+    pub fn load_from<I: IntoIterator<Item = (K, V)>>(&self, iter: I) {
+        let entries: Vec<(K, V)> = iter.into_iter().collect();
+        for (key, val) in entries.into_iter().rev() {
+            let _: Result<EvictedEntry<K, V>, (K, V)> = self.put(key, val);
+        }
+    }
+
+    /// Loads key-value pairs into the [`HashCache`].
+    ///
+    /// The key-value pairs are expected to be ordered from the most important one, e.g., as
+    /// yielded by [`export_async`](Self::export_async), therefore they are inserted in reverse
+    /// order so that the first key-value pairs end up being the most recently used ones in their
+    /// buckets. Keys that already exist in the [`HashCache`] are not updated. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// let future_load = hashcache.load_from_async([(1, 10), (2, 20)]);
+    /// ```
+    #[inline]
+    pub async fn load_from_async<I: IntoIterator<Item = (K, V)>>(&self, iter: I) {
+        let entries: Vec<(K, V)> = iter.into_iter().collect();
+        for (key, val) in entries.into_iter().rev() {
+            let _: Result<EvictedEntry<K, V>, (K, V)> = self.put_async(key, val).await;
+        }
+    }
+
+    /// Exports clones of all the entries ordered from the most recently used ones.
+    ///
+    /// [`HashCache`] does not keep track of the global recency order, therefore the most recently
+    /// used entries of the buckets come first, followed by the second most recently used entries
+    /// of the buckets, and so on. This method does not affect the LRU information in each bucket,
+    /// and the exported entries can be loaded back with [`load_from`](Self::load_from).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 10).is_ok());
+    /// assert!(hashcache.put(2, 20).is_ok());
+    ///
+    /// let snapshot: Vec<(u64, u32)> = hashcache.export().collect();
+    /// assert_eq!(snapshot.len(), 2);
+    ///
+    /// let restored: HashCache<u64, u32> = HashCache::default();
+    /// restored.load_from(snapshot);
+    /// assert_eq!(restored.read(&2, |_, v| *v), Some(20));
+    /// ```
+    #[inline]
+    pub fn export(&self) -> impl Iterator<Item = (K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let guard = Guard::new();
+        let mut ranked = Vec::new();
+        let mut current_array_ptr = self.array.load(Acquire, &guard);
+        while let Some(current_array) = current_array_ptr.as_ref() {
+            ranked.clear();
+            self.clear_old_array(current_array, &guard);
+            for index in 0..current_array.num_buckets() {
+                let bucket = current_array.bucket(index);
+                if let Some(reader) = Reader::lock(bucket, &guard) {
+                    let data_block = current_array.data_block(index);
+                    for (rank, (k, v)) in reader
+                        .entries_by_recency(data_block)
+                        .into_iter()
+                        .enumerate()
+                    {
+                        ranked.push((rank, (k.clone(), v.clone())));
+                    }
+                }
+            }
+            let new_current_array_ptr = self.array.load(Acquire, &guard);
+            if current_array_ptr.without_tag() == new_current_array_ptr.without_tag() {
+                break;
+            }
+            current_array_ptr = new_current_array_ptr;
+        }
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, entry)| entry)
+    }
+
+    /// Exports clones of all the entries ordered from the most recently used ones.
+    ///
+    /// [`HashCache`] does not keep track of the global recency order, therefore the most recently
+    /// used entries of the buckets come first, followed by the second most recently used entries
+    /// of the buckets, and so on. This method does not affect the LRU information in each bucket,
+    /// and the exported entries can be loaded back with
+    /// [`load_from_async`](Self::load_from_async). It is an asynchronous method returning an
+    /// `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// let future_put = hashcache.put_async(1, 10);
+    /// let future_export = hashcache.export_async();
+    /// ```
+    #[inline]
+    pub async fn export_async(&self) -> impl Iterator<Item = (K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut ranked = Vec::new();
+        let mut current_array_holder = self.array.get_shared(Acquire, &Guard::new());
+        while let Some(current_array) = current_array_holder.take() {
+            ranked.clear();
+            self.cleanse_old_array_async(&current_array).await;
+            for index in 0..current_array.num_buckets() {
+                loop {
+                    let mut async_wait = AsyncWait::default();
+                    let mut async_wait_pinned = Pin::new(&mut async_wait);
+                    {
+                        let guard = Guard::new();
+                        let bucket = current_array.bucket(index);
+                        if let Ok(reader) =
+                            Reader::try_lock_or_wait(bucket, &mut async_wait_pinned, &guard)
+                        {
+                            if let Some(reader) = reader {
+                                let data_block = current_array.data_block(index);
+                                for (rank, (k, v)) in reader
+                                    .entries_by_recency(data_block)
+                                    .into_iter()
+                                    .enumerate()
+                                {
+                                    ranked.push((rank, (k.clone(), v.clone())));
+                                }
+                            }
+                            break;
+                        };
+                    }
+                    async_wait_pinned.await;
+                }
+            }
+
+            if let Some(new_current_array) = self.array.get_shared(Acquire, &Guard::new()) {
+                if new_current_array.as_ptr() == current_array.as_ptr() {
+                    break;
+                }
+                current_array_holder.replace(new_current_array);
+                continue;
+            }
+            break;
+        }
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, entry)| entry)
     }
 
     /// Scans all the entries.
@@ -865,9 +1804,8 @@ where
     /// assert!(!hashcache.contains(&3));
     /// ```
     #[inline]
-    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&self, mut pred: F) {
-        #[allow(clippy::redundant_closure_for_method_calls)]
-        self.retain_entries(|k, v| pred(k, v));
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&self, pred: F) {
+        self.retain_with_cause(pred, EvictionCause::Removed);
     }
 
     /// Retains the entries specified by the predicate.
@@ -891,7 +1829,80 @@ where
     /// let future_retain = hashcache.retain_async(|k, v| *k == 1);
     /// ```
     #[inline]
-    pub async fn retain_async<F: FnMut(&K, &mut V) -> bool>(&self, mut filter: F) {
+    pub async fn retain_async<F: FnMut(&K, &mut V) -> bool>(&self, filter: F) {
+        self.retain_with_cause_async(filter, EvictionCause::Removed)
+            .await;
+    }
+
+    /// Retains the entries specified by the predicate, notifying the eviction listener of removed
+    /// entries with the supplied [`EvictionCause`].
+    fn retain_with_cause<F: FnMut(&K, &mut V) -> bool>(&self, mut pred: F, cause: EvictionCause) {
+        self.retain_pinned(
+            |k, v, _| pred(k, v),
+            |k, v, pinned| {
+                if pinned {
+                    self.sub_pinned_weight(k, v);
+                }
+                self.notify_eviction(k, v, cause);
+            },
+        );
+    }
+
+    /// Retains the entries specified by the predicate which is additionally given whether the
+    /// entry is pinned.
+    ///
+    /// `removed` is invoked on each removed entry after the bucket that contained the entry is
+    /// unlocked.
+    fn retain_pinned<F: FnMut(&K, &mut V, bool) -> bool, R: FnMut(&K, &V, bool)>(
+        &self,
+        mut pred: F,
+        mut removed: R,
+    ) {
+        let guard = Guard::new();
+        let mut any_removed = false;
+        let mut removed_entries = Vec::new();
+        let mut current_array_ptr = self.array.load(Acquire, &guard);
+        while let Some(current_array) = current_array_ptr.as_ref() {
+            self.clear_old_array(current_array, &guard);
+            for index in 0..current_array.num_buckets() {
+                let bucket = current_array.bucket_mut(index);
+                if let Some(mut locker) = Locker::lock(bucket, &guard) {
+                    let data_block_mut = current_array.data_block_mut(index);
+                    let mut entry_ptr = EntryPtr::new(&guard);
+                    while entry_ptr.move_to_next(&locker, &guard) {
+                        let pinned = locker.is_pinned(&entry_ptr);
+                        let (k, v) = entry_ptr.get_mut(data_block_mut, &mut locker);
+                        if !pred(k, v, pinned) {
+                            let entry = locker.remove(data_block_mut, &mut entry_ptr, &guard);
+                            removed_entries.push((entry, pinned));
+                        }
+                    }
+                }
+                any_removed |= !removed_entries.is_empty();
+                for ((k, v), pinned) in removed_entries.drain(..) {
+                    removed(&k, &v, pinned);
+                }
+            }
+
+            let new_current_array_ptr = self.array.load(Acquire, &guard);
+            if current_array_ptr.without_tag() == new_current_array_ptr.without_tag() {
+                break;
+            }
+            current_array_ptr = new_current_array_ptr;
+        }
+
+        if any_removed {
+            self.try_resize(0, &guard);
+        }
+    }
+
+    /// Retains the entries specified by the predicate, notifying the eviction listener of removed
+    /// entries with the supplied [`EvictionCause`].
+    async fn retain_with_cause_async<F: FnMut(&K, &mut V) -> bool>(
+        &self,
+        mut filter: F,
+        cause: EvictionCause,
+    ) {
         let mut removed = false;
         let mut current_array_holder = self.array.get_shared(Acquire, &Guard::new());
         while let Some(current_array) = current_array_holder.take() {
@@ -906,17 +1917,27 @@ where
                         if let Ok(locker) =
                             Locker::try_lock_or_wait(bucket, &mut async_wait_pinned, &guard)
                         {
+                            let mut removed_entries = Vec::new();
                             if let Some(mut locker) = locker {
                                 let data_block_mut = current_array.data_block_mut(index);
                                 let mut entry_ptr = EntryPtr::new(&guard);
                                 while entry_ptr.move_to_next(&locker, &guard) {
+                                    let pinned = locker.is_pinned(&entry_ptr);
                                     let (k, v) = entry_ptr.get_mut(data_block_mut, &mut locker);
                                     if !filter(k, v) {
-                                        locker.remove(data_block_mut, &mut entry_ptr, &guard);
-                                        removed = true;
+                                        let entry =
+                                            locker.remove(data_block_mut, &mut entry_ptr, &guard);
+                                        removed_entries.push((entry, pinned));
                                     }
                                 }
                             }
+                            removed |= !removed_entries.is_empty();
+                            for ((k, v), pinned) in removed_entries {
+                                if pinned {
+                                    self.sub_pinned_weight(&k, &v);
+                                }
+                                self.notify_eviction(&k, &v, cause);
+                            }
                             break;
                         };
                     }
@@ -1034,40 +2055,261 @@ where
         self.num_slots(&Guard::new())
     }
 
-    /// Returns the current capacity range of the [`HashCache`].
+    /// Returns the current capacity range of the [`HashCache`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert_eq!(hashcache.capacity_range(), 0..=256);
+    /// ```
+    #[inline]
+    pub fn capacity_range(&self) -> RangeInclusive<usize> {
+        self.minimum_capacity.load(Relaxed)..=self.maximum_capacity()
+    }
+
+    /// Returns `true` if a new entry having the specified hash value should be admitted.
+    ///
+    /// `lru_head` is the entry to be evicted for the new entry if the bucket is full.
+    #[inline]
+    fn admit(&self, hash: u64, lru_head: Option<&(K, V)>) -> bool {
+        let (Some(frequency_sketch), Some((lru_key, _))) =
+            (self.frequency_sketch.as_ref(), lru_head)
+        else {
+            return true;
+        };
+        frequency_sketch.estimate_hash(hash) > frequency_sketch.estimate_hash(self.hash(lru_key))
+    }
+
+    /// Adds the weight of a new or replaced entry, and evicts least recently used entries in the
+    /// bucket if the total weight exceeds the maximum weight.
+    ///
+    /// The evicted entries are pushed to `evicted`, and must be passed to
+    /// [`finish_eviction`](Self::finish_eviction) after the bucket is unlocked.
+    fn add_weight(
+        &self,
+        weight: usize,
+        locker: &mut Locker<K, V, DoublyLinkedList, CACHE>,
+        data_block_mut: &mut DataBlock<K, V, BUCKET_LEN>,
+        entry_ptr: &EntryPtr<K, V, CACHE>,
+        evicted: &mut Vec<(K, V)>,
+    ) {
+        let Some(weights) = self.weights.as_ref() else {
+            return;
+        };
+        let _: Result<usize, usize> = weights
+            .total_weight
+            .fetch_update(Relaxed, Relaxed, |w| Some(w.saturating_add(weight)));
+        while self.is_overweight() {
+            let Some((k, v)) = locker.evict_lru_head_except(data_block_mut, entry_ptr) else {
+                break;
+            };
+            self.sub_weight(&k, &v);
+            evicted.push((k, v));
+        }
+    }
+
+    /// Returns the loaders in flight, allocating the map on first use.
+    fn in_flight(&self) -> Shared<HashMap<K, Arc<Flight>>> {
+        let guard = Guard::new();
+        loop {
+            if let Some(in_flight) = self.in_flight.get_shared(Acquire, &guard) {
+                return in_flight;
+            }
+            let _result = self.in_flight.compare_exchange(
+                Ptr::null(),
+                (
+                    Some(unsafe { Shared::new_unchecked(HashMap::default()) }),
+                    Tag::None,
+                ),
+                AcqRel,
+                Acquire,
+                &guard,
+            );
+        }
+    }
+
+    /// Reserves an entry for the key asynchronously without updating the statistics.
+    async fn reserve_entry_async(&self, key: K, hash: u64) -> Entry<K, V, H> {
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            {
+                let guard = Guard::new();
+                if let Ok(mut locked_entry) = self.reserve_entry(
+                    &key,
+                    hash,
+                    &mut async_wait_pinned,
+                    self.prolonged_guard_ref(&guard),
+                ) {
+                    if locked_entry.entry_ptr.is_valid() {
+                        locked_entry.locker.update_lru_tail(&locked_entry.entry_ptr);
+                        return Entry::Occupied(OccupiedEntry {
+                            hashcache: self,
+                            locked_entry: ManuallyDrop::new(locked_entry),
+                            rejected: false,
+                            evicted: Vec::new(),
+                        });
+                    }
+                    return Entry::Vacant(VacantEntry {
+                        hashcache: self,
+                        key,
+                        hash,
+                        locked_entry,
+                    });
+                }
+            }
+            async_wait_pinned.await;
+        }
+    }
+
+    /// Clears the old array asynchronously.
+    async fn cleanse_old_array_async(
+        &self,
+        current_array: &BucketArray<K, V, DoublyLinkedList, CACHE>,
+    ) {
+        while current_array.has_old_array() {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if self.incremental_rehash::<K, _, false>(
+                current_array,
+                &mut async_wait_pinned,
+                &Guard::new(),
+            ) == Ok(true)
+            {
+                break;
+            }
+            async_wait_pinned.await;
+        }
+    }
+}
+
+impl<K, V, H> HashCache<K, Expiring<V>, H>
+where
+    K: Eq + Hash,
+    H: BuildHasher,
+{
+    /// Gets an [`OccupiedEntry`] corresponding to the key if the value has not expired.
+    ///
+    /// An expired entry is treated as a miss, and it is removed from the [`HashCache`]. The
+    /// time-to-idle timer of the value is reset if the value has not expired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let hashcache: HashCache<u64, Expiring<u32>> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, Expiring::with_ttl(10, Duration::from_secs(60))).is_ok());
+    /// assert!(hashcache.put(2, Expiring::with_ttl(20, Duration::ZERO)).is_ok());
+    ///
+    /// assert_eq!(*hashcache.get_unexpired(&1).unwrap().get().get(), 10);
+    /// assert!(hashcache.get_unexpired(&2).is_none());
+    /// assert!(!hashcache.contains(&2));
+    /// ```
+    #[inline]
+    pub fn get_unexpired<Q>(&self, key: &Q) -> Option<OccupiedEntry<K, Expiring<V>, H>>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let now = Instant::now();
+        let mut entry = self.get(key)?;
+        if entry.get().is_expired_at(now) {
+            let _: (K, Expiring<V>) = entry.remove_entry_with_cause(EvictionCause::Expired);
+            self.counters(&Guard::new()).record_expired_hit();
+            return None;
+        }
+        entry.get_mut().touch_at(now);
+        Some(entry)
+    }
+
+    /// Gets an [`OccupiedEntry`] corresponding to the key if the value has not expired.
+    ///
+    /// An expired entry is treated as a miss, and it is removed from the [`HashCache`]. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::Expiring;
+    ///
+    /// let hashcache: HashCache<u64, Expiring<u32>> = HashCache::default();
+    ///
+    /// let future_put = hashcache.put_async(11, Expiring::new(17));
+    /// let future_get = hashcache.get_unexpired_async(&11);
+    /// ```
+    #[inline]
+    pub async fn get_unexpired_async<Q>(&self, key: &Q) -> Option<OccupiedEntry<K, Expiring<V>, H>>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        let mut entry = self.get_async(key).await?;
+        let now = Instant::now();
+        if entry.get().is_expired_at(now) {
+            let _: (K, Expiring<V>) = entry.remove_entry_with_cause(EvictionCause::Expired);
+            self.counters(&Guard::new()).record_expired_hit();
+            return None;
+        }
+        entry.get_mut().touch_at(now);
+        Some(entry)
+    }
+
+    /// Removes all the expired entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::Expiring;
+    /// use std::time::Duration;
+    ///
+    /// let hashcache: HashCache<u64, Expiring<u32>> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, Expiring::new(10)).is_ok());
+    /// assert!(hashcache.put(2, Expiring::with_tti(20, Duration::ZERO)).is_ok());
+    ///
+    /// hashcache.remove_expired();
+    /// assert!(hashcache.contains(&1));
+    /// assert!(!hashcache.contains(&2));
+    /// ```
+    #[inline]
+    pub fn remove_expired(&self) {
+        let now = Instant::now();
+        self.retain_with_cause(
+            |_, expiring| !expiring.is_expired_at(now),
+            EvictionCause::Expired,
+        );
+    }
+
+    /// Removes all the expired entries.
+    ///
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
     ///
     /// # Examples
     ///
     /// ```
     /// use scc::HashCache;
+    /// use scc::Expiring;
     ///
-    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    /// let hashcache: HashCache<u64, Expiring<u32>> = HashCache::default();
     ///
-    /// assert_eq!(hashcache.capacity_range(), 0..=256);
+    /// let future_remove = hashcache.remove_expired_async();
     /// ```
     #[inline]
-    pub fn capacity_range(&self) -> RangeInclusive<usize> {
-        self.minimum_capacity.load(Relaxed)..=self.maximum_capacity()
-    }
-
-    /// Clears the old array asynchronously.
-    async fn cleanse_old_array_async(
-        &self,
-        current_array: &BucketArray<K, V, DoublyLinkedList, CACHE>,
-    ) {
-        while current_array.has_old_array() {
-            let mut async_wait = AsyncWait::default();
-            let mut async_wait_pinned = Pin::new(&mut async_wait);
-            if self.incremental_rehash::<K, _, false>(
-                current_array,
-                &mut async_wait_pinned,
-                &Guard::new(),
-            ) == Ok(true)
-            {
-                break;
-            }
-            async_wait_pinned.await;
-        }
+    pub async fn remove_expired_async(&self) {
+        let now = Instant::now();
+        self.retain_with_cause_async(
+            |_, expiring| !expiring.is_expired_at(now),
+            EvictionCause::Expired,
+        )
+        .await;
     }
 }
 
@@ -1177,9 +2419,32 @@ where
                 // remain outside the lifetime of the `HashCache`.
                 a.drop_in_place()
             });
+        self.in_flight
+            .swap((None, Tag::None), Relaxed)
+            .0
+            .map(|m| unsafe {
+                // The loaders in flight borrow the `HashCache`, therefore none of them can remain.
+                m.drop_in_place()
+            });
     }
 }
 
+impl<K, V, H> RefUnwindSafe for HashCache<K, V, H>
+where
+    K: RefUnwindSafe,
+    V: RefUnwindSafe,
+    H: BuildHasher + RefUnwindSafe,
+{
+}
+
+impl<K, V, H> UnwindSafe for HashCache<K, V, H>
+where
+    K: UnwindSafe,
+    V: UnwindSafe,
+    H: BuildHasher + UnwindSafe,
+{
+}
+
 impl<K, V, H> HashTable<K, V, H, DoublyLinkedList, CACHE> for HashCache<K, V, H>
 where
     K: Eq + Hash,
@@ -1455,25 +2720,44 @@ where
     /// ```
     #[inline]
     #[must_use]
-    pub fn remove_entry(mut self) -> (K, V) {
+    pub fn remove_entry(self) -> (K, V) {
+        self.remove_entry_with_cause(EvictionCause::Removed)
+    }
+
+    /// Takes ownership of the key and value from the [`HashCache`], notifying the eviction
+    /// listener of the removal with the supplied [`EvictionCause`].
+    fn remove_entry_with_cause(mut self, cause: EvictionCause) -> (K, V) {
         let guard = Guard::new();
-        let (k, v) = self.locked_entry.locker.remove(
-            self.locked_entry.data_block_mut,
-            &mut self.locked_entry.entry_ptr,
-            self.hashcache.prolonged_guard_ref(&guard),
+        let hashcache = self.hashcache;
+        let rejected = replace(&mut self.rejected, false);
+        let pinned = self
+            .locked_entry
+            .locker
+            .is_pinned(&self.locked_entry.entry_ptr);
+        let locked_entry = &mut *self.locked_entry;
+        let (k, v) = locked_entry.locker.remove(
+            locked_entry.data_block_mut,
+            &mut locked_entry.entry_ptr,
+            hashcache.prolonged_guard_ref(&guard),
         );
-        if self.locked_entry.locker.num_entries() <= 1 || self.locked_entry.locker.need_rebuild() {
-            let hashcache = self.hashcache;
+        let index = locked_entry.index;
+        let shrink = locked_entry.locker.num_entries() <= 1 || locked_entry.locker.need_rebuild();
+        drop(self);
+        if shrink {
             if let Some(current_array) = hashcache.bucket_array().load(Acquire, &guard).as_ref() {
-                if !current_array.has_old_array() {
-                    let index = self.locked_entry.index;
-                    if current_array.within_sampling_range(index) {
-                        drop(self);
-                        hashcache.try_shrink_or_rebuild(current_array, index, &guard);
-                    }
+                if !current_array.has_old_array() && current_array.within_sampling_range(index) {
+                    hashcache.try_shrink_or_rebuild(current_array, index, &guard);
                 }
             }
         }
+        if pinned {
+            hashcache.sub_pinned_weight(&k, &v);
+        }
+        if rejected {
+            hashcache.notify_rejection(&k, &v, cause);
+        } else {
+            hashcache.notify_eviction(&k, &v, cause);
+        }
         (k, v)
     }
 
@@ -1524,13 +2808,10 @@ where
     /// ```
     #[inline]
     pub fn get_mut(&mut self) -> &mut V {
-        &mut self
-            .locked_entry
+        let locked_entry = &mut *self.locked_entry;
+        &mut locked_entry
             .entry_ptr
-            .get_mut(
-                self.locked_entry.data_block_mut,
-                &mut self.locked_entry.locker,
-            )
+            .get_mut(locked_entry.data_block_mut, &mut locked_entry.locker)
             .1
     }
 
@@ -1554,7 +2835,31 @@ where
     /// ```
     #[inline]
     pub fn put(&mut self, val: V) -> V {
-        replace(self.get_mut(), val)
+        let hashcache = self.hashcache;
+        let Some(weights) = hashcache.weights.as_ref().filter(|_| !self.rejected) else {
+            return replace(self.get_mut(), val);
+        };
+        let weight = (weights.weigher)(self.key(), &val);
+        let old_weight = (weights.weigher)(self.key(), self.get());
+        let old_val = replace(self.get_mut(), val);
+        let _: Result<usize, usize> = weights
+            .total_weight
+            .fetch_update(Relaxed, Relaxed, |w| Some(w.saturating_sub(old_weight)));
+        if self.is_pinned() {
+            let _: Result<usize, usize> =
+                weights.pinned_weight.fetch_update(Relaxed, Relaxed, |w| {
+                    Some(w.saturating_sub(old_weight).saturating_add(weight))
+                });
+        }
+        let locked_entry = &mut *self.locked_entry;
+        hashcache.add_weight(
+            weight,
+            &mut locked_entry.locker,
+            locked_entry.data_block_mut,
+            &locked_entry.entry_ptr,
+            &mut self.evicted,
+        );
+        old_val
     }
 
     /// Takes the value out of the entry, and returns it.
@@ -1578,6 +2883,103 @@ where
     pub fn remove(self) -> V {
         self.remove_entry().1
     }
+
+    /// Pins the entry, thereby exempting it from eviction.
+    ///
+    /// Pinned entries are never evicted to make room for new entries or to reduce the total
+    /// weight, however they can still be removed or expire. Returns `false` if the entry was
+    /// already pinned, or if it was inserted through [`VacantEntry::put_entry`] and is heavier than
+    /// the maximum weight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::hash_cache::Entry;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// hashcache.entry(11).or_put(17);
+    ///
+    /// if let Entry::Occupied(mut o) = hashcache.entry(11) {
+    ///     assert!(o.pin());
+    ///     assert!(!o.pin());
+    ///     assert!(o.is_pinned());
+    /// };
+    /// ```
+    #[inline]
+    pub fn pin(&mut self) -> bool {
+        let locked_entry = &mut *self.locked_entry;
+        if self.rejected || !locked_entry.locker.pin(&locked_entry.entry_ptr) {
+            return false;
+        }
+        self.hashcache.add_pinned_weight(self.key(), self.get());
+        true
+    }
+
+    /// Unpins the entry, and makes it the most recently used one in its bucket.
+    ///
+    /// Least recently used entries may be evicted if the total weight of unpinned entries exceeds
+    /// the maximum weight. Returns `false` if the entry was not pinned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::hash_cache::Entry;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// hashcache.entry(11).or_put(17);
+    ///
+    /// if let Entry::Occupied(mut o) = hashcache.entry(11) {
+    ///     assert!(!o.unpin());
+    ///     assert!(o.pin());
+    ///     assert!(o.unpin());
+    ///     assert!(!o.is_pinned());
+    /// };
+    /// ```
+    #[inline]
+    pub fn unpin(&mut self) -> bool {
+        let locked_entry = &mut *self.locked_entry;
+        if !locked_entry.locker.unpin(&locked_entry.entry_ptr) {
+            return false;
+        }
+        self.hashcache.sub_pinned_weight(self.key(), self.get());
+        let locked_entry = &mut *self.locked_entry;
+        self.hashcache.add_weight(
+            0,
+            &mut locked_entry.locker,
+            locked_entry.data_block_mut,
+            &locked_entry.entry_ptr,
+            &mut self.evicted,
+        );
+        true
+    }
+
+    /// Returns `true` if the entry is pinned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    /// use scc::hash_cache::Entry;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// hashcache.entry(11).or_put(17);
+    ///
+    /// if let Entry::Occupied(o) = hashcache.entry(11) {
+    ///     assert!(!o.is_pinned());
+    /// };
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.locked_entry
+            .locker
+            .is_pinned(&self.locked_entry.entry_ptr)
+    }
 }
 
 impl<'h, K, V, H> Debug for OccupiedEntry<'h, K, V, H>
@@ -1595,6 +2997,34 @@ where
     }
 }
 
+impl<K, V, H> Drop for OccupiedEntry<'_, K, V, H>
+where
+    H: BuildHasher,
+{
+    #[inline]
+    fn drop(&mut self) {
+        let mut locked_entry = unsafe { ManuallyDrop::take(&mut self.locked_entry) };
+        let mut rejected = None;
+        if self.rejected && locked_entry.entry_ptr.is_valid() {
+            let guard = Guard::new();
+            rejected.replace(locked_entry.locker.remove(
+                locked_entry.data_block_mut,
+                &mut locked_entry.entry_ptr,
+                // The removed entry is not referenced outside the lifetime of the `HashCache`.
+                unsafe { transmute::<&Guard, &Guard>(&guard) },
+            ));
+        }
+        let index = locked_entry.index;
+        drop(locked_entry);
+        if let Some((k, v)) = rejected {
+            self.hashcache
+                .notify_rejection(&k, &v, EvictionCause::Capacity);
+        }
+        self.hashcache
+            .finish_eviction(take(&mut self.evicted), index);
+    }
+}
+
 impl<'h, K, V, H> Deref for OccupiedEntry<'h, K, V, H>
 where
     K: Eq + Hash,
@@ -1660,7 +3090,10 @@ where
 
     /// Sets the value of the entry with its key, and returns an [`OccupiedEntry`].
     ///
-    /// Returns a key-value pair if an entry was evicted for the new key-value pair.
+    /// Returns a key-value pair if an entry was evicted for the new key-value pair. If the new
+    /// entry is heavier than the maximum weight set by [`HashCache::set_weigher`], the entry does
+    /// not count towards the total weight, and it is evicted and passed to the eviction listener
+    /// when the returned [`OccupiedEntry`] is dropped.
     ///
     /// # Examples
     ///
@@ -1678,10 +3111,33 @@ where
     /// ```
     #[inline]
     pub fn put_entry(mut self, val: V) -> (EvictedEntry<K, V>, OccupiedEntry<'h, K, V, H>) {
-        let evicted = self
+        if let Some((k, v)) = self
             .locked_entry
             .locker
-            .evict_lru_head(self.locked_entry.data_block_mut);
+            .evict_lru_head(self.locked_entry.data_block_mut)
+        {
+            // The eviction listener is invoked without holding the lock, and the key may be
+            // inserted by another thread before the bucket is locked again.
+            let VacantEntry {
+                hashcache,
+                key,
+                hash,
+                locked_entry,
+            } = self;
+            hashcache.sub_weight(&k, &v);
+            drop(locked_entry);
+            hashcache.notify_rejection(&k, &v, EvictionCause::Capacity);
+            let occupied = match hashcache.lock_entry(key, hash) {
+                Entry::Occupied(mut o) => {
+                    o.put(val);
+                    o
+                }
+                Entry::Vacant(v) => v.put_entry(val).1,
+            };
+            return (Some((k, v)), occupied);
+        }
+        let weight = self.hashcache.weight_of(&self.key, &val);
+        let rejected = weight > self.hashcache.maximum_weight();
         let entry_ptr = self.locked_entry.locker.insert_with(
             self.locked_entry.data_block_mut,
             BucketArray::<K, V, DoublyLinkedList, CACHE>::partial_hash(self.hash),
@@ -1689,17 +3145,31 @@ where
             self.hashcache.prolonged_guard_ref(&Guard::new()),
         );
         self.locked_entry.locker.update_lru_tail(&entry_ptr);
+        let mut evicted = Vec::new();
+        if !rejected {
+            // A rejected entry does not count towards the total weight until it is evicted.
+            self.hashcache.counters(&Guard::new()).record_insertion();
+            self.hashcache.add_weight(
+                weight,
+                &mut self.locked_entry.locker,
+                self.locked_entry.data_block_mut,
+                &entry_ptr,
+                &mut evicted,
+            );
+        }
         let occupied = OccupiedEntry {
             hashcache: self.hashcache,
-            locked_entry: LockedEntry {
+            locked_entry: ManuallyDrop::new(LockedEntry {
                 index: self.locked_entry.index,
                 data_block_mut: self.locked_entry.data_block_mut,
                 locker: self.locked_entry.locker,
                 entry_ptr,
-            },
+            }),
+            rejected,
+            evicted,
         };
 
-        (evicted, occupied)
+        (None, occupied)
     }
 }
 
@@ -1714,3 +3184,253 @@ where
         f.debug_tuple("VacantEntry").field(self.key()).finish()
     }
 }
+
+impl Statistics {
+    /// Returns the number of lookups that found the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert_eq!(hashcache.read(&1, |_, v| *v), Some(0));
+    /// assert_eq!(hashcache.statistics().hits(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Returns the number of lookups that did not find the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.get(&1).is_none());
+    /// assert_eq!(hashcache.statistics().misses(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Returns the number of inserted entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(hashcache.put(1, 1).is_err());
+    /// assert_eq!(hashcache.statistics().insertions(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn insertions(&self) -> usize {
+        self.insertions
+    }
+
+    /// Returns the number of entries evicted to make room for new entries or on expiration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::with_capacity(0, 64);
+    ///
+    /// for k in 0..1024 {
+    ///     assert!(hashcache.put(k, 0).is_ok());
+    /// }
+    /// assert_eq!(hashcache.statistics().evictions(), 1024 - hashcache.len());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn evictions(&self) -> usize {
+        self.evictions
+    }
+
+    /// Returns the ratio of hits to lookups.
+    ///
+    /// Returns `0.0` if there have been no lookups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashCache;
+    ///
+    /// let hashcache: HashCache<u64, u32> = HashCache::default();
+    ///
+    /// assert!(hashcache.put(1, 0).is_ok());
+    /// assert!(hashcache.contains(&1));
+    /// assert!(!hashcache.contains(&2));
+    /// assert_eq!(hashcache.statistics().hit_ratio(), 0.5);
+    /// ```
+    #[allow(clippy::cast_precision_loss)]
+    #[inline]
+    #[must_use]
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// [`Counters`] keeps track of cache performance figures in per-thread stripes.
+#[derive(Default)]
+struct Counters {
+    stripes: [Stripe; Counters::NUM_STRIPES],
+}
+
+/// [`Stripe`] is a set of counters occupying a cache line.
+#[derive(Default)]
+#[repr(align(64))]
+struct Stripe {
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+    insertions: AtomicUsize,
+    evictions: AtomicUsize,
+}
+
+impl Counters {
+    /// The number of stripes.
+    const NUM_STRIPES: usize = 8;
+
+    /// Records the result of a lookup.
+    #[inline]
+    fn record_lookup(&self, hit: bool) {
+        let stripe = self.stripe();
+        if hit {
+            stripe.hits.fetch_add(1, Relaxed);
+        } else {
+            stripe.misses.fetch_add(1, Relaxed);
+        }
+    }
+
+    /// Reclassifies a hit as a miss as the entry turned out to have expired.
+    #[inline]
+    fn record_expired_hit(&self) {
+        let stripe = self.stripe();
+        stripe.hits.fetch_sub(1, Relaxed);
+        stripe.misses.fetch_add(1, Relaxed);
+    }
+
+    /// Records an insertion.
+    #[inline]
+    fn record_insertion(&self) {
+        self.stripe().insertions.fetch_add(1, Relaxed);
+    }
+
+    /// Records an eviction.
+    #[inline]
+    fn record_eviction(&self) {
+        self.stripe().evictions.fetch_add(1, Relaxed);
+    }
+
+    /// Returns a [`Statistics`] snapshot.
+    fn snapshot(&self) -> Statistics {
+        self.stripes
+            .iter()
+            .fold(Statistics::default(), |statistics, stripe| Statistics {
+                hits: statistics.hits.wrapping_add(stripe.hits.load(Relaxed)),
+                misses: statistics.misses.wrapping_add(stripe.misses.load(Relaxed)),
+                insertions: statistics
+                    .insertions
+                    .wrapping_add(stripe.insertions.load(Relaxed)),
+                evictions: statistics
+                    .evictions
+                    .wrapping_add(stripe.evictions.load(Relaxed)),
+            })
+    }
+
+    /// Returns the [`Stripe`] assigned to the current thread.
+    #[inline]
+    fn stripe(&self) -> &Stripe {
+        static NEXT_STRIPE: AtomicUsize = AtomicUsize::new(0);
+        thread_local! {
+            static STRIPE: usize = NEXT_STRIPE.fetch_add(1, Relaxed) % Counters::NUM_STRIPES;
+        }
+        &self.stripes[STRIPE.with(|stripe| *stripe)]
+    }
+}
+
+/// [`Weights`] keeps track of the weight of the entries in a [`HashCache`] having a weigher.
+struct Weights<W: ?Sized> {
+    maximum_weight: usize,
+    total_weight: AtomicUsize,
+    pinned_weight: AtomicUsize,
+    weigher: W,
+}
+
+/// [`Flight`] is shared among callers loading the same key.
+#[derive(Debug, Default)]
+struct Flight {
+    /// `true` if the loader has either completed or been cancelled.
+    done: AtomicBool,
+
+    /// Callers waiting for the loader.
+    wait_queue: WaitQueue,
+}
+
+/// [`FlightGuard`] unregisters and completes a [`Flight`] when dropped.
+struct FlightGuard<K: Eq + Hash> {
+    in_flight: Shared<HashMap<K, Arc<Flight>>>,
+    key: K,
+    flight: Arc<Flight>,
+}
+
+impl Flight {
+    /// Waits for the loader to complete or be cancelled.
+    async fn wait(&self) {
+        let mut async_wait = AsyncWait::default();
+        let mut async_wait_pinned = Pin::new(&mut async_wait);
+        if let Some(async_wait) = async_wait_pinned.derive() {
+            let registered = self.wait_queue.push_async_entry(async_wait, || {
+                if self.done.load(Acquire) {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            });
+            if registered.is_err() {
+                async_wait_pinned.await;
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash> FlightGuard<K> {
+    /// Creates a new [`FlightGuard`].
+    fn new(in_flight: Shared<HashMap<K, Arc<Flight>>>, key: K, flight: Arc<Flight>) -> Self {
+        FlightGuard {
+            in_flight,
+            key,
+            flight,
+        }
+    }
+}
+
+impl<K: Eq + Hash> Drop for FlightGuard<K> {
+    #[inline]
+    fn drop(&mut self) {
+        self.in_flight
+            .remove_if(&self.key, |flight| Arc::ptr_eq(flight, &self.flight));
+        self.flight.done.store(true, Release);
+        self.flight.wait_queue.signal();
+    }
+}
@@ -5,17 +5,20 @@ use super::hash_table::bucket::{Bucket, EntryPtr, Locker, OPTIMISTIC};
 use super::hash_table::bucket_array::BucketArray;
 use super::hash_table::{HashTable, LockedEntry};
 use super::wait_queue::AsyncWait;
-use super::Equivalent;
+use super::{Equivalent, Expiring};
+use std::cmp::Reverse;
 use std::collections::hash_map::RandomState;
 use std::fmt::{self, Debug};
 use std::hash::{BuildHasher, Hash};
 use std::iter::FusedIterator;
+use std::mem::transmute;
 use std::ops::{Deref, RangeInclusive};
 use std::panic::UnwindSafe;
 use std::pin::Pin;
 use std::ptr;
-use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::{Acquire, Relaxed};
+use std::sync::atomic::{AtomicPtr, AtomicUsize};
+use std::time::{Duration, Instant};
 
 /// Scalable concurrent hash index.
 ///
@@ -47,6 +50,9 @@ where
 {
     array: AtomicShared<BucketArray<K, V, (), OPTIMISTIC>>,
     minimum_capacity: AtomicUsize,
+    /// The `fn(&V) -> bool` telling whether a value has expired, or null if the values never
+    /// expire.
+    expiry: AtomicPtr<()>,
     build_hasher: H,
 }
 
@@ -110,6 +116,40 @@ where
     guard: &'g Guard,
 }
 
+/// An iterator over the keys of a [`HashIndex`].
+///
+/// A [`Keys`] iterates over all the keys that survive the [`Keys`].
+pub struct Keys<'h, 'g, K, V, H = RandomState>
+where
+    H: BuildHasher,
+{
+    iter: Iter<'h, 'g, K, V, H>,
+}
+
+/// An iterator over the values of a [`HashIndex`].
+///
+/// A [`Values`] iterates over all the values that survive the [`Values`].
+pub struct Values<'h, 'g, K, V, H = RandomState>
+where
+    H: BuildHasher,
+{
+    iter: Iter<'h, 'g, K, V, H>,
+}
+
+/// [`Statistics`] is a snapshot of the internal state of a [`HashIndex`].
+///
+/// The figures are gathered without acquiring locks, and therefore may not be accurate if the
+/// [`HashIndex`] is being modified concurrently.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Statistics {
+    capacity: usize,
+    num_buckets: usize,
+    num_entries: usize,
+    num_removed_entries: usize,
+    num_resizes: usize,
+    resizing: bool,
+}
+
 impl<K, V, H> HashIndex<K, V, H>
 where
     H: BuildHasher,
@@ -130,6 +170,7 @@ where
         Self {
             array: AtomicShared::null(),
             minimum_capacity: AtomicUsize::new(0),
+            expiry: AtomicPtr::new(ptr::null_mut()),
             build_hasher,
         }
     }
@@ -170,6 +211,7 @@ where
         Self {
             array,
             minimum_capacity,
+            expiry: AtomicPtr::new(ptr::null_mut()),
             build_hasher,
         }
     }
@@ -401,7 +443,7 @@ where
     pub fn any_entry<P: FnMut(&K, &V) -> bool>(&self, pred: P) -> Option<OccupiedEntry<K, V, H>> {
         let guard = Guard::new();
         let prolonged_guard = self.prolonged_guard_ref(&guard);
-        let locked_entry = self.find_entry(pred, prolonged_guard)?;
+        let locked_entry = self.find_entry(pred, 0, prolonged_guard)?;
         Some(OccupiedEntry {
             hashindex: self,
             locked_entry,
@@ -505,6 +547,98 @@ where
         }
     }
 
+    /// Inserts multiple key-value pairs into the [`HashIndex`].
+    ///
+    /// The [`HashIndex`] is resized in advance to accommodate all the supplied key-value pairs,
+    /// and each bucket is locked only once for all the key-value pairs that belong to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied key-value pairs whose keys exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert_many((0..1024).map(|k| (k, 0))).is_ok());
+    /// assert_eq!(hashindex.len(), 1024);
+    /// assert_eq!(hashindex.insert_many([(1, 1), (2048, 1)]).unwrap_err(), [(1, 1)]);
+    /// assert!(hashindex.contains(&2048));
+    /// ```
+    #[inline]
+    pub fn insert_many<I: IntoIterator<Item = (K, V)>>(
+        &self,
+        entries: I,
+    ) -> Result<(), Vec<(K, V)>> {
+        let mut entries = self.prepare_entries(entries);
+        let reserve = self.reserve(entries.len());
+        let guard = Guard::new();
+        self.sort_entries(&mut entries, &guard);
+        let mut rejected = Vec::new();
+        let result = self.insert_sorted_entries(&mut entries, &mut rejected, &mut (), &guard);
+        debug_assert!(result.is_ok());
+        drop(reserve);
+        if rejected.is_empty() {
+            Ok(())
+        } else {
+            Err(rejected)
+        }
+    }
+
+    /// Inserts multiple key-value pairs into the [`HashIndex`].
+    ///
+    /// The [`HashIndex`] is resized in advance to accommodate all the supplied key-value pairs,
+    /// and each bucket is locked only once for all the key-value pairs that belong to it. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied key-value pairs whose keys exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    /// let future_insert_many = hashindex.insert_many_async((0..1024).map(|k| (k, 0)));
+    /// ```
+    #[inline]
+    pub async fn insert_many_async<I: IntoIterator<Item = (K, V)>>(
+        &self,
+        entries: I,
+    ) -> Result<(), Vec<(K, V)>> {
+        let mut entries = self.prepare_entries(entries);
+        let reserve = self.reserve(entries.len());
+        self.sort_entries(&mut entries, &Guard::new());
+        let mut rejected = Vec::new();
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if self
+                .insert_sorted_entries(
+                    &mut entries,
+                    &mut rejected,
+                    &mut async_wait_pinned,
+                    &Guard::new(),
+                )
+                .is_ok()
+            {
+                break;
+            }
+            async_wait_pinned.await;
+        }
+        drop(reserve);
+        if rejected.is_empty() {
+            Ok(())
+        } else {
+            Err(rejected)
+        }
+    }
+
     /// Removes a key-value pair if the key exists.
     ///
     /// Returns `false` if the key does not exist.
@@ -638,7 +772,7 @@ where
     /// [`OccupiedEntry`] exclusively owns the entry, preventing others from gaining access to it:
     /// use [`peek`](Self::peek) if read-only access is sufficient.
     ///
-    /// Returns `None` if the key does not exist.
+    /// Returns `None` if the key does not exist or the entry has expired.
     ///
     /// # Examples
     ///
@@ -658,19 +792,19 @@ where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         let guard = Guard::new();
-        let locked_entry = self
-            .get_entry(
-                key,
-                self.hash(key),
-                &mut (),
-                self.prolonged_guard_ref(&guard),
-            )
-            .ok()
-            .flatten()?;
-        Some(OccupiedEntry {
+        self.get_entry(
+            key,
+            self.hash(key),
+            &mut (),
+            self.prolonged_guard_ref(&guard),
+        )
+        .ok()
+        .flatten()
+        .map(|locked_entry| OccupiedEntry {
             hashindex: self,
             locked_entry,
         })
+        .filter(|entry| !self.is_expired(entry.get()))
     }
 
     /// Gets an [`OccupiedEntry`] corresponding to the key for in-place modification.
@@ -678,8 +812,8 @@ where
     /// [`OccupiedEntry`] exclusively owns the entry, preventing others from gaining access to it:
     /// use [`peek`](Self::peek) if read-only access is sufficient.
     ///
-    /// Returns `None` if the key does not exist. It is an asynchronous method returning an
-    /// `impl Future` for the caller to await.
+    /// Returns `None` if the key does not exist or the entry has expired. It is an asynchronous
+    /// method returning an `impl Future` for the caller to await.
     ///
     /// # Examples
     ///
@@ -705,22 +839,85 @@ where
                 &mut async_wait_pinned,
                 self.prolonged_guard_ref(&Guard::new()),
             ) {
-                if let Some(locked_entry) = result {
-                    return Some(OccupiedEntry {
+                return result
+                    .map(|locked_entry| OccupiedEntry {
                         hashindex: self,
                         locked_entry,
-                    });
-                }
-                return None;
+                    })
+                    .filter(|entry| !self.is_expired(entry.get()));
             }
             async_wait_pinned.await;
         }
     }
 
+    /// Replaces the value associated with the key with a new value derived from the current one.
+    ///
+    /// The closure is invoked with the entry locked, therefore it always observes the latest value
+    /// and concurrent modifications of the same entry are serialized; lock-free readers keep
+    /// observing the old value until the new value is installed. The value is left intact if the
+    /// closure returns `None`, and expired entries are treated as absent. Returns `true` if a new
+    /// value was installed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(!hashindex.modify(&1, |v| Some(v + 1)));
+    /// assert!(hashindex.insert(1, 10).is_ok());
+    /// assert!(hashindex.modify(&1, |v| Some(v + 1)));
+    /// assert!(!hashindex.modify(&1, |_| None));
+    /// assert_eq!(hashindex.peek_with(&1, |_, v| *v), Some(11));
+    /// ```
+    #[inline]
+    pub fn modify<Q, F: FnOnce(&V) -> Option<V>>(&self, key: &Q, f: F) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        if let Some(entry) = self.get(key) {
+            if let Some(val) = f(entry.get()) {
+                entry.update(val);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Replaces the value associated with the key with a new value derived from the current one.
+    ///
+    /// The value is left intact if the closure returns `None`, and expired entries are treated as
+    /// absent. Returns `true` if a new value was installed. It is an asynchronous method returning
+    /// an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    /// let future_insert = hashindex.insert_async(11, 17);
+    /// let future_modify = hashindex.modify_async(&11, |v| Some(v + 1));
+    /// ```
+    #[inline]
+    pub async fn modify_async<Q, F: FnOnce(&V) -> Option<V>>(&self, key: &Q, f: F) -> bool
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        if let Some(entry) = self.get_async(key).await {
+            if let Some(val) = f(entry.get()) {
+                entry.update(val);
+                return true;
+            }
+        }
+        false
+    }
+
     /// Returns a guarded reference to the value for the specified key without acquiring locks.
     ///
-    /// Returns `None` if the key does not exist. The returned reference can survive as long as the
-    /// associated [`Guard`] is alive.
+    /// Returns `None` if the key does not exist or the entry has expired. The returned reference
+    /// can survive as long as the associated [`Guard`] is alive.
     ///
     /// This method is not linearizable since the entry can be removed while being read.
     ///
@@ -740,18 +937,51 @@ where
     /// ```
     #[inline]
     pub fn peek<'g, Q>(&self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        self.peek_entry(key, guard).map(|(_, v)| v)
+    }
+
+    /// Returns a guarded reference to the key-value pair for the specified key without acquiring
+    /// locks.
+    ///
+    /// Returns `None` if the key does not exist or the entry has expired. The returned reference
+    /// can survive as long as the associated [`Guard`] is alive.
+    ///
+    /// This method is not linearizable since the entry can be removed while being read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::HashIndex;
+    /// use std::sync::Arc;
+    ///
+    /// let hashindex: HashIndex<Arc<str>, u32> = HashIndex::default();
+    ///
+    /// let guard = Guard::new();
+    /// assert!(hashindex.peek_entry("foo", &guard).is_none());
+    ///
+    /// assert!(hashindex.insert("foo".into(), 1).is_ok());
+    ///
+    /// let (key, value) = hashindex.peek_entry("foo", &guard).unwrap();
+    /// assert_eq!((key.as_ref(), *value), ("foo", 1));
+    /// ```
+    #[inline]
+    pub fn peek_entry<'g, Q>(&self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
     where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         self.read_entry(key, self.hash(key), &mut (), guard)
             .ok()
             .flatten()
-            .map(|(_, v)| v)
+            .filter(|(_, v)| !self.is_expired(v))
     }
 
     /// Peeks a key-value pair without acquiring locks.
     ///
-    /// Returns `None` if the key does not exist.
+    /// Returns `None` if the key does not exist or the entry has expired.
     ///
     /// This method is not linearizable since the entry can be removed while being read.
     ///
@@ -772,14 +1002,13 @@ where
         Q: Equivalent<K> + Hash + ?Sized,
     {
         let guard = Guard::new();
-        self.read_entry(key, self.hash(key), &mut (), &guard)
-            .ok()
-            .flatten()
-            .map(|(k, v)| reader(k, v))
+        self.peek_entry(key, &guard).map(|(k, v)| reader(k, v))
     }
 
     /// Returns `true` if the [`HashIndex`] contains a value for the specified key.
     ///
+    /// Expired entries are treated as absent.
+    ///
     /// # Examples
     ///
     /// ```
@@ -833,164 +1062,752 @@ where
     /// if they are not removed, however the same entry can be visited more than once if the
     /// [`HashIndex`] gets resized by another thread.
     ///
-    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// let future_insert = hashindex.insert_async(1, 0);
+    /// let future_retain = hashindex.retain_async(|k, v| *k == 1);
+    /// ```
+    #[inline]
+    pub async fn retain_async<F: FnMut(&K, &V) -> bool>(&self, mut pred: F) {
+        let mut removed = false;
+        let mut current_array_holder = self.array.get_shared(Acquire, &Guard::new());
+        while let Some(current_array) = current_array_holder.take() {
+            self.cleanse_old_array_async(&current_array).await;
+            for index in 0..current_array.num_buckets() {
+                loop {
+                    let mut async_wait = AsyncWait::default();
+                    let mut async_wait_pinned = Pin::new(&mut async_wait);
+                    {
+                        let guard = Guard::new();
+                        let bucket = current_array.bucket_mut(index);
+                        if let Ok(locker) =
+                            Locker::try_lock_or_wait(bucket, &mut async_wait_pinned, &guard)
+                        {
+                            if let Some(mut locker) = locker {
+                                let data_block_mut = current_array.data_block_mut(index);
+                                let mut entry_ptr = EntryPtr::new(&guard);
+                                while entry_ptr.move_to_next(&locker, &guard) {
+                                    let (k, v) = entry_ptr.get(data_block_mut);
+                                    if !pred(k, v) {
+                                        locker.mark_removed(&mut entry_ptr, &guard);
+                                        removed = true;
+                                    }
+                                }
+                            }
+                            break;
+                        };
+                    }
+                    async_wait_pinned.await;
+                }
+            }
+
+            if let Some(new_current_array) = self.array.get_shared(Acquire, &Guard::new()) {
+                if new_current_array.as_ptr() == current_array.as_ptr() {
+                    break;
+                }
+                current_array_holder.replace(new_current_array);
+                continue;
+            }
+            break;
+        }
+
+        if removed {
+            self.try_resize(0, &Guard::new());
+        }
+    }
+
+    /// Invokes the supplied function on every entry in parallel.
+    ///
+    /// The bucket array is partitioned across [`rayon`](https://crates.io/crates/rayon) worker
+    /// threads, and entries are read without acquiring any locks. Entries that have existed since
+    /// the invocation of the method are guaranteed to be visited if they are not removed, however
+    /// the same entry can be visited more than once if the [`HashIndex`] gets resized by another
+    /// thread. Expired entries are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    /// use std::sync::atomic::AtomicU64;
+    /// use std::sync::atomic::Ordering::Relaxed;
+    ///
+    /// let hashindex: HashIndex<u64, u64> = HashIndex::default();
+    ///
+    /// for k in 0..1024 {
+    ///     assert!(hashindex.insert(k, k).is_ok());
+    /// }
+    ///
+    /// let sum = AtomicU64::new(0);
+    /// hashindex.par_for_each(|_, v| {
+    ///     sum.fetch_add(*v, Relaxed);
+    /// });
+    /// assert_eq!(sum.load(Relaxed), 1023 * 512);
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_for_each<F: Fn(&K, &V) + Send + Sync>(&self, f: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+    {
+        if let Some(expiry) = self.expiry() {
+            self.par_for_each_entry(|k, v| {
+                if !expiry(v) {
+                    f(k, v);
+                }
+            });
+        } else {
+            self.par_for_each_entry(f);
+        }
+    }
+
+    /// Retains the entries specified by the predicate in parallel.
+    ///
+    /// The bucket array is partitioned across [`rayon`](https://crates.io/crates/rayon) worker
+    /// threads. Entries that have existed since the invocation of the method are guaranteed to be
+    /// visited if they are not removed, however the same entry can be visited more than once if the
+    /// [`HashIndex`] gets resized by another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// for k in 0..1024 {
+    ///     assert!(hashindex.insert(k, 0).is_ok());
+    /// }
+    ///
+    /// hashindex.par_retain(|k, _| k % 2 == 0);
+    ///
+    /// assert_eq!(hashindex.len(), 512);
+    /// assert!(hashindex.contains(&0));
+    /// assert!(!hashindex.contains(&1));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_retain<F: Fn(&K, &V) -> bool + Send + Sync>(&self, pred: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+    {
+        self.par_retain_entries(|k, v| pred(k, v));
+    }
+
+    /// Clears the [`HashIndex`] by removing all key-value pairs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// hashindex.clear();
+    ///
+    /// assert!(!hashindex.contains(&1));
+    /// ```
+    pub fn clear(&self) {
+        self.retain(|_, _| false);
+    }
+
+    /// Clears the [`HashIndex`] by removing all key-value pairs.
+    ///
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// let future_insert = hashindex.insert_async(1, 0);
+    /// let future_retain = hashindex.clear_async();
+    /// ```
+    pub async fn clear_async(&self) {
+        self.retain_async(|_, _| false).await;
+    }
+
+    /// Compacts the [`HashIndex`] by rebuilding the bucket array at a size fitting the number of
+    /// entries.
+    ///
+    /// Removed entries are not relocated to the new bucket array, and the memory they occupied is
+    /// reclaimed once the old bucket array becomes unreachable. Returns `false` if the
+    /// [`HashIndex`] is being resized by another thread or no bucket array has been allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// for k in 0..1024 {
+    ///     assert!(hashindex.insert(k, 0).is_ok());
+    /// }
+    /// hashindex.retain(|k, _| *k == 0);
+    ///
+    /// assert!(hashindex.compact());
+    /// assert_eq!(hashindex.capacity(), 64);
+    /// assert_eq!(hashindex.peek_with(&0, |_, v| *v), Some(0));
+    /// ```
+    #[inline]
+    pub fn compact(&self) -> bool {
+        self.try_compact(&Guard::new())
+    }
+
+    /// Compacts the [`HashIndex`] by rebuilding the bucket array at a size fitting the number of
+    /// entries.
+    ///
+    /// Returns `false` if the [`HashIndex`] is being resized by another task or no bucket array has
+    /// been allocated. It is an asynchronous method returning an `impl Future` for the caller to
+    /// await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// let future_insert = hashindex.insert_async(1, 0);
+    /// let future_compact = hashindex.compact_async();
+    /// ```
+    #[inline]
+    pub async fn compact_async(&self) -> bool {
+        let Some(current_array) = self.array.get_shared(Acquire, &Guard::new()) else {
+            return false;
+        };
+        self.cleanse_old_array_async(&current_array).await;
+        if !self.try_rebuild(&Guard::new()) {
+            return false;
+        }
+        let new_array = self.array.get_shared(Acquire, &Guard::new());
+        if let Some(current_array) = new_array {
+            self.cleanse_old_array_async(&current_array).await;
+        }
+        true
+    }
+
+    /// Returns the number of entries in the [`HashIndex`].
+    ///
+    /// It reads the entire metadata area of the bucket array to calculate the number of valid
+    /// entries, making its time complexity `O(N)`. Furthermore, it may overcount entries if an old
+    /// bucket array has yet to be dropped, and it counts expired entries that have yet to be
+    /// removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert_eq!(hashindex.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.num_entries(&Guard::new())
+    }
+
+    /// Returns `true` if the [`HashIndex`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.is_empty());
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert!(!hashindex.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        !self.has_entry(&Guard::new())
+    }
+
+    /// Returns the capacity of the [`HashIndex`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex_default: HashIndex<u64, u32> = HashIndex::default();
+    /// assert_eq!(hashindex_default.capacity(), 0);
+    ///
+    /// assert!(hashindex_default.insert(1, 0).is_ok());
+    /// assert_eq!(hashindex_default.capacity(), 64);
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1000);
+    /// assert_eq!(hashindex.capacity(), 1024);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.num_slots(&Guard::new())
+    }
+
+    /// Returns the current capacity range of the [`HashIndex`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert_eq!(hashindex.capacity_range(), 0..=(1_usize << (usize::BITS - 1)));
+    ///
+    /// let reserved = hashindex.reserve(1000);
+    /// assert_eq!(hashindex.capacity_range(), 1000..=(1_usize << (usize::BITS - 1)));
+    /// ```
+    #[inline]
+    pub fn capacity_range(&self) -> RangeInclusive<usize> {
+        self.minimum_capacity.load(Relaxed)..=self.maximum_capacity()
+    }
+
+    /// Returns a [`Statistics`] snapshot of the [`HashIndex`].
+    ///
+    /// It reads the entire metadata area of the bucket array, making its time complexity `O(N)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert!(hashindex.insert(2, 0).is_ok());
+    /// assert!(hashindex.remove(&1));
+    ///
+    /// let statistics = hashindex.statistics();
+    /// assert_eq!(statistics.capacity(), 64);
+    /// assert_eq!(statistics.num_entries(), 1);
+    /// assert!(statistics.num_removed_entries() <= 1);
+    /// ```
+    #[inline]
+    pub fn statistics(&self) -> Statistics {
+        let guard = Guard::new();
+        let mut statistics = Statistics::default();
+        if let Some(current_array) = self.array.load(Acquire, &guard).as_ref() {
+            statistics.capacity = current_array.num_entries();
+            statistics.num_buckets = current_array.num_buckets();
+            statistics.num_resizes = current_array.num_resizes();
+            let old_array_ptr = current_array.old_array(&guard);
+            if let Some(old_array) = old_array_ptr.as_ref() {
+                statistics.resizing = true;
+                for i in 0..old_array.num_buckets() {
+                    let bucket = old_array.bucket(i);
+                    statistics.num_entries += bucket.num_entries();
+                    statistics.num_removed_entries += bucket.num_removed_entries(&guard);
+                }
+            }
+            for i in 0..current_array.num_buckets() {
+                let bucket = current_array.bucket(i);
+                statistics.num_entries += bucket.num_entries();
+                statistics.num_removed_entries += bucket.num_removed_entries(&guard);
+            }
+        }
+        statistics
+    }
+
+    /// Returns the index of the bucket that may contain the key.
+    ///
+    /// The method returns the index of the bucket associated with the key. The number of buckets
+    /// can be calculated by dividing `32` into the capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1024);
+    ///
+    /// let bucket_index = hashindex.bucket_index(&11);
+    /// assert!(bucket_index < hashindex.capacity() / 32);
+    /// ```
+    #[inline]
+    pub fn bucket_index<Q>(&self, key: &Q) -> usize
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        self.calculate_bucket_index(key)
+    }
+
+    /// Returns an [`Iter`].
+    ///
+    /// It is guaranteed to go through all the key-value pairs pertaining in the [`HashIndex`]
+    /// at the moment, however the same key-value pair can be visited more than once if the
+    /// [`HashIndex`] is being resized. Expired entries are skipped.
+    ///
+    /// It requires the user to supply a reference to a [`Guard`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let mut iter = hashindex.iter(&guard);
+    /// let entry_ref = iter.next().unwrap();
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// for iter in hashindex.iter(&guard) {
+    ///     assert_eq!(iter, (&1, &0));
+    /// }
+    ///
+    /// drop(hashindex);
+    ///
+    /// assert_eq!(entry_ref, (&1, &0));
+    /// ```
+    #[inline]
+    pub fn iter<'h, 'g>(&'h self, guard: &'g Guard) -> Iter<'h, 'g, K, V, H> {
+        Iter {
+            hashindex: self,
+            current_array: None,
+            current_index: 0,
+            current_bucket: None,
+            current_entry_ptr: EntryPtr::new(guard),
+            guard,
+        }
+    }
+
+    /// Returns a [`Keys`] iterating over the keys of the [`HashIndex`].
+    ///
+    /// It requires the user to supply a reference to a [`Guard`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert!(hashindex.insert(2, 1).is_ok());
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let mut keys: Vec<u64> = hashindex.keys(&guard).copied().collect();
+    /// keys.sort_unstable();
+    /// assert_eq!(keys, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn keys<'h, 'g>(&'h self, guard: &'g Guard) -> Keys<'h, 'g, K, V, H> {
+        Keys {
+            iter: self.iter(guard),
+        }
+    }
+
+    /// Returns a [`Values`] iterating over the values of the [`HashIndex`].
+    ///
+    /// It requires the user to supply a reference to a [`Guard`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::HashIndex;
+    ///
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert(1, 7).is_ok());
+    /// assert!(hashindex.insert(2, 11).is_ok());
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// assert_eq!(hashindex.values(&guard).sum::<u32>(), 18);
+    /// ```
+    #[inline]
+    pub fn values<'h, 'g>(&'h self, guard: &'g Guard) -> Values<'h, 'g, K, V, H> {
+        Values {
+            iter: self.iter(guard),
+        }
+    }
+
+    /// Computes the hash value of each key.
+    fn prepare_entries<I: IntoIterator<Item = (K, V)>>(&self, entries: I) -> Vec<(u64, K, V)> {
+        entries
+            .into_iter()
+            .map(|(k, v)| (self.hash(&k), k, v))
+            .collect()
+    }
+
+    /// Sorts the entries in descending order of their bucket indexes in the current array.
+    fn sort_entries(&self, entries: &mut [(u64, K, V)], guard: &Guard) {
+        if !entries.is_empty() {
+            let current_array = self.get_current_array(guard);
+            entries.sort_unstable_by_key(|e| Reverse(current_array.calculate_bucket_index(e.0)));
+        }
+    }
+
+    /// Returns `true` if the value has expired.
+    ///
+    /// Values never expire unless the values are [`Expiring`] and expiration has been enabled.
+    #[inline]
+    fn is_expired(&self, val: &V) -> bool {
+        self.expiry().map_or(false, |expiry| expiry(val))
+    }
+
+    /// Returns the function telling whether a value has expired if expiration has been enabled.
+    ///
+    /// The function pointer is the only data shared through the field, therefore a `Relaxed`
+    /// load suffices, and the indirect call is only made once expiration has been enabled.
+    #[inline]
+    fn expiry(&self) -> Option<fn(&V) -> bool> {
+        let expiry = self.expiry.load(Relaxed);
+        if expiry.is_null() {
+            None
+        } else {
+            // Only `fn(&V) -> bool` is stored in the field.
+            Some(unsafe { transmute::<*mut (), fn(&V) -> bool>(expiry) })
+        }
+    }
+
+    /// Clears the old array asynchronously.
+    async fn cleanse_old_array_async(&self, current_array: &BucketArray<K, V, (), OPTIMISTIC>) {
+        while current_array.has_old_array() {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if self.incremental_rehash::<K, _, false>(
+                current_array,
+                &mut async_wait_pinned,
+                &Guard::new(),
+            ) == Ok(true)
+            {
+                break;
+            }
+            async_wait_pinned.await;
+        }
+    }
+}
+
+impl<K, V, H> HashIndex<K, Expiring<V>, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+    /// Inserts a key-value pair into the [`HashIndex`] that expires after the specified duration.
+    ///
+    /// An expired entry associated with the key is replaced with the new one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied key-value pair if the key exists and has yet to
+    /// expire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Expiring;
+    /// use scc::HashIndex;
+    /// use std::time::Duration;
+    ///
+    /// let hashindex: HashIndex<u64, Expiring<u32>> = HashIndex::default();
+    ///
+    /// assert!(hashindex.insert_with_ttl(1, 0, Duration::ZERO).is_ok());
+    /// assert!(hashindex.insert_with_ttl(1, 1, Duration::from_secs(60)).is_ok());
+    /// assert_eq!(
+    ///     hashindex.insert_with_ttl(1, 2, Duration::from_secs(60)).unwrap_err(),
+    ///     (1, 2)
+    /// );
+    /// ```
+    #[inline]
+    pub fn insert_with_ttl(&self, key: K, val: V, ttl: Duration) -> Result<(), (K, V)> {
+        self.enable_expiry();
+        let expiring = Expiring::with_ttl(val, ttl);
+        match self.entry(key) {
+            Entry::Occupied(o) => {
+                if o.get().is_expired() {
+                    o.update(expiring);
+                    Ok(())
+                } else {
+                    Err((o.key().clone(), expiring.into_inner()))
+                }
+            }
+            Entry::Vacant(v) => {
+                v.insert_entry(expiring);
+                Ok(())
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the [`HashIndex`] that expires after the specified duration.
+    ///
+    /// An expired entry associated with the key is replaced with the new one. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied key-value pair if the key exists and has yet to
+    /// expire.
     ///
     /// # Examples
     ///
     /// ```
+    /// use scc::Expiring;
     /// use scc::HashIndex;
+    /// use std::time::Duration;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
-    ///
-    /// let future_insert = hashindex.insert_async(1, 0);
-    /// let future_retain = hashindex.retain_async(|k, v| *k == 1);
+    /// let hashindex: HashIndex<u64, Expiring<u32>> = HashIndex::default();
+    /// let future_insert = hashindex.insert_with_ttl_async(11, 17, Duration::from_secs(60));
     /// ```
     #[inline]
-    pub async fn retain_async<F: FnMut(&K, &V) -> bool>(&self, mut pred: F) {
-        let mut removed = false;
-        let mut current_array_holder = self.array.get_shared(Acquire, &Guard::new());
-        while let Some(current_array) = current_array_holder.take() {
-            self.cleanse_old_array_async(&current_array).await;
-            for index in 0..current_array.num_buckets() {
-                loop {
-                    let mut async_wait = AsyncWait::default();
-                    let mut async_wait_pinned = Pin::new(&mut async_wait);
-                    {
-                        let guard = Guard::new();
-                        let bucket = current_array.bucket_mut(index);
-                        if let Ok(locker) =
-                            Locker::try_lock_or_wait(bucket, &mut async_wait_pinned, &guard)
-                        {
-                            if let Some(mut locker) = locker {
-                                let data_block_mut = current_array.data_block_mut(index);
-                                let mut entry_ptr = EntryPtr::new(&guard);
-                                while entry_ptr.move_to_next(&locker, &guard) {
-                                    let (k, v) = entry_ptr.get(data_block_mut);
-                                    if !pred(k, v) {
-                                        locker.mark_removed(&mut entry_ptr, &guard);
-                                        removed = true;
-                                    }
-                                }
-                            }
-                            break;
-                        };
-                    }
-                    async_wait_pinned.await;
+    pub async fn insert_with_ttl_async(&self, key: K, val: V, ttl: Duration) -> Result<(), (K, V)> {
+        self.enable_expiry();
+        let expiring = Expiring::with_ttl(val, ttl);
+        match self.entry_async(key).await {
+            Entry::Occupied(o) => {
+                if o.get().is_expired() {
+                    o.update(expiring);
+                    Ok(())
+                } else {
+                    Err((o.key().clone(), expiring.into_inner()))
                 }
             }
-
-            if let Some(new_current_array) = self.array.get_shared(Acquire, &Guard::new()) {
-                if new_current_array.as_ptr() == current_array.as_ptr() {
-                    break;
-                }
-                current_array_holder.replace(new_current_array);
-                continue;
+            Entry::Vacant(v) => {
+                v.insert_entry(expiring);
+                Ok(())
             }
-            break;
-        }
-
-        if removed {
-            self.try_resize(0, &Guard::new());
         }
     }
 
-    /// Clears the [`HashIndex`] by removing all key-value pairs.
+    /// Removes all the expired entries from the [`HashIndex`].
+    ///
+    /// Readers that have observed the removed entries can safely keep accessing them until their
+    /// [`Guard`] is dropped.
     ///
     /// # Examples
     ///
     /// ```
+    /// use scc::Expiring;
     /// use scc::HashIndex;
+    /// use std::time::Duration;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
+    /// let hashindex: HashIndex<u64, Expiring<u32>> = HashIndex::default();
     ///
-    /// assert!(hashindex.insert(1, 0).is_ok());
-    /// hashindex.clear();
+    /// assert!(hashindex.insert_with_ttl(1, 10, Duration::from_secs(60)).is_ok());
+    /// assert!(hashindex.insert_with_ttl(2, 20, Duration::ZERO).is_ok());
     ///
-    /// assert!(!hashindex.contains(&1));
+    /// hashindex.remove_expired();
+    /// assert!(hashindex.contains(&1));
+    /// assert!(!hashindex.contains(&2));
     /// ```
-    pub fn clear(&self) {
-        self.retain(|_, _| false);
+    #[inline]
+    pub fn remove_expired(&self) {
+        let now = Instant::now();
+        self.retain(|_, expiring| !expiring.is_expired_at(now));
     }
 
-    /// Clears the [`HashIndex`] by removing all key-value pairs.
+    /// Removes all the expired entries from the [`HashIndex`].
     ///
     /// It is an asynchronous method returning an `impl Future` for the caller to await.
     ///
     /// # Examples
     ///
     /// ```
+    /// use scc::Expiring;
     /// use scc::HashIndex;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
-    ///
-    /// let future_insert = hashindex.insert_async(1, 0);
-    /// let future_retain = hashindex.clear_async();
+    /// let hashindex: HashIndex<u64, Expiring<u32>> = HashIndex::default();
+    /// let future_remove_expired = hashindex.remove_expired_async();
     /// ```
-    pub async fn clear_async(&self) {
-        self.retain_async(|_, _| false).await;
+    #[inline]
+    pub async fn remove_expired_async(&self) {
+        let now = Instant::now();
+        self.retain_async(|_, expiring| !expiring.is_expired_at(now))
+            .await;
     }
 
-    /// Returns the number of entries in the [`HashIndex`].
-    ///
-    /// It reads the entire metadata area of the bucket array to calculate the number of valid
-    /// entries, making its time complexity `O(N)`. Furthermore, it may overcount entries if an old
-    /// bucket array has yet to be dropped.
+    /// Makes the read methods of the [`HashIndex`] treat expired entries as absent.
+    fn enable_expiry(&self) {
+        if self.expiry.load(Relaxed).is_null() {
+            let expiry: fn(&Expiring<V>) -> bool = Expiring::is_expired;
+            self.expiry.store(expiry as *mut (), Relaxed);
+        }
+    }
+}
+
+impl Statistics {
+    /// Returns the capacity of the current bucket array.
     ///
     /// # Examples
     ///
     /// ```
     /// use scc::HashIndex;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
-    ///
-    /// assert!(hashindex.insert(1, 0).is_ok());
-    /// assert_eq!(hashindex.len(), 1);
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1000);
+    /// assert_eq!(hashindex.statistics().capacity(), 1024);
     /// ```
     #[inline]
-    pub fn len(&self) -> usize {
-        self.num_entries(&Guard::new())
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
     }
 
-    /// Returns `true` if the [`HashIndex`] is empty.
+    /// Returns the number of buckets in the current bucket array.
     ///
     /// # Examples
     ///
     /// ```
     /// use scc::HashIndex;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
-    ///
-    /// assert!(hashindex.is_empty());
-    /// assert!(hashindex.insert(1, 0).is_ok());
-    /// assert!(!hashindex.is_empty());
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1024);
+    /// assert_eq!(hashindex.statistics().num_buckets(), 32);
     /// ```
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        !self.has_entry(&Guard::new())
+    #[must_use]
+    pub const fn num_buckets(&self) -> usize {
+        self.num_buckets
     }
 
-    /// Returns the capacity of the [`HashIndex`].
+    /// Returns the number of reachable entries.
+    ///
+    /// Entries in an old bucket array that has yet to be dropped are also counted, therefore the
+    /// figure may be overcounted while the [`HashIndex`] is being resized.
     ///
     /// # Examples
     ///
     /// ```
     /// use scc::HashIndex;
     ///
-    /// let hashindex_default: HashIndex<u64, u32> = HashIndex::default();
-    /// assert_eq!(hashindex_default.capacity(), 0);
-    ///
-    /// assert!(hashindex_default.insert(1, 0).is_ok());
-    /// assert_eq!(hashindex_default.capacity(), 64);
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1000);
-    /// assert_eq!(hashindex.capacity(), 1024);
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert_eq!(hashindex.statistics().num_entries(), 1);
     /// ```
     #[inline]
-    pub fn capacity(&self) -> usize {
-        self.num_slots(&Guard::new())
+    #[must_use]
+    pub const fn num_entries(&self) -> usize {
+        self.num_entries
     }
 
-    /// Returns the current capacity range of the [`HashIndex`].
+    /// Returns the number of removed entries awaiting reclamation.
+    ///
+    /// Removed entries in a [`HashIndex`] keep occupying their slots until no readers can access
+    /// them.
     ///
     /// # Examples
     ///
@@ -999,98 +1816,55 @@ where
     ///
     /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
     ///
-    /// assert_eq!(hashindex.capacity_range(), 0..=(1_usize << (usize::BITS - 1)));
-    ///
-    /// let reserved = hashindex.reserve(1000);
-    /// assert_eq!(hashindex.capacity_range(), 1000..=(1_usize << (usize::BITS - 1)));
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert!(hashindex.insert(2, 0).is_ok());
+    /// assert!(hashindex.remove(&1));
+    /// assert!(hashindex.statistics().num_removed_entries() <= 1);
     /// ```
     #[inline]
-    pub fn capacity_range(&self) -> RangeInclusive<usize> {
-        self.minimum_capacity.load(Relaxed)..=self.maximum_capacity()
+    #[must_use]
+    pub const fn num_removed_entries(&self) -> usize {
+        self.num_removed_entries
     }
 
-    /// Returns the index of the bucket that may contain the key.
+    /// Returns the number of times the bucket array has been replaced since it was allocated.
     ///
-    /// The method returns the index of the bucket associated with the key. The number of buckets
-    /// can be calculated by dividing `32` into the capacity.
+    /// The figure is reset when the [`HashIndex`] deallocates the bucket array after all the
+    /// entries are removed.
     ///
     /// # Examples
     ///
     /// ```
     /// use scc::HashIndex;
     ///
-    /// let hashindex: HashIndex<u64, u32> = HashIndex::with_capacity(1024);
+    /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
     ///
-    /// let bucket_index = hashindex.bucket_index(&11);
-    /// assert!(bucket_index < hashindex.capacity() / 32);
+    /// assert!(hashindex.insert(1, 0).is_ok());
+    /// assert_eq!(hashindex.statistics().num_resizes(), 0);
+    ///
+    /// assert!(hashindex.compact());
+    /// assert_eq!(hashindex.statistics().num_resizes(), 1);
     /// ```
     #[inline]
-    pub fn bucket_index<Q>(&self, key: &Q) -> usize
-    where
-        Q: Equivalent<K> + Hash + ?Sized,
-    {
-        self.calculate_bucket_index(key)
+    #[must_use]
+    pub const fn num_resizes(&self) -> usize {
+        self.num_resizes
     }
 
-    /// Returns an [`Iter`].
-    ///
-    /// It is guaranteed to go through all the key-value pairs pertaining in the [`HashIndex`]
-    /// at the moment, however the same key-value pair can be visited more than once if the
-    /// [`HashIndex`] is being resized.
-    ///
-    /// It requires the user to supply a reference to a [`Guard`].
+    /// Returns `true` if an old bucket array was attached to the current one.
     ///
     /// # Examples
     ///
     /// ```
-    /// use scc::ebr::Guard;
     /// use scc::HashIndex;
     ///
     /// let hashindex: HashIndex<u64, u32> = HashIndex::default();
-    ///
-    /// assert!(hashindex.insert(1, 0).is_ok());
-    ///
-    /// let guard = Guard::new();
-    ///
-    /// let mut iter = hashindex.iter(&guard);
-    /// let entry_ref = iter.next().unwrap();
-    /// assert_eq!(iter.next(), None);
-    ///
-    /// for iter in hashindex.iter(&guard) {
-    ///     assert_eq!(iter, (&1, &0));
-    /// }
-    ///
-    /// drop(hashindex);
-    ///
-    /// assert_eq!(entry_ref, (&1, &0));
+    /// assert!(!hashindex.statistics().is_resizing());
     /// ```
     #[inline]
-    pub fn iter<'h, 'g>(&'h self, guard: &'g Guard) -> Iter<'h, 'g, K, V, H> {
-        Iter {
-            hashindex: self,
-            current_array: None,
-            current_index: 0,
-            current_bucket: None,
-            current_entry_ptr: EntryPtr::new(guard),
-            guard,
-        }
-    }
-
-    /// Clears the old array asynchronously.
-    async fn cleanse_old_array_async(&self, current_array: &BucketArray<K, V, (), OPTIMISTIC>) {
-        while current_array.has_old_array() {
-            let mut async_wait = AsyncWait::default();
-            let mut async_wait_pinned = Pin::new(&mut async_wait);
-            if self.incremental_rehash::<K, _, false>(
-                current_array,
-                &mut async_wait_pinned,
-                &Guard::new(),
-            ) == Ok(true)
-            {
-                break;
-            }
-            async_wait_pinned.await;
-        }
+    #[must_use]
+    pub const fn is_resizing(&self) -> bool {
+        self.resizing
     }
 }
 
@@ -1103,7 +1877,9 @@ where
     #[inline]
     fn clone(&self) -> Self {
         let self_clone = Self::with_capacity_and_hasher(self.capacity(), self.hasher().clone());
-        for (k, v) in self.iter(&Guard::new()) {
+        self_clone.expiry.store(self.expiry.load(Relaxed), Relaxed);
+        let guard = Guard::new();
+        for (k, v) in self.iter(&guard) {
             let _reuslt = self_clone.insert(k.clone(), v.clone());
         }
         self_clone
@@ -1892,12 +2668,14 @@ where
         loop {
             if let Some(bucket) = self.current_bucket.take() {
                 // Go to the next entry in the bucket.
-                if self.current_entry_ptr.move_to_next(bucket, self.guard) {
+                while self.current_entry_ptr.move_to_next(bucket, self.guard) {
                     let (k, v) = self
                         .current_entry_ptr
                         .get(array.data_block(self.current_index));
-                    self.current_bucket.replace(bucket);
-                    return Some((k, v));
+                    if !self.hashindex.is_expired(v) {
+                        self.current_bucket.replace(bucket);
+                        return Some((k, v));
+                    }
                 }
             }
             self.current_index += 1;
@@ -1967,3 +2745,87 @@ where
     H: BuildHasher + UnwindSafe,
 {
 }
+
+impl<'h, 'g, K, V, H> Debug for Keys<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keys").field("iter", &self.iter).finish()
+    }
+}
+
+impl<'h, 'g, K, V, H> Iterator for Keys<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+    type Item = &'g K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(k, _)| k)
+    }
+}
+
+impl<'h, 'g, K, V, H> FusedIterator for Keys<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+}
+
+impl<'h, 'g, K, V, H> UnwindSafe for Keys<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash + UnwindSafe,
+    V: 'static + Clone + UnwindSafe,
+    H: BuildHasher + UnwindSafe,
+{
+}
+
+impl<'h, 'g, K, V, H> Debug for Values<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Values").field("iter", &self.iter).finish()
+    }
+}
+
+impl<'h, 'g, K, V, H> Iterator for Values<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+    type Item = &'g V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, v)| v)
+    }
+}
+
+impl<'h, 'g, K, V, H> FusedIterator for Values<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash,
+    V: 'static + Clone,
+    H: BuildHasher,
+{
+}
+
+impl<'h, 'g, K, V, H> UnwindSafe for Values<'h, 'g, K, V, H>
+where
+    K: 'static + Clone + Eq + Hash + UnwindSafe,
+    V: 'static + Clone + UnwindSafe,
+    H: BuildHasher + UnwindSafe,
+{
+}
@@ -401,7 +401,7 @@ where
     pub fn any_entry<P: FnMut(&K, &V) -> bool>(&self, pred: P) -> Option<OccupiedEntry<K, V, H>> {
         let guard = Guard::new();
         let prolonged_guard = self.prolonged_guard_ref(&guard);
-        let locked_entry = self.find_entry(pred, prolonged_guard)?;
+        let locked_entry = self.find_entry(pred, 0, prolonged_guard)?;
         Some(OccupiedEntry {
             hashmap: self,
             locked_entry,
@@ -444,6 +444,31 @@ where
         None
     }
 
+    /// Gets an [`OccupiedEntry`], searching the buckets from the specified bucket index.
+    #[inline]
+    pub(crate) fn first_entry_from(&self, start_index: usize) -> Option<OccupiedEntry<K, V, H>> {
+        let guard = Guard::new();
+        let prolonged_guard = self.prolonged_guard_ref(&guard);
+        let locked_entry = self.find_entry(|_, _| true, start_index, prolonged_guard)?;
+        Some(OccupiedEntry {
+            hashmap: self,
+            locked_entry,
+        })
+    }
+
+    /// Gets an [`OccupiedEntry`], searching the buckets from the specified bucket index.
+    #[inline]
+    pub(crate) async fn first_entry_from_async(
+        &self,
+        start_index: usize,
+    ) -> Option<OccupiedEntry<K, V, H>> {
+        let locked_entry = LockedEntry::first_entry_from_async(self, start_index).await?;
+        Some(OccupiedEntry {
+            hashmap: self,
+            locked_entry,
+        })
+    }
+
     /// Inserts a key-value pair into the [`HashMap`].
     ///
     /// # Errors
@@ -1427,7 +1452,10 @@ where
     }
 
     /// Clears the old array asynchronously.
-    async fn cleanse_old_array_async(&self, current_array: &BucketArray<K, V, (), SEQUENTIAL>) {
+    pub(super) async fn cleanse_old_array_async(
+        &self,
+        current_array: &BucketArray<K, V, (), SEQUENTIAL>,
+    ) {
         while current_array.has_old_array() {
             let mut async_wait = AsyncWait::default();
             let mut async_wait_pinned = Pin::new(&mut async_wait);
@@ -1915,6 +1943,18 @@ where
             .1
     }
 
+    /// Replaces the key of the entry with an equivalent key, and returns the old key.
+    ///
+    /// The supplied key must be equal to the old key, otherwise the [`HashMap`] becomes corrupt.
+    #[inline]
+    pub(crate) fn replace_key(&mut self, key: K) -> K {
+        let entry = self.locked_entry.entry_ptr.get_mut(
+            self.locked_entry.data_block_mut,
+            &mut self.locked_entry.locker,
+        );
+        replace(&mut entry.0, key)
+    }
+
     /// Sets the value of the entry, and returns the old value.
     ///
     /// # Examples
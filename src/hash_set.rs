@@ -1,10 +1,17 @@
 //! [`HashSet`] is a concurrent and asynchronous hash set.
 
+use super::ebr::Guard;
+use super::hash_table::bucket::SEQUENTIAL;
+use super::hash_table::{BucketCursor, HashTable};
+use super::wait_queue::AsyncWait;
 use super::{Equivalent, HashMap};
+use std::cell::Cell;
 use std::collections::hash_map::RandomState;
 use std::fmt::{self, Debug};
-use std::hash::{BuildHasher, Hash};
+use std::hash::{BuildHasher, Hash, Hasher};
 use std::ops::RangeInclusive;
+use std::pin::Pin;
+use std::sync::atomic::Ordering::Acquire;
 
 /// Scalable concurrent hash set.
 ///
@@ -157,6 +164,60 @@ where
         self.map.insert_async(key, ()).await.map_err(|(k, ())| k)
     }
 
+    /// Inserts a key into the [`HashSet`], replacing an existing equal key.
+    ///
+    /// Returns the replaced key if an equal key existed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<String> = HashSet::default();
+    ///
+    /// assert!(hashset.replace("one".to_string()).is_none());
+    /// assert_eq!(hashset.replace("one".to_string()).unwrap(), "one");
+    /// assert_eq!(hashset.len(), 1);
+    /// ```
+    #[inline]
+    pub fn replace(&self, mut key: K) -> Option<K> {
+        loop {
+            if let Some(mut entry) = self.map.get(&key) {
+                return Some(entry.replace_key(key));
+            }
+            match self.map.insert(key, ()) {
+                Ok(()) => return None,
+                Err((k, ())) => key = k,
+            }
+        }
+    }
+
+    /// Inserts a key into the [`HashSet`], replacing an existing equal key.
+    ///
+    /// Returns the replaced key if an equal key existed. It is an asynchronous method returning
+    /// an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    /// let future_replace = hashset.replace_async(11);
+    /// ```
+    #[inline]
+    pub async fn replace_async(&self, mut key: K) -> Option<K> {
+        loop {
+            if let Some(mut entry) = self.map.get_async(&key).await {
+                return Some(entry.replace_key(key));
+            }
+            match self.map.insert_async(key, ()).await {
+                Ok(()) => return None,
+                Err((k, ())) => key = k,
+            }
+        }
+    }
+
     /// Removes a key if the key exists.
     ///
     /// Returns `None` if the key does not exist.
@@ -205,6 +266,98 @@ where
             .map(|(k, ())| k)
     }
 
+    /// Removes a key if the key exists, and returns the key stored in the [`HashSet`].
+    ///
+    /// It is equivalent to [`remove`](Self::remove), and returns `None` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<String> = HashSet::default();
+    ///
+    /// assert!(hashset.insert("one".to_string()).is_ok());
+    /// assert_eq!(hashset.take("one").unwrap(), "one");
+    /// assert!(hashset.take("one").is_none());
+    /// ```
+    #[inline]
+    pub fn take<Q>(&self, key: &Q) -> Option<K>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        self.remove(key)
+    }
+
+    /// Removes a key if the key exists, and returns the key stored in the [`HashSet`].
+    ///
+    /// It is equivalent to [`remove_async`](Self::remove_async), and returns `None` if the key
+    /// does not exist. It is an asynchronous method returning an `impl Future` for the caller to
+    /// await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    /// let future_insert = hashset.insert_async(11);
+    /// let future_take = hashset.take_async(&11);
+    /// ```
+    #[inline]
+    pub async fn take_async<Q>(&self, key: &Q) -> Option<K>
+    where
+        Q: Equivalent<K> + Hash + ?Sized,
+    {
+        self.remove_async(key).await
+    }
+
+    /// Removes an arbitrary key from the [`HashSet`], and returns it.
+    ///
+    /// The search for a key starts from a randomly chosen bucket in order to reduce contention
+    /// among threads popping keys concurrently. Returns `None` if the [`HashSet`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    ///
+    /// assert!(hashset.pop_any().is_none());
+    /// assert!(hashset.insert(1).is_ok());
+    /// assert_eq!(hashset.pop_any(), Some(1));
+    /// assert!(hashset.is_empty());
+    /// ```
+    #[inline]
+    pub fn pop_any(&self) -> Option<K> {
+        let entry = self.map.first_entry_from(Self::random_bucket_index())?;
+        Some(entry.remove_entry().0)
+    }
+
+    /// Removes an arbitrary key from the [`HashSet`], and returns it.
+    ///
+    /// The search for a key starts from a randomly chosen bucket in order to reduce contention
+    /// among tasks popping keys concurrently. Returns `None` if the [`HashSet`] is empty. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    /// let future_pop = hashset.pop_any_async();
+    /// ```
+    #[inline]
+    pub async fn pop_any_async(&self) -> Option<K> {
+        let entry = self
+            .map
+            .first_entry_from_async(Self::random_bucket_index())
+            .await?;
+        Some(entry.remove_entry().0)
+    }
+
     /// Removes a key if the key exists and the given condition is met.
     ///
     /// Returns `None` if the key does not exist or the condition was not met.
@@ -489,6 +642,67 @@ where
         self.map.retain_async(|k, ()| filter(k)).await;
     }
 
+    /// Invokes the supplied function on every key in parallel.
+    ///
+    /// The bucket array is partitioned across [`rayon`](https://crates.io/crates/rayon) worker
+    /// threads, and each bucket is shared-locked while its keys are being visited. Keys that have
+    /// existed since the invocation of the method are guaranteed to be visited if they are not
+    /// removed, however the same key can be visited more than once if the [`HashSet`] gets resized
+    /// by another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    /// use std::sync::atomic::AtomicU64;
+    /// use std::sync::atomic::Ordering::Relaxed;
+    ///
+    /// let hashset: HashSet<u64> = (0..1024).collect();
+    ///
+    /// let sum = AtomicU64::new(0);
+    /// hashset.par_for_each(|k| {
+    ///     sum.fetch_add(*k, Relaxed);
+    /// });
+    /// assert_eq!(sum.load(Relaxed), 1023 * 512);
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_for_each<F: Fn(&K) + Send + Sync>(&self, f: F)
+    where
+        K: Send + Sync,
+    {
+        self.map.par_for_each_entry(|k, ()| f(k));
+    }
+
+    /// Retains the keys specified by the predicate in parallel.
+    ///
+    /// The bucket array is partitioned across [`rayon`](https://crates.io/crates/rayon) worker
+    /// threads. Keys that have existed since the invocation of the method are guaranteed to be
+    /// visited if they are not removed, however the same key can be visited more than once if the
+    /// [`HashSet`] gets resized by another thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = (0..1024).collect();
+    ///
+    /// hashset.par_retain(|k| k % 2 == 0);
+    ///
+    /// assert_eq!(hashset.len(), 512);
+    /// assert!(hashset.contains(&0));
+    /// assert!(!hashset.contains(&1));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_retain<F: Fn(&K) -> bool + Send + Sync>(&self, pred: F)
+    where
+        K: Send + Sync,
+    {
+        self.map.par_retain_entries(|k, ()| pred(k));
+    }
+
     /// Clears the [`HashSet`] by removing all keys.
     ///
     /// # Examples
@@ -606,6 +820,65 @@ where
         self.map.capacity_range()
     }
 
+    /// Shrinks the capacity of the [`HashSet`] to fit the number of keys.
+    ///
+    /// The bucket array is rebuilt at a size fitting the number of keys, and the capacity does not
+    /// shrink below the minimum capacity of the [`HashSet`]. Returns `false` if the [`HashSet`] is
+    /// being resized by another thread or no bucket array has been allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    ///
+    /// for k in 0..1024 {
+    ///     assert!(hashset.insert(k).is_ok());
+    /// }
+    /// hashset.retain(|k| *k == 0);
+    ///
+    /// assert!(hashset.shrink_to_fit());
+    /// assert_eq!(hashset.capacity(), 64);
+    /// assert!(hashset.contains(&0));
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&self) -> bool {
+        self.map.try_compact(&Guard::new())
+    }
+
+    /// Shrinks the capacity of the [`HashSet`] to fit the number of keys.
+    ///
+    /// Returns `false` if the [`HashSet`] is being resized by another task or no bucket array has
+    /// been allocated. It is an asynchronous method returning an `impl Future` for the caller to
+    /// await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = HashSet::default();
+    ///
+    /// let future_insert = hashset.insert_async(1);
+    /// let future_shrink = hashset.shrink_to_fit_async();
+    /// ```
+    #[inline]
+    pub async fn shrink_to_fit_async(&self) -> bool {
+        let Some(current_array) = self.map.bucket_array().get_shared(Acquire, &Guard::new()) else {
+            return false;
+        };
+        self.map.cleanse_old_array_async(&current_array).await;
+        if !self.map.try_rebuild(&Guard::new()) {
+            return false;
+        }
+        let new_array = self.map.bucket_array().get_shared(Acquire, &Guard::new());
+        if let Some(current_array) = new_array {
+            self.map.cleanse_old_array_async(&current_array).await;
+        }
+        true
+    }
+
     /// Returns the index of the bucket that may contain the key.
     ///
     /// The method returns the index of the bucket associated with the key. The number of buckets
@@ -628,6 +901,383 @@ where
     {
         self.map.bucket_index(key)
     }
+
+    /// Returns a random bucket index to start searching for an entry from.
+    #[allow(clippy::cast_possible_truncation)]
+    fn random_bucket_index() -> usize {
+        thread_local! {
+            static STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+        }
+        STATE.with(|state| {
+            // `xorshift64`.
+            let mut x = state.get();
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            state.set(x);
+            x as usize
+        })
+    }
+}
+
+impl<K, H> HashSet<K, H>
+where
+    K: Clone + Eq + Hash,
+    H: BuildHasher,
+{
+    /// Inserts all the keys in `other` into the [`HashSet`].
+    ///
+    /// Keys that have existed in `other` since the invocation of the method are guaranteed to be
+    /// inserted if they are not removed from `other`.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in `other` are cloned bucket by bucket, and no locks on `other` are held while the keys
+    /// are being inserted, therefore concurrent set operations on the same pair of [`HashSet`]
+    /// instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// assert!(hashset1.insert(1).is_ok());
+    /// assert!(hashset2.insert(2).is_ok());
+    ///
+    /// hashset1.union_with(&hashset2);
+    /// assert!(hashset1.contains(&1));
+    /// assert!(hashset1.contains(&2));
+    /// ```
+    #[inline]
+    pub fn union_with<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        other.for_each_bucket(|keys| {
+            for key in keys.drain(..) {
+                let _: Result<(), K> = self.insert(key);
+            }
+        });
+    }
+
+    /// Inserts all the keys in `other` into the [`HashSet`].
+    ///
+    /// Keys in `other` are cloned bucket by bucket, and no locks on `other` are held while the keys
+    /// are being inserted. It is an asynchronous method returning an `impl Future` for the caller
+    /// to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// let future_union = hashset1.union_with_async(&hashset2);
+    /// ```
+    #[inline]
+    pub async fn union_with_async<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        let mut cursor = (None, 0);
+        let mut keys = Vec::new();
+        while other
+            .clone_next_bucket_keys_async(&mut cursor, &mut keys)
+            .await
+        {
+            for key in keys.drain(..) {
+                let _: Result<(), K> = self.insert_async(key).await;
+            }
+        }
+    }
+
+    /// Removes all the keys that do not exist in `other` from the [`HashSet`].
+    ///
+    /// Keys that have existed in the [`HashSet`] since the invocation of the method are guaranteed
+    /// to be visited if they are not removed.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in the [`HashSet`] are cloned bucket by bucket, and no locks on the [`HashSet`] are held
+    /// while `other` is being searched for the keys, therefore concurrent set operations on the
+    /// same pair of [`HashSet`] instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// assert!(hashset1.insert(1).is_ok());
+    /// assert!(hashset1.insert(2).is_ok());
+    /// assert!(hashset2.insert(2).is_ok());
+    ///
+    /// hashset1.intersection_with(&hashset2);
+    /// assert!(!hashset1.contains(&1));
+    /// assert!(hashset1.contains(&2));
+    /// ```
+    #[inline]
+    pub fn intersection_with<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        self.for_each_bucket(|keys| {
+            for key in keys.drain(..) {
+                if !other.contains(&key) {
+                    self.remove(&key);
+                }
+            }
+        });
+    }
+
+    /// Removes all the keys that do not exist in `other` from the [`HashSet`].
+    ///
+    /// Keys in the [`HashSet`] are cloned bucket by bucket, and no locks on the [`HashSet`] are held
+    /// while `other` is being searched for the keys. It is an asynchronous method returning an
+    /// `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// let future_intersection = hashset1.intersection_with_async(&hashset2);
+    /// ```
+    #[inline]
+    pub async fn intersection_with_async<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        let mut cursor = (None, 0);
+        let mut keys = Vec::new();
+        while self
+            .clone_next_bucket_keys_async(&mut cursor, &mut keys)
+            .await
+        {
+            for key in keys.drain(..) {
+                if !other.contains_async(&key).await {
+                    self.remove_async(&key).await;
+                }
+            }
+        }
+    }
+
+    /// Returns `true` if all the keys in the [`HashSet`] exist in `other`.
+    ///
+    /// It returns as soon as a key that does not exist in `other` is found. Keys inserted into or
+    /// removed from either [`HashSet`] during the method call may or may not be taken into
+    /// account.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in the [`HashSet`] are cloned bucket by bucket, and no locks on the [`HashSet`] are held
+    /// while `other` is being searched for the keys, therefore concurrent set operations on the
+    /// same pair of [`HashSet`] instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = (0..4).collect();
+    /// let hashset2: HashSet<u64> = (0..8).collect();
+    ///
+    /// assert!(hashset1.is_subset(&hashset2));
+    /// assert!(!hashset2.is_subset(&hashset1));
+    /// ```
+    #[inline]
+    pub fn is_subset<H2: BuildHasher>(&self, other: &HashSet<K, H2>) -> bool {
+        let mut cursor = (None, 0);
+        let mut keys = Vec::new();
+        while self
+            .map
+            .clone_next_bucket_keys(&mut cursor, &mut keys, &mut (), &Guard::new())
+            == Ok(true)
+        {
+            if keys.drain(..).any(|k| !other.contains(&k)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns `true` if all the keys in `other` exist in the [`HashSet`].
+    ///
+    /// It returns as soon as a key that does not exist in the [`HashSet`] is found. Keys inserted
+    /// into or removed from either [`HashSet`] during the method call may or may not be taken into
+    /// account.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in `other` are cloned bucket by bucket, and no locks on `other` are held while the
+    /// [`HashSet`] is being searched for the keys, therefore concurrent set operations on the same
+    /// pair of [`HashSet`] instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = (0..8).collect();
+    /// let hashset2: HashSet<u64> = (0..4).collect();
+    ///
+    /// assert!(hashset1.is_superset(&hashset2));
+    /// assert!(!hashset2.is_superset(&hashset1));
+    /// ```
+    #[inline]
+    pub fn is_superset<H2: BuildHasher>(&self, other: &HashSet<K, H2>) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Removes all the keys that do not exist in `other` from the [`HashSet`] in parallel.
+    ///
+    /// The bucket array of the [`HashSet`] is partitioned across
+    /// [`rayon`](https://crates.io/crates/rayon) worker threads. Keys that have existed in the
+    /// [`HashSet`] since the invocation of the method are guaranteed to be visited if they are not
+    /// removed.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in the [`HashSet`] are cloned bucket by bucket, and no locks on the [`HashSet`] are held
+    /// while `other` is being searched for the keys, therefore concurrent set operations on the
+    /// same pair of [`HashSet`] instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = (0..1024).collect();
+    /// let hashset2: HashSet<u64> = (512..2048).collect();
+    ///
+    /// hashset1.par_intersection_with(&hashset2);
+    ///
+    /// assert_eq!(hashset1.len(), 512);
+    /// assert!(!hashset1.contains(&0));
+    /// assert!(hashset1.contains(&512));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[inline]
+    pub fn par_intersection_with<H2: BuildHasher + Sync>(&self, other: &HashSet<K, H2>)
+    where
+        K: Send + Sync,
+        H: Sync,
+    {
+        if (self as *const Self).cast::<()>() == (other as *const HashSet<K, H2>).cast::<()>() {
+            return;
+        }
+        self.map.par_for_each_bucket_keys(|keys| {
+            for key in keys {
+                if !other.contains(&key) {
+                    self.remove(&key);
+                }
+            }
+        });
+    }
+
+    /// Removes all the keys that exist in `other` from the [`HashSet`].
+    ///
+    /// Keys that have existed in `other` since the invocation of the method are guaranteed to be
+    /// removed from the [`HashSet`] if they are not removed from `other`.
+    ///
+    /// ## Locking behavior
+    ///
+    /// Keys in `other` are cloned bucket by bucket, and no locks on `other` are held while the keys
+    /// are being removed, therefore concurrent set operations on the same pair of [`HashSet`]
+    /// instances never lead to a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// assert!(hashset1.insert(1).is_ok());
+    /// assert!(hashset1.insert(2).is_ok());
+    /// assert!(hashset2.insert(2).is_ok());
+    ///
+    /// hashset1.difference_with(&hashset2);
+    /// assert!(hashset1.contains(&1));
+    /// assert!(!hashset1.contains(&2));
+    /// ```
+    #[inline]
+    pub fn difference_with<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        other.for_each_bucket(|keys| {
+            for key in keys.drain(..) {
+                self.remove(&key);
+            }
+        });
+    }
+
+    /// Removes all the keys that exist in `other` from the [`HashSet`].
+    ///
+    /// Keys in `other` are cloned bucket by bucket, and no locks on `other` are held while the keys
+    /// are being removed. It is an asynchronous method returning an `impl Future` for the caller to
+    /// await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset1: HashSet<u64> = HashSet::default();
+    /// let hashset2: HashSet<u64> = HashSet::default();
+    ///
+    /// let future_difference = hashset1.difference_with_async(&hashset2);
+    /// ```
+    #[inline]
+    pub async fn difference_with_async<H2: BuildHasher>(&self, other: &HashSet<K, H2>) {
+        let mut cursor = (None, 0);
+        let mut keys = Vec::new();
+        while other
+            .clone_next_bucket_keys_async(&mut cursor, &mut keys)
+            .await
+        {
+            for key in keys.drain(..) {
+                self.remove_async(&key).await;
+            }
+        }
+    }
+
+    /// Invokes the supplied closure on the cloned keys of each bucket without holding any locks.
+    fn for_each_bucket<F: FnMut(&mut Vec<K>)>(&self, mut f: F) {
+        let mut cursor = (None, 0);
+        let mut keys = Vec::new();
+        loop {
+            let result =
+                self.map
+                    .clone_next_bucket_keys(&mut cursor, &mut keys, &mut (), &Guard::new());
+            if result != Ok(true) {
+                break;
+            }
+            f(&mut keys);
+            keys.clear();
+        }
+    }
+
+    /// Clones the keys in the next bucket pointed by `cursor` into `keys`.
+    ///
+    /// Returns `false` if all the buckets were visited.
+    async fn clone_next_bucket_keys_async(
+        &self,
+        cursor: &mut BucketCursor<K, (), (), SEQUENTIAL>,
+        keys: &mut Vec<K>,
+    ) -> bool {
+        loop {
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            let result = self.map.clone_next_bucket_keys(
+                cursor,
+                keys,
+                &mut async_wait_pinned,
+                &Guard::new(),
+            );
+            if let Ok(result) = result {
+                return result;
+            }
+            async_wait_pinned.await;
+        }
+    }
 }
 
 impl<K, H> Clone for HashSet<K, H>
@@ -726,6 +1376,59 @@ where
     }
 }
 
+impl<K, H> Extend<K> for HashSet<K, H>
+where
+    K: Eq + Hash,
+    H: BuildHasher,
+{
+    /// Inserts all the keys yielded by the iterator into the [`HashSet`].
+    ///
+    /// Keys that already exist in the [`HashSet`] are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let mut hashset: HashSet<u64> = HashSet::default();
+    /// hashset.extend([1, 2, 2]);
+    ///
+    /// assert_eq!(hashset.len(), 2);
+    /// ```
+    #[inline]
+    fn extend<I: IntoIterator<Item = K>>(&mut self, iter: I) {
+        for key in iter {
+            let _: Result<(), K> = self.insert(key);
+        }
+    }
+}
+
+impl<K, H> FromIterator<K> for HashSet<K, H>
+where
+    K: Eq + Hash,
+    H: BuildHasher + Default,
+{
+    /// Creates a [`HashSet`] from the keys yielded by the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HashSet;
+    ///
+    /// let hashset: HashSet<u64> = (0..4).chain(2..6).collect();
+    ///
+    /// assert_eq!(hashset.len(), 6);
+    /// assert!(hashset.contains(&5));
+    /// ```
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut hashset = Self::with_capacity_and_hasher(iter.size_hint().0, H::default());
+        hashset.extend(iter);
+        hashset
+    }
+}
+
 impl<K, H> PartialEq for HashSet<K, H>
 where
     K: Eq + Hash,
@@ -9,12 +9,17 @@ use bucket::{DataBlock, EntryPtr, Locker, LruList, Reader, BUCKET_LEN, CACHE, OP
 use bucket_array::BucketArray;
 use std::hash::{BuildHasher, Hash, Hasher};
 use std::pin::Pin;
+use std::ptr;
 use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, Release};
 use std::sync::atomic::{fence, AtomicUsize};
 
 /// The maximum resize factor.
 const MAX_RESIZE_FACTOR: usize = (usize::BITS / 2) as usize;
 
+/// [`BucketCursor`] points to a bucket in a [`BucketArray`].
+pub(super) type BucketCursor<K, V, L, const TYPE: char> =
+    (Option<Shared<BucketArray<K, V, L, TYPE>>>, usize);
+
 /// `HashTable` defines common functions for hash table implementations.
 pub(super) trait HashTable<K, V, H, L: LruList, const TYPE: char>
 where
@@ -248,6 +253,53 @@ where
         }
     }
 
+    /// Inserts entries sorted by their bucket indexes, locking each bucket only once.
+    ///
+    /// Entries are popped from the end of `entries`, and those with existing keys are pushed into
+    /// `rejected`. Returns an error if locking failed, in which case the remaining entries are
+    /// retained in `entries`.
+    fn insert_sorted_entries<D: DeriveAsyncWait>(
+        &self,
+        entries: &mut Vec<(u64, K, V)>,
+        rejected: &mut Vec<(K, V)>,
+        async_wait: &mut D,
+        guard: &Guard,
+    ) -> Result<(), ()> {
+        while let Some(hash) = entries.last().map(|e| e.0) {
+            let current_array = self.get_current_array(guard);
+            while !self.incremental_rehash::<K, D, false>(current_array, async_wait, guard)? {}
+
+            let index = current_array.calculate_bucket_index(hash);
+            let bucket = current_array.bucket_mut(index);
+            let lock_result = if let Some(async_wait) = async_wait.derive() {
+                Locker::try_lock_or_wait(bucket, async_wait, guard)?
+            } else {
+                Locker::lock(bucket, guard)
+            };
+            let Some(mut locker) = lock_result else {
+                // Reaching here means that `self.bucket_array()` has been updated.
+                continue;
+            };
+            let data_block_mut = current_array.data_block_mut(index);
+            while let Some((hash, key, val)) = entries.pop() {
+                if current_array.calculate_bucket_index(hash) != index {
+                    entries.push((hash, key, val));
+                    break;
+                }
+                let partial_hash = BucketArray::<K, V, L, TYPE>::partial_hash(hash);
+                if locker
+                    .get_entry_ptr(data_block_mut, &key, partial_hash, guard)
+                    .is_valid()
+                {
+                    rejected.push((key, val));
+                } else {
+                    locker.insert_with(data_block_mut, partial_hash, || (key, val), guard);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Returns a [`LockedEntry`] pointing to the first occupied entry.
     #[inline]
     fn lock_first_entry<'g>(&self, guard: &'g Guard) -> Option<LockedEntry<'g, K, V, L, TYPE>> {
@@ -521,12 +573,15 @@ where
     fn find_entry<'g, P: FnMut(&K, &V) -> bool>(
         &self,
         mut pred: P,
+        start_index: usize,
         guard: &'g Guard,
     ) -> Option<LockedEntry<'g, K, V, L, TYPE>> {
         let mut current_array_ptr = self.bucket_array().load(Acquire, guard);
         while let Some(current_array) = current_array_ptr.as_ref() {
             self.clear_old_array(current_array, guard);
-            for index in 0..current_array.num_buckets() {
+            let num_buckets = current_array.num_buckets();
+            for offset in 0..num_buckets {
+                let index = start_index.wrapping_add(offset) % num_buckets;
                 let bucket = current_array.bucket_mut(index);
                 if let Some(locker) = Locker::lock(bucket, guard) {
                     let data_block_mut = current_array.data_block_mut(index);
@@ -594,6 +649,202 @@ where
         }
     }
 
+    /// Clones the keys in the next bucket pointed by `cursor` into `keys`.
+    ///
+    /// `cursor` is a pair of the bucket array and the index of the next bucket, and no locks are
+    /// held when the method returns. Returns `false` if all the buckets were visited, or an error
+    /// if locking failed.
+    fn clone_next_bucket_keys<D: DeriveAsyncWait>(
+        &self,
+        cursor: &mut BucketCursor<K, V, L, TYPE>,
+        keys: &mut Vec<K>,
+        async_wait: &mut D,
+        guard: &Guard,
+    ) -> Result<bool, ()>
+    where
+        K: Clone,
+    {
+        loop {
+            let Some(current_array) = cursor.0.as_ref() else {
+                let Some(current_array) = self.bucket_array().get_shared(Acquire, guard) else {
+                    return Ok(false);
+                };
+                cursor.0.replace(current_array);
+                cursor.1 = 0;
+                continue;
+            };
+            if cursor.1 == 0 {
+                while !self.incremental_rehash::<K, D, false>(current_array, async_wait, guard)? {}
+            }
+            if cursor.1 == current_array.num_buckets() {
+                let new_current_array_ptr = self.bucket_array().load(Acquire, guard);
+                if new_current_array_ptr.is_null()
+                    || ptr::eq(new_current_array_ptr.as_ptr(), current_array.as_ptr())
+                {
+                    // Finished scanning the entire array.
+                    return Ok(false);
+                }
+                cursor.0 = self.bucket_array().get_shared(Acquire, guard);
+                cursor.1 = 0;
+                continue;
+            }
+
+            let bucket = current_array.bucket(cursor.1);
+            let lock_result = if let Some(async_wait) = async_wait.derive() {
+                Reader::try_lock_or_wait(bucket, async_wait, guard)?
+            } else {
+                Reader::lock(bucket, guard)
+            };
+            if let Some(reader) = lock_result {
+                let data_block = current_array.data_block(cursor.1);
+                let mut entry_ptr = EntryPtr::new(guard);
+                while entry_ptr.move_to_next(*reader, guard) {
+                    keys.push(entry_ptr.get(data_block).0.clone());
+                }
+            }
+            cursor.1 += 1;
+            return Ok(true);
+        }
+    }
+
+    /// Invokes the supplied function on each entry, partitioning buckets across
+    /// [`rayon`](https://crates.io/crates/rayon) worker threads.
+    ///
+    /// Buckets are read without acquiring any locks only if `TYPE` is `OPTIMISTIC`.
+    #[cfg(feature = "rayon")]
+    fn par_for_each_entry<F: Fn(&K, &V) + Send + Sync>(&self, f: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let guard = Guard::new();
+        let mut current_array_ptr = self.bucket_array().load(Acquire, &guard);
+        while let Some(current_array) = current_array_ptr.as_ref() {
+            self.clear_old_array(current_array, &guard);
+            (0..current_array.num_buckets())
+                .into_par_iter()
+                .for_each(|index| {
+                    let guard = Guard::new();
+                    let bucket = current_array.bucket(index);
+                    let data_block = current_array.data_block(index);
+                    let reader = if TYPE == OPTIMISTIC {
+                        None
+                    } else if let Some(reader) = Reader::lock(bucket, &guard) {
+                        Some(reader)
+                    } else {
+                        return;
+                    };
+                    let mut entry_ptr = EntryPtr::new(&guard);
+                    while entry_ptr.move_to_next(bucket, &guard) {
+                        let (k, v) = entry_ptr.get(data_block);
+                        f(k, v);
+                    }
+                    drop(reader);
+                });
+
+            let new_current_array_ptr = self.bucket_array().load(Acquire, &guard);
+            if current_array_ptr.without_tag() == new_current_array_ptr.without_tag() {
+                break;
+            }
+            current_array_ptr = new_current_array_ptr;
+        }
+    }
+
+    /// Invokes the supplied function on the cloned keys of each bucket, partitioning buckets
+    /// across [`rayon`](https://crates.io/crates/rayon) worker threads.
+    ///
+    /// No locks are held while the supplied function is being invoked.
+    #[cfg(feature = "rayon")]
+    fn par_for_each_bucket_keys<F: Fn(Vec<K>) + Send + Sync>(&self, f: F)
+    where
+        K: Clone + Send + Sync,
+        V: Send + Sync,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+        let guard = Guard::new();
+        let mut current_array_ptr = self.bucket_array().load(Acquire, &guard);
+        while let Some(current_array) = current_array_ptr.as_ref() {
+            self.clear_old_array(current_array, &guard);
+            (0..current_array.num_buckets())
+                .into_par_iter()
+                .for_each(|index| {
+                    let guard = Guard::new();
+                    let bucket = current_array.bucket(index);
+                    let Some(reader) = Reader::lock(bucket, &guard) else {
+                        return;
+                    };
+                    let data_block = current_array.data_block(index);
+                    let mut keys = Vec::new();
+                    let mut entry_ptr = EntryPtr::new(&guard);
+                    while entry_ptr.move_to_next(*reader, &guard) {
+                        keys.push(entry_ptr.get(data_block).0.clone());
+                    }
+                    drop(reader);
+                    f(keys);
+                });
+
+            let new_current_array_ptr = self.bucket_array().load(Acquire, &guard);
+            if current_array_ptr.without_tag() == new_current_array_ptr.without_tag() {
+                break;
+            }
+            current_array_ptr = new_current_array_ptr;
+        }
+    }
+
+    /// Retains entries that satisfy the supplied predicate, partitioning buckets across
+    /// [`rayon`](https://crates.io/crates/rayon) worker threads.
+    #[cfg(feature = "rayon")]
+    fn par_retain_entries<F: Fn(&K, &mut V) -> bool + Send + Sync>(&self, pred: F)
+    where
+        K: Send + Sync,
+        V: Send + Sync,
+    {
+        use rayon::iter::{IntoParallelIterator, ParallelIterator};
+        use std::sync::atomic::AtomicBool;
+
+        let guard = Guard::new();
+        let removed = AtomicBool::new(false);
+        let mut current_array_ptr = self.bucket_array().load(Acquire, &guard);
+        while let Some(current_array) = current_array_ptr.as_ref() {
+            self.clear_old_array(current_array, &guard);
+            (0..current_array.num_buckets())
+                .into_par_iter()
+                .for_each(|index| {
+                    let guard = Guard::new();
+                    let bucket = current_array.bucket_mut(index);
+                    let Some(mut locker) = Locker::lock(bucket, &guard) else {
+                        return;
+                    };
+                    let data_block_mut = current_array.data_block_mut(index);
+                    let mut entry_ptr = EntryPtr::new(&guard);
+                    while entry_ptr.move_to_next(&locker, &guard) {
+                        let (k, v) = entry_ptr.get_mut(data_block_mut, &mut locker);
+                        if !pred(k, v) {
+                            if TYPE == OPTIMISTIC {
+                                locker.mark_removed(&mut entry_ptr, &guard);
+                            } else {
+                                locker.remove(data_block_mut, &mut entry_ptr, &guard);
+                            }
+                            removed.store(true, Relaxed);
+                        }
+                    }
+                });
+
+            let new_current_array_ptr = self.bucket_array().load(Acquire, &guard);
+            if current_array_ptr.without_tag() == new_current_array_ptr.without_tag() {
+                break;
+            }
+            current_array_ptr = new_current_array_ptr;
+        }
+
+        if removed.load(Relaxed) {
+            self.try_resize(0, &guard);
+        }
+    }
+
     /// Prunes entries satisfying the predicate.
     #[inline]
     fn prune_entries<F: FnMut(&K, V) -> Option<V>>(&self, mut pred: F) {
@@ -820,7 +1071,8 @@ where
                 };
 
                 let entry_clone = Self::try_clone(old_entry);
-                target_bucket.insert_with(
+                let pinned = TYPE == CACHE && old_locker.is_pinned(&entry_ptr);
+                let new_entry_ptr = target_bucket.insert_with(
                     current_array.data_block_mut(new_index),
                     partial_hash,
                     || {
@@ -833,6 +1085,9 @@ where
                     },
                     guard,
                 );
+                if pinned {
+                    target_bucket.pin(&new_entry_ptr);
+                }
 
                 if TYPE == OPTIMISTIC {
                     // In order for readers that have observed the following erasure to see the above
@@ -1145,6 +1400,84 @@ where
         }
     }
 
+    /// Tries to replace the current array with a new one that fits the number of entries.
+    ///
+    /// Unlike [`try_resize`](Self::try_resize), the number of entries is counted rather than
+    /// estimated, and the array is unconditionally replaced. Returns `false` if no array is
+    /// allocated, another thread is resizing the array, or an old array is attached to it.
+    fn try_rebuild(&self, guard: &Guard) -> bool {
+        let current_array_ptr = self.bucket_array().load(Acquire, guard);
+        if current_array_ptr.tag() != Tag::None {
+            // Another thread is currently allocating a new bucket array.
+            return false;
+        }
+        let Some(current_array) = current_array_ptr.as_ref() else {
+            return false;
+        };
+        if current_array.has_old_array() {
+            // The hash table cannot be rebuilt with an old array attached to it.
+            return false;
+        }
+
+        // The new array is sized so that the load factor is below `1/2`.
+        let capacity = current_array.num_entries();
+        let num_entries = (0..current_array.num_buckets())
+            .map(|i| current_array.bucket(i).num_entries())
+            .sum::<usize>();
+        let new_capacity = num_entries
+            .saturating_mul(2)
+            .max(self.minimum_capacity().load(Relaxed))
+            .max(BucketArray::<K, V, L, TYPE>::minimum_capacity())
+            .min(self.maximum_capacity())
+            .min(capacity.saturating_mul(MAX_RESIZE_FACTOR))
+            .next_power_of_two();
+        if !self.bucket_array().update_tag_if(
+            Tag::First,
+            |ptr| ptr == current_array_ptr,
+            Relaxed,
+            Relaxed,
+        ) {
+            // The bucket array is being replaced with a new one.
+            return false;
+        }
+
+        let mut new_array = ExitGuard::new(None, |new_array: Option<Shared<_>>| {
+            if let Some(new_array) = new_array {
+                self.bucket_array()
+                    .swap((Some(new_array), Tag::None), Release);
+            } else {
+                // Release the lock if allocation failed.
+                self.bucket_array()
+                    .update_tag_if(Tag::None, |_| true, Relaxed, Relaxed);
+            }
+        });
+        new_array.replace(unsafe {
+            Shared::new_unchecked(BucketArray::<K, V, L, TYPE>::new(
+                new_capacity,
+                self.bucket_array().clone(Relaxed, guard),
+            ))
+        });
+        true
+    }
+
+    /// Clears old arrays, and replaces the current array with a new one that fits the number of
+    /// entries.
+    ///
+    /// Returns `false` if no array is allocated or another thread is resizing the array.
+    fn try_compact(&self, guard: &Guard) -> bool {
+        let Some(current_array) = self.bucket_array().load(Acquire, guard).as_ref() else {
+            return false;
+        };
+        self.clear_old_array(current_array, guard);
+        if !self.try_rebuild(guard) {
+            return false;
+        }
+        if let Some(current_array) = self.bucket_array().load(Acquire, guard).as_ref() {
+            self.clear_old_array(current_array, guard);
+        }
+        true
+    }
+
     /// Returns a reference to the specified [`Guard`] whose lifetime matches that of `self`.
     fn prolonged_guard_ref<'h>(&'h self, guard: &Guard) -> &'h Guard {
         let _: &Self = self;
@@ -1190,6 +1523,14 @@ impl<'h, K: Eq + Hash + 'h, V: 'h, L: LruList, const TYPE: char> LockedEntry<'h,
     /// Gets the first occupied entry.
     pub(super) async fn first_entry_async<H: BuildHasher, T: HashTable<K, V, H, L, TYPE>>(
         hash_table: &'h T,
+    ) -> Option<LockedEntry<'h, K, V, L, TYPE>> {
+        Self::first_entry_from_async(hash_table, 0).await
+    }
+
+    /// Gets the first occupied entry, searching the bucket array from the specified index.
+    pub(super) async fn first_entry_from_async<H: BuildHasher, T: HashTable<K, V, H, L, TYPE>>(
+        hash_table: &'h T,
+        start_index: usize,
     ) -> Option<LockedEntry<'h, K, V, L, TYPE>> {
         let mut current_array_holder = hash_table.bucket_array().get_shared(Acquire, &Guard::new());
         while let Some(current_array) = current_array_holder.take() {
@@ -1206,7 +1547,9 @@ impl<'h, K: Eq + Hash + 'h, V: 'h, L: LruList, const TYPE: char> LockedEntry<'h,
                 }
                 async_wait_pinned.await;
             }
-            for index in 0..current_array.num_buckets() {
+            let num_buckets = current_array.num_buckets();
+            for offset in 0..num_buckets {
+                let index = start_index.wrapping_add(offset) % num_buckets;
                 loop {
                     let mut async_wait = AsyncWait::default();
                     let mut async_wait_pinned = Pin::new(&mut async_wait);
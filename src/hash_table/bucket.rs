@@ -40,6 +40,12 @@ pub trait LruList: 'static + Copy + Default {
         None
     }
 
+    /// Returns the entry to be evicted next.
+    #[inline]
+    fn peek(&self, _tail: u32) -> Option<u8> {
+        None
+    }
+
     /// Removes an entry.
     #[inline]
     fn remove(&mut self, _tail: u32, _entry: u8) -> Option<u32> {
@@ -51,6 +57,32 @@ pub trait LruList: 'static + Copy + Default {
     fn promote(&mut self, _tail: u32, _entry: u8) -> Option<u32> {
         None
     }
+
+    /// Visits the entries in the list from the most recently used one.
+    #[inline]
+    fn for_each_recent<F: FnMut(u8)>(&self, _tail: u32, _f: F) {}
+
+    /// Pins an entry by removing it from the list and marking it pinned.
+    #[inline]
+    fn pin(&mut self, _tail: u32, _entry: u8) -> Option<u32> {
+        None
+    }
+
+    /// Clears the pinned mark of an entry without adding it to the list.
+    #[inline]
+    fn unpin(&mut self, _entry: u8) {}
+
+    /// Returns `true` if the entry is pinned.
+    #[inline]
+    fn is_pinned(&self, _entry: u8) -> bool {
+        false
+    }
+
+    /// Returns a bitmap of the pinned entries.
+    #[inline]
+    fn pinned_bitmap(&self) -> u32 {
+        0
+    }
 }
 
 /// [`DoublyLinkedList`] is an array of `(u8, u8)`.
@@ -58,6 +90,12 @@ pub trait LruList: 'static + Copy + Default {
 /// [`CACHE`] manages a least-recently-used entry list using [`DoublyLinkedList`].
 pub type DoublyLinkedList = [(u8, u8); BUCKET_LEN];
 
+/// The link of a pinned entry in a [`DoublyLinkedList`].
+///
+/// Pinned entries are not a part of the linked list, and the link value cannot be mistaken for an
+/// actual link since entry indexes are smaller than [`BUCKET_LEN`].
+const PINNED: (u8, u8) = (u8::MAX, u8::MAX);
+
 /// The type of [`Bucket`] only allows sequential access to it.
 pub const SEQUENTIAL: char = 'S';
 
@@ -137,6 +175,24 @@ impl<K, V, L: LruList, const TYPE: char> Bucket<K, V, L, TYPE> {
         self.num_entries as usize
     }
 
+    /// Returns the number of removed entries that are yet to be dropped in the [`Bucket`].
+    ///
+    /// It always returns `0` unless `TYPE == OPTIMISTIC`.
+    #[inline]
+    pub(crate) fn num_removed_entries(&self, guard: &Guard) -> usize {
+        if TYPE != OPTIMISTIC {
+            return 0;
+        }
+        let mut num_removed_entries =
+            self.metadata.removed_bitmap_or_lru_tail.count_ones() as usize;
+        let mut link_ptr = self.metadata.link.load(Acquire, guard);
+        while let Some(link) = link_ptr.as_ref() {
+            num_removed_entries += link.metadata.removed_bitmap_or_lru_tail.count_ones() as usize;
+            link_ptr = link.metadata.link.load(Acquire, guard);
+        }
+        num_removed_entries
+    }
+
     /// Returns `true` if the [`Bucket`] needs to be rebuilt.
     ///
     /// If `TYPE == OPTIMISTIC`, removed entries are not dropped, still occupying the slots,
@@ -236,6 +292,10 @@ impl<K, V, L: LruList, const TYPE: char> Bucket<K, V, L, TYPE> {
                 0
             );
             link_mut.metadata.occupied_bitmap &= !(1_u32 << entry_ptr.current_index);
+            if TYPE == CACHE {
+                // Clear the pinned bit.
+                link_mut.metadata.removed_bitmap_or_lru_tail &= !(1_u32 << entry_ptr.current_index);
+            }
             let removed = unsafe {
                 link_mut.data_block[entry_ptr.current_index]
                     .as_mut_ptr()
@@ -356,15 +416,19 @@ impl<K, V, L: LruList, const TYPE: char> Bucket<K, V, L, TYPE> {
         debug_assert_eq!(TYPE, CACHE);
 
         if self.metadata.occupied_bitmap == 0b1111_1111_1111_1111_1111_1111_1111_1111 {
-            self.num_entries -= 1;
             let tail = self.metadata.removed_bitmap_or_lru_tail;
             let evicted = if let Some((evicted, new_tail)) = self.lru_list.evict(tail) {
                 self.metadata.removed_bitmap_or_lru_tail = new_tail;
                 evicted as usize
             } else {
-                // Evict the first occupied entry.
-                0
+                // Evict the first unpinned entry.
+                let unpinned = !self.lru_list.pinned_bitmap();
+                if unpinned == 0 {
+                    return None;
+                }
+                unpinned.trailing_zeros() as usize
             };
+            self.num_entries -= 1;
             debug_assert_ne!(self.metadata.occupied_bitmap & (1_u32 << evicted), 0);
             self.metadata.occupied_bitmap &= !(1_u32 << evicted);
             return Some(unsafe { data_block[evicted].as_mut_ptr().read() });
@@ -372,6 +436,161 @@ impl<K, V, L: LruList, const TYPE: char> Bucket<K, V, L, TYPE> {
         None
     }
 
+    /// Evicts the least recently used entry other than the one pointed to by `entry_ptr`.
+    ///
+    /// Entries in linked buckets and pinned entries are not evicted.
+    pub(crate) fn evict_lru_head_except(
+        &mut self,
+        data_block: &mut DataBlock<K, V, BUCKET_LEN>,
+        entry_ptr: &EntryPtr<K, V, TYPE>,
+    ) -> Option<(K, V)> {
+        debug_assert_eq!(TYPE, CACHE);
+
+        let mut bitmap = self.metadata.occupied_bitmap & !self.lru_list.pinned_bitmap();
+        if entry_ptr.current_link_ptr.is_null() && entry_ptr.current_index < BUCKET_LEN {
+            bitmap &= !(1_u32 << entry_ptr.current_index);
+        }
+        if bitmap == 0 {
+            return None;
+        }
+        let tail = self.metadata.removed_bitmap_or_lru_tail;
+        let evicted = match self.lru_list.peek(tail) {
+            Some(lru) if (bitmap & (1_u32 << lru)) != 0 => lru,
+            _ => {
+                // The entry is not tracked, or it is the excluded entry.
+                #[allow(clippy::cast_possible_truncation)]
+                let first = bitmap.trailing_zeros() as u8;
+                first
+            }
+        };
+        if let Some(new_tail) = self.lru_list.remove(tail, evicted) {
+            self.metadata.removed_bitmap_or_lru_tail = new_tail;
+        }
+        self.num_entries -= 1;
+        self.metadata.occupied_bitmap &= !(1_u32 << evicted);
+        Some(unsafe { data_block[evicted as usize].as_mut_ptr().read() })
+    }
+
+    /// Returns references to the occupied entries ordered from the most recently used one.
+    ///
+    /// Entries that are not tracked by the LRU list are placed at the end, and entries in linked
+    /// buckets are not included.
+    pub(crate) fn entries_by_recency<'d>(
+        &self,
+        data_block: &'d DataBlock<K, V, BUCKET_LEN>,
+    ) -> Vec<&'d (K, V)> {
+        debug_assert_eq!(TYPE, CACHE);
+
+        let mut untracked = self.metadata.occupied_bitmap;
+        let mut entries = Vec::with_capacity(untracked.count_ones() as usize);
+        self.lru_list
+            .for_each_recent(self.metadata.removed_bitmap_or_lru_tail, |entry| {
+                untracked &= !(1_u32 << entry);
+                entries.push(unsafe { &*data_block[entry as usize].as_ptr() });
+            });
+        while untracked != 0 {
+            let entry = untracked.trailing_zeros();
+            untracked &= !(1_u32 << entry);
+            entries.push(unsafe { &*data_block[entry as usize].as_ptr() });
+        }
+        entries
+    }
+
+    /// Returns a reference to the least recently used entry if the [`Bucket`] is full.
+    ///
+    /// Returns `None` if all the entries are pinned.
+    pub(crate) fn lru_head<'d>(
+        &self,
+        data_block: &'d DataBlock<K, V, BUCKET_LEN>,
+    ) -> Option<&'d (K, V)> {
+        debug_assert_eq!(TYPE, CACHE);
+
+        if self.metadata.occupied_bitmap == 0b1111_1111_1111_1111_1111_1111_1111_1111 {
+            let tail = self.metadata.removed_bitmap_or_lru_tail;
+            let lru = if let Some(lru) = self.lru_list.peek(tail) {
+                lru as usize
+            } else {
+                let unpinned = !self.lru_list.pinned_bitmap();
+                if unpinned == 0 {
+                    return None;
+                }
+                unpinned.trailing_zeros() as usize
+            };
+            return Some(unsafe { &*data_block[lru].as_ptr() });
+        }
+        None
+    }
+
+    /// Pins the entry, thereby exempting it from eviction.
+    ///
+    /// Returns `false` if the entry was already pinned.
+    pub(crate) fn pin(&mut self, entry_ptr: &EntryPtr<K, V, TYPE>) -> bool {
+        debug_assert_eq!(TYPE, CACHE);
+        debug_assert_ne!(entry_ptr.current_index, usize::MAX);
+        debug_assert_ne!(entry_ptr.current_index, BUCKET_LEN);
+
+        let link_ptr = entry_ptr.current_link_ptr.as_ptr().cast_mut();
+        if let Some(link_mut) = unsafe { link_ptr.as_mut() } {
+            let bit = 1_u32 << entry_ptr.current_index;
+            if link_mut.metadata.removed_bitmap_or_lru_tail & bit != 0 {
+                return false;
+            }
+            link_mut.metadata.removed_bitmap_or_lru_tail |= bit;
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let entry = entry_ptr.current_index as u8;
+            if self.lru_list.is_pinned(entry) {
+                return false;
+            }
+            let tail = self.metadata.removed_bitmap_or_lru_tail;
+            if let Some(new_tail) = self.lru_list.pin(tail, entry) {
+                self.metadata.removed_bitmap_or_lru_tail = new_tail;
+            }
+        }
+        true
+    }
+
+    /// Unpins the entry, and makes it the most recently used one.
+    ///
+    /// Returns `false` if the entry was not pinned.
+    pub(crate) fn unpin(&mut self, entry_ptr: &EntryPtr<K, V, TYPE>) -> bool {
+        debug_assert_eq!(TYPE, CACHE);
+        debug_assert_ne!(entry_ptr.current_index, usize::MAX);
+        debug_assert_ne!(entry_ptr.current_index, BUCKET_LEN);
+
+        let link_ptr = entry_ptr.current_link_ptr.as_ptr().cast_mut();
+        if let Some(link_mut) = unsafe { link_ptr.as_mut() } {
+            let bit = 1_u32 << entry_ptr.current_index;
+            if link_mut.metadata.removed_bitmap_or_lru_tail & bit == 0 {
+                return false;
+            }
+            link_mut.metadata.removed_bitmap_or_lru_tail &= !bit;
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let entry = entry_ptr.current_index as u8;
+            if !self.lru_list.is_pinned(entry) {
+                return false;
+            }
+            self.lru_list.unpin(entry);
+            self.update_lru_tail(entry_ptr);
+        }
+        true
+    }
+
+    /// Returns `true` if the entry is pinned.
+    pub(crate) fn is_pinned(&self, entry_ptr: &EntryPtr<K, V, TYPE>) -> bool {
+        if TYPE != CACHE {
+            return false;
+        }
+        if let Some(link) = entry_ptr.current_link_ptr.as_ref() {
+            link.metadata.removed_bitmap_or_lru_tail & (1_u32 << entry_ptr.current_index) != 0
+        } else {
+            #[allow(clippy::cast_possible_truncation)]
+            let entry = entry_ptr.current_index as u8;
+            self.lru_list.is_pinned(entry)
+        }
+    }
+
     /// Sets the entry having been just accessed.
     pub(crate) fn update_lru_tail(&mut self, entry_ptr: &EntryPtr<K, V, TYPE>) {
         debug_assert_eq!(TYPE, CACHE);
@@ -1100,8 +1319,22 @@ impl LruList for DoublyLinkedList {
         }
     }
 
+    #[inline]
+    fn peek(&self, tail: u32) -> Option<u8> {
+        if tail == 0 {
+            None
+        } else {
+            Some(self[tail as usize - 1].0)
+        }
+    }
+
     #[inline]
     fn remove(&mut self, tail: u32, entry: u8) -> Option<u32> {
+        if self[entry as usize] == PINNED {
+            // The entry is not a part of the linked list.
+            self[entry as usize] = (0, 0);
+            return None;
+        }
         if tail == 0
             || (self[entry as usize] == (0, 0)
                 && (self[0] != (entry, entry) || (tail != 1 && tail != u32::from(entry) + 1)))
@@ -1140,7 +1373,10 @@ impl LruList for DoublyLinkedList {
 
     #[inline]
     fn promote(&mut self, tail: u32, entry: u8) -> Option<u32> {
-        if tail == u32::from(entry) + 1 {
+        if self[entry as usize] == PINNED {
+            // Pinned entries are not tracked.
+            return None;
+        } else if tail == u32::from(entry) + 1 {
             // Nothing to do.
             return None;
         } else if tail == 0 {
@@ -1179,6 +1415,52 @@ impl LruList for DoublyLinkedList {
         // Update `head`.
         Some(u32::from(entry) + 1)
     }
+
+    #[inline]
+    fn for_each_recent<F: FnMut(u8)>(&self, tail: u32, mut f: F) {
+        if tail == 0 {
+            return;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        let head = (tail - 1) as u8;
+        let mut entry = head;
+        loop {
+            f(entry);
+            entry = self[entry as usize].1;
+            if entry == head {
+                break;
+            }
+        }
+    }
+
+    #[inline]
+    fn pin(&mut self, tail: u32, entry: u8) -> Option<u32> {
+        let new_tail = self.remove(tail, entry);
+        self[entry as usize] = PINNED;
+        new_tail
+    }
+
+    #[inline]
+    fn unpin(&mut self, entry: u8) {
+        debug_assert_eq!(self[entry as usize], PINNED);
+        self[entry as usize] = (0, 0);
+    }
+
+    #[inline]
+    fn is_pinned(&self, entry: u8) -> bool {
+        self[entry as usize] == PINNED
+    }
+
+    #[inline]
+    fn pinned_bitmap(&self) -> u32 {
+        self.iter().enumerate().fold(0, |bitmap, (entry, link)| {
+            if *link == PINNED {
+                bitmap | (1_u32 << entry)
+            } else {
+                bitmap
+            }
+        })
+    }
 }
 
 impl<K, V, const LEN: usize> LinkedBucket<K, V, LEN> {
@@ -1264,6 +1546,91 @@ mod test {
             }
         }
 
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn evict_except(xs in 1..=BUCKET_LEN) {
+            let mut data_block: DataBlock<usize, usize, BUCKET_LEN> =
+                unsafe { MaybeUninit::uninit().assume_init() };
+            let mut bucket: Bucket<usize, usize, DoublyLinkedList, CACHE> = default_bucket();
+            let guard = Guard::new();
+            let mut locker = Locker::lock(&mut bucket, &guard).unwrap();
+            let mut last_entry_ptr = EntryPtr::new(&guard);
+            for v in 0..xs {
+                last_entry_ptr = locker.insert_with(&mut data_block, 0, || (v, v), &guard);
+                locker.update_lru_tail(&last_entry_ptr);
+            }
+            let by_recency = locker.entries_by_recency(&data_block);
+            assert_eq!(by_recency.len(), xs);
+            for (rank, (k, _)) in by_recency.into_iter().enumerate() {
+                assert_eq!(*k, xs - 1 - rank);
+            }
+            for v in 0..xs - 1 {
+                let evicted = locker.evict_lru_head_except(&mut data_block, &last_entry_ptr);
+                assert_eq!(evicted, Some((v, v)));
+            }
+            assert!(locker.evict_lru_head_except(&mut data_block, &last_entry_ptr).is_none());
+            assert_eq!(locker.num_entries(), 1);
+
+            let evicted = locker.evict_lru_head_except(&mut data_block, &EntryPtr::new(&guard));
+            assert_eq!(evicted, Some((xs - 1, xs - 1)));
+            assert_eq!(locker.num_entries(), 0);
+            assert_eq!(locker.metadata.removed_bitmap_or_lru_tail, 0);
+        }
+
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn evict_pinned(xs in 0..=BUCKET_LEN) {
+            let mut data_block: DataBlock<usize, usize, BUCKET_LEN> =
+                unsafe { MaybeUninit::uninit().assume_init() };
+            let mut bucket: Bucket<usize, usize, DoublyLinkedList, CACHE> = default_bucket();
+            let guard = Guard::new();
+            let mut locker = Locker::lock(&mut bucket, &guard).unwrap();
+            let mut pinned_entry_ptrs = Vec::new();
+            for v in 0..BUCKET_LEN {
+                let entry_ptr = locker.insert_with(&mut data_block, 0, || (v, v), &guard);
+                locker.update_lru_tail(&entry_ptr);
+                if v < xs {
+                    pinned_entry_ptrs.push(entry_ptr);
+                }
+            }
+            for entry_ptr in &pinned_entry_ptrs {
+                assert!(locker.pin(entry_ptr));
+                assert!(!locker.pin(entry_ptr));
+                locker.update_lru_tail(entry_ptr);
+            }
+            assert_eq!(locker.lru_list.pinned_bitmap().count_ones() as usize, xs);
+
+            for v in BUCKET_LEN..BUCKET_LEN * 3 {
+                let lru_head_key = locker.lru_head(&data_block).map(|(k, _)| *k);
+                let evicted = locker.evict_lru_head(&mut data_block);
+                assert_eq!(evicted.map(|(k, _)| k), lru_head_key);
+                if xs == BUCKET_LEN {
+                    assert!(evicted.is_none());
+                    break;
+                }
+                assert!(evicted.unwrap().0 >= xs);
+                let entry_ptr = locker.insert_with(&mut data_block, 0, || (v, v), &guard);
+                locker.update_lru_tail(&entry_ptr);
+                assert!(!locker.is_pinned(&entry_ptr));
+            }
+            for entry_ptr in &pinned_entry_ptrs {
+                assert!(locker.is_pinned(entry_ptr));
+                assert!(entry_ptr.get(&data_block).0 < xs);
+                assert!(locker.unpin(entry_ptr));
+                assert!(!locker.unpin(entry_ptr));
+            }
+            assert_eq!(locker.lru_list.pinned_bitmap(), 0);
+            assert_eq!(locker.entries_by_recency(&data_block).len(), BUCKET_LEN);
+            if !pinned_entry_ptrs.is_empty() {
+                let mut entry_ptr = pinned_entry_ptrs.swap_remove(0);
+                assert_eq!(locker.entries_by_recency(&data_block)[0].0, xs - 1);
+                assert!(locker.pin(&entry_ptr));
+                locker.remove(&mut data_block, &mut entry_ptr, &guard);
+                let entry_ptr = locker.insert_with(&mut data_block, 0, || (0, 0), &guard);
+                assert!(!locker.is_pinned(&entry_ptr));
+            }
+        }
+
         #[cfg_attr(miri, ignore)]
         #[test]
         fn evict_overflowed(xs in 1..BUCKET_LEN * 2) {
@@ -1284,9 +1651,13 @@ mod test {
 
                 let mut evicted_key = None;
                 if xs >= BUCKET_LEN {
+                    let lru_head_key = locker.lru_head(&data_block).map(|(k, _)| *k);
                     let evicted = locker.evict_lru_head(&mut data_block);
                     assert!(evicted.is_some());
                     evicted_key = evicted.map(|(k, _)| k);
+                    assert_eq!(lru_head_key, evicted_key);
+                } else {
+                    assert!(locker.lru_head(&data_block).is_none());
                 }
                 assert_ne!(locker.metadata.removed_bitmap_or_lru_tail, 0);
 
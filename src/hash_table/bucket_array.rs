@@ -15,6 +15,7 @@ pub struct BucketArray<K, V, L: LruList, const TYPE: char> {
     bucket_ptr_offset: u16,
     old_array: AtomicShared<BucketArray<K, V, L, TYPE>>,
     num_cleared_buckets: AtomicUsize,
+    num_resizes: usize,
 }
 
 impl<K, V, L: LruList, const TYPE: char> BucketArray<K, V, L, TYPE> {
@@ -77,6 +78,11 @@ impl<K, V, L: LruList, const TYPE: char> BucketArray<K, V, L, TYPE> {
         let log2_array_len = Self::calculate_log2_array_size(capacity);
         assert_ne!(log2_array_len, 0);
 
+        let num_resizes = old_array
+            .load(Relaxed, &Guard::new())
+            .as_ref()
+            .map_or(0, |old_array| old_array.num_resizes + 1);
+
         let array_len = 1_usize << log2_array_len;
         unsafe {
             let (bucket_size, bucket_array_allocation_size, bucket_array_layout) =
@@ -130,6 +136,7 @@ impl<K, V, L: LruList, const TYPE: char> BucketArray<K, V, L, TYPE> {
                 bucket_ptr_offset: bucket_array_ptr_offset,
                 old_array,
                 num_cleared_buckets: AtomicUsize::new(0),
+                num_resizes,
             }
         }
     }
@@ -186,6 +193,12 @@ impl<K, V, L: LruList, const TYPE: char> BucketArray<K, V, L, TYPE> {
         ((self.sample_size as usize) * (self.sample_size as usize)).min(self.num_buckets())
     }
 
+    /// Returns the number of [`BucketArray`] instances that were replaced to create this one.
+    #[inline]
+    pub(crate) const fn num_resizes(&self) -> usize {
+        self.num_resizes
+    }
+
     /// Returns a [`Ptr`] to the old array.
     #[inline]
     pub(crate) fn has_old_array(&self) -> bool {
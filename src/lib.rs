@@ -4,12 +4,18 @@
 pub mod bag;
 pub use bag::Bag;
 
+pub mod bit_set;
+pub use bit_set::BitSet;
+
 #[cfg(not(feature = "equivalent"))]
 mod equivalent;
 pub use equivalent::{Comparable, Equivalent};
 
 mod exit_guard;
 
+mod expiring;
+pub use expiring::Expiring;
+
 pub mod hash_cache;
 pub use hash_cache::HashCache;
 
@@ -25,7 +31,10 @@ pub use hash_set::HashSet;
 mod hash_table;
 
 mod linked_list;
+pub use linked_list::CountedHead;
+pub use linked_list::Cursor as LinkedCursor;
 pub use linked_list::Entry as LinkedEntry;
+pub use linked_list::Iter as LinkedIter;
 pub use linked_list::LinkedList;
 
 #[cfg(feature = "loom")]
@@ -40,6 +49,8 @@ mod maybe_std {
     pub(crate) use std::thread::yield_now;
 }
 
+pub mod mpmc;
+
 pub mod queue;
 pub use queue::Queue;
 
@@ -65,12 +76,27 @@ mod range_helper {
     }
 }
 
+pub mod radix_tree;
+pub use radix_tree::RadixTree;
+
+pub mod range_map;
+pub use range_map::RangeMap;
+
+pub mod ring_buffer;
+pub use ring_buffer::RingBuffer;
+
 /// Re-exports the [`sdd`](https://crates.io/crates/sdd) crate for backward compatibility.
 pub use sdd as ebr;
 
 #[cfg(feature = "serde")]
 mod serde;
 
+pub mod sketch;
+pub use sketch::{CountMinSketch, HyperLogLog};
+
+pub mod skip_list;
+pub use skip_list::SkipList;
+
 pub mod stack;
 pub use stack::Stack;
 
@@ -81,3 +107,6 @@ pub mod tree_index;
 pub use tree_index::TreeIndex;
 
 mod wait_queue;
+
+pub mod work_stealing_deque;
+pub use work_stealing_deque::WorkStealingDeque;
@@ -1,9 +1,50 @@
 use super::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
+use std::cmp;
 use std::fmt::{self, Debug, Display};
+use std::iter::FusedIterator;
 use std::ops::{Deref, DerefMut};
-use std::sync::atomic::Ordering::{self, AcqRel, Acquire, Relaxed};
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{self, AcqRel, Acquire, Relaxed, Release};
 
 /// [`LinkedList`] is a type trait implementing a lock-free singly linked list.
+///
+/// [`LinkedList`] does not maintain backward links, since a pair of forward and backward
+/// [`Shared`] links would form a reference cycle that could never be reclaimed. Any entry,
+/// including the last one, can nevertheless be removed in `O(1)` by invoking
+/// [`delete_self`](Self::delete_self) on it: the entry is immediately invisible to
+/// [`next_ptr`](Self::next_ptr), and is physically unlinked when its predecessor is traversed.
+///
+/// # Examples
+///
+/// ```
+/// use scc::LinkedList;
+/// use scc::ebr::{AtomicShared, Guard, Shared};
+/// use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+///
+/// #[derive(Default)]
+/// struct L(AtomicShared<L>, usize);
+/// impl LinkedList for L {
+///     fn link_ref(&self) -> &AtomicShared<L> {
+///         &self.0
+///     }
+/// }
+///
+/// let guard = Guard::new();
+///
+/// let head: L = L::default();
+/// let tail: Shared<L> = Shared::new(L(AtomicShared::null(), 3));
+/// assert!(head.push_back(tail.clone(), false, Release, &guard).is_ok());
+/// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 2)), false, Release, &guard).is_ok());
+/// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+///
+/// // Remove the last entry without traversing the linked list.
+/// assert!(tail.delete_self(Release));
+///
+/// let first = head.next_ptr(Acquire, &guard);
+/// let second = first.as_ref().unwrap().next_ptr(Acquire, &guard);
+/// assert_eq!(second.as_ref().unwrap().1, 2);
+/// assert!(second.as_ref().unwrap().next_ptr(Acquire, &guard).is_null());
+/// ```
 pub trait LinkedList: Sized {
     /// Returns a reference to the forward link.
     ///
@@ -196,6 +237,53 @@ pub trait LinkedList: Sized {
         tag == Tag::Second || tag == Tag::Both
     }
 
+    /// Resets the link of `self`, clearing the mark and the deleted flag, so that `self` can be
+    /// linked to a linked list again.
+    ///
+    /// Exclusive access to `self` is required: an entry that was deleted and then unlinked from
+    /// a linked list can be reset once no other thread can reach it, e.g., it is the only
+    /// strong reference and no [`Guard`] that was created before it was unlinked is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::LinkedList;
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Relaxed, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let head: L = L::default();
+    /// let mut entry: Shared<L> = Shared::new(L(AtomicShared::null(), 1));
+    ///
+    /// let guard = Guard::new();
+    /// assert!(head.push_back(entry.clone(), false, Release, &guard).is_ok());
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 2)), false, Release, &guard).is_ok());
+    ///
+    /// // Delete the entry, and unlink it by traversing the linked list.
+    /// assert!(entry.delete_self(Release));
+    /// assert!(head.iter_list(&guard).map(|e| e.1).eq([2]));
+    /// drop(guard);
+    ///
+    /// // No other threads can access the entry.
+    /// unsafe { entry.get_mut().unwrap().reset_link() };
+    /// assert!(!entry.is_deleted(Relaxed));
+    ///
+    /// let guard = Guard::new();
+    /// assert!(head.push_back(entry, false, Release, &guard).is_ok());
+    /// assert!(head.iter_list(&guard).map(|e| e.1).eq([1, 2]));
+    /// ```
+    #[inline]
+    fn reset_link(&mut self) {
+        self.link_ref().swap((None, Tag::None), Relaxed);
+    }
+
     /// Appends the given entry to `self` and returns a pointer to the entry.
     ///
     /// If `mark` is given `true`, it atomically marks an internal flag on `self` when updating
@@ -273,6 +361,257 @@ pub trait LinkedList: Sized {
         Err(entry)
     }
 
+    /// Inserts the given entry into a sorted linked list following `self`, and returns a pointer
+    /// to the entry.
+    ///
+    /// `cmp` compares an entry in the linked list with the supplied entry, and the supplied entry
+    /// is inserted right before the first entry that is greater than it. Marks on the entry that
+    /// precedes the inserted one are preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied [`Shared`] when it finds `self` deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::LinkedList;
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// for v in [3, 1, 2] {
+    ///     let entry = Shared::new(L(AtomicShared::null(), v));
+    ///     assert!(head.insert_sorted_list(entry, |a, b| a.1.cmp(&b.1), Release, &guard).is_ok());
+    /// }
+    ///
+    /// let first = head.next_ptr(Acquire, &guard);
+    /// let second = first.as_ref().unwrap().next_ptr(Acquire, &guard);
+    /// let third = second.as_ref().unwrap().next_ptr(Acquire, &guard);
+    /// assert_eq!(first.as_ref().unwrap().1, 1);
+    /// assert_eq!(second.as_ref().unwrap().1, 2);
+    /// assert_eq!(third.as_ref().unwrap().1, 3);
+    ///
+    /// head.delete_self(Relaxed);
+    /// let entry = Shared::new(L(AtomicShared::null(), 0));
+    /// assert!(head.insert_sorted_list(entry, |a, b| a.1.cmp(&b.1), Release, &guard).is_err());
+    /// ```
+    #[inline]
+    fn insert_sorted_list<'g, F: FnMut(&Self, &Self) -> cmp::Ordering>(
+        &self,
+        mut entry: Shared<Self>,
+        mut cmp: F,
+        order: Ordering,
+        guard: &'g Guard,
+    ) -> Result<Ptr<'g, Self>, Shared<Self>> {
+        'restart: while !self.is_deleted(Relaxed) {
+            let mut prev = self;
+            loop {
+                let next_ptr = prev.next_ptr(Acquire, guard);
+                if let Some(next) = next_ptr.as_ref() {
+                    if cmp(next, &entry) != cmp::Ordering::Greater {
+                        prev = next;
+                        continue;
+                    }
+                }
+
+                // Try to link `entry` between `prev` and `next`.
+                let link_ptr = prev.link_ref().load(Acquire, guard);
+                let tag = link_ptr.tag();
+                if tag == Tag::Second || tag == Tag::Both {
+                    // `prev` has been deleted.
+                    continue 'restart;
+                }
+                let next_entry = next_ptr.get_shared();
+                if link_ptr.without_tag() != next_ptr.without_tag()
+                    || (!next_ptr.is_null() && next_entry.is_none())
+                {
+                    // There is an invalid entry or a new entry between `prev` and `next`.
+                    continue;
+                }
+                entry.link_ref().swap((next_entry, Tag::None), Relaxed);
+                match prev.link_ref().compare_exchange(
+                    link_ptr,
+                    (Some(entry), tag),
+                    order,
+                    Relaxed,
+                    guard,
+                ) {
+                    Ok((_, updated)) => return Ok(updated),
+                    Err((passed, _)) => {
+                        entry = unsafe { passed.unwrap_unchecked() };
+                    }
+                }
+            }
+        }
+
+        // `self` has been deleted.
+        entry.link_ref().swap((None, Tag::None), Relaxed);
+        Err(entry)
+    }
+
+    /// Deletes all the entries following `self` that satisfy the given condition, and returns
+    /// the number of deleted entries.
+    ///
+    /// Deleted entries are unlinked while traversing the linked list; entries that are deleted
+    /// by other threads are neither examined nor counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::LinkedList;
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// for v in 0..4 {
+    ///     let entry = Shared::new(L(AtomicShared::null(), v));
+    ///     assert!(head.push_back(entry, false, Release, &guard).is_ok());
+    /// }
+    ///
+    /// assert_eq!(head.remove_if_list(|e| e.1 % 2 == 1, Release, &guard), 2);
+    ///
+    /// let first = head.next_ptr(Acquire, &guard);
+    /// let second = first.as_ref().unwrap().next_ptr(Acquire, &guard);
+    /// assert_eq!(first.as_ref().unwrap().1, 2);
+    /// assert_eq!(second.as_ref().unwrap().1, 0);
+    /// assert!(second.as_ref().unwrap().next_ptr(Acquire, &guard).is_null());
+    /// ```
+    #[inline]
+    fn remove_if_list<F: FnMut(&Self) -> bool>(
+        &self,
+        mut cond: F,
+        order: Ordering,
+        guard: &Guard,
+    ) -> usize {
+        let mut removed = 0;
+        let mut prev = self;
+        let mut next_ptr = self.next_ptr(Acquire, guard);
+        while let Some(next) = next_ptr.as_ref() {
+            if cond(next) {
+                if next.delete_self(order) {
+                    removed += 1;
+                }
+
+                // Unlink `next` from `prev`.
+                next_ptr = prev.next_ptr(Acquire, guard);
+            } else {
+                prev = next;
+                next_ptr = next.next_ptr(Acquire, guard);
+            }
+        }
+        removed
+    }
+
+    /// Appends the chain of entries starting from `chain` to the last entry of the linked list
+    /// following `self`, and returns a pointer to `chain`.
+    ///
+    /// The chain is spliced onto the last entry with a single successful compare-and-swap; if the
+    /// last entry is deleted in the meantime, the new last entry is searched for.
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied [`Shared`] when it finds `self` deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::LinkedList;
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    ///
+    /// let chain: L = L::default();
+    /// assert!(chain.push_back(Shared::new(L(AtomicShared::null(), 3)), false, Release, &guard).is_ok());
+    /// assert!(chain.push_back(Shared::new(L(AtomicShared::null(), 2)), false, Release, &guard).is_ok());
+    ///
+    /// let first = chain.next_shared(Acquire, &guard).unwrap();
+    /// assert!(head.append_list(first, Release, &guard).is_ok());
+    ///
+    /// let mut values = Vec::new();
+    /// let mut current = head.next_ptr(Acquire, &guard);
+    /// while let Some(entry) = current.as_ref() {
+    ///     values.push(entry.1);
+    ///     current = entry.next_ptr(Acquire, &guard);
+    /// }
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    #[inline]
+    fn append_list<'g>(
+        &self,
+        mut chain: Shared<Self>,
+        order: Ordering,
+        guard: &'g Guard,
+    ) -> Result<Ptr<'g, Self>, Shared<Self>> {
+        'restart: while !self.is_deleted(Relaxed) {
+            let mut last = self;
+            loop {
+                let next_ptr = last.next_ptr(Acquire, guard);
+                if let Some(next) = next_ptr.as_ref() {
+                    last = next;
+                    continue;
+                }
+
+                let link_ptr = last.link_ref().load(Acquire, guard);
+                let tag = link_ptr.tag();
+                if tag == Tag::Second || tag == Tag::Both {
+                    // `last` has been deleted.
+                    continue 'restart;
+                } else if !link_ptr.is_null() {
+                    // There is an invalid entry or a new entry after `last`.
+                    continue;
+                }
+                match last.link_ref().compare_exchange(
+                    link_ptr,
+                    (Some(chain), tag),
+                    order,
+                    Relaxed,
+                    guard,
+                ) {
+                    Ok((_, updated)) => return Ok(updated),
+                    Err((passed, _)) => {
+                        chain = unsafe { passed.unwrap_unchecked() };
+                    }
+                }
+            }
+        }
+
+        // `self` has been deleted.
+        Err(chain)
+    }
+
     /// Returns the closest next valid entry.
     ///
     /// It unlinks deleted entries until it reaches a valid one.
@@ -352,6 +691,42 @@ pub trait LinkedList: Sized {
         }
         next_entry
     }
+
+    /// Returns an [`Iter`] over the entries following `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::LinkedList;
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Relaxed, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// let deleted: Shared<L> = Shared::new(L(AtomicShared::null(), 2));
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 3)), false, Release, &guard).is_ok());
+    /// assert!(head.push_back(deleted.clone(), false, Release, &guard).is_ok());
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    /// assert!(deleted.delete_self(Relaxed));
+    ///
+    /// assert!(head.iter_list(&guard).map(|e| e.1).eq([1, 3]));
+    /// ```
+    #[inline]
+    fn iter_list<'g>(&self, guard: &'g Guard) -> Iter<'g, Self> {
+        Iter {
+            next: self.next_ptr(Acquire, guard),
+            guard,
+        }
+    }
 }
 
 /// [`Entry`] stores an instance of `T` and a link to the next entry.
@@ -495,6 +870,753 @@ impl<T: PartialEq> PartialEq for Entry<T> {
     }
 }
 
+/// [`Cursor`] points to an entry of a [`LinkedList`], and moves forward skipping deleted entries.
+///
+/// [`Cursor`] remembers the last valid entry it passed, so that it can still insert an entry or
+/// unlink the current entry after the current entry was deleted by another thread.
+pub struct Cursor<'g, L: LinkedList> {
+    /// The entry that the [`Cursor`] points to, or `None` if it has moved past the last entry.
+    current: Option<&'g L>,
+
+    /// The entry that the [`Cursor`] pointed to before the current one.
+    prev: Option<&'g L>,
+
+    /// The [`Guard`] that protects the entries.
+    guard: &'g Guard,
+}
+
+impl<'g, L: LinkedList> Cursor<'g, L> {
+    /// Creates a new [`Cursor`] pointing to `head`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{LinkedCursor, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// let cursor = LinkedCursor::new(&head, &guard);
+    /// assert_eq!(cursor.current().map(|e| e.1), Some(0));
+    /// ```
+    #[inline]
+    pub fn new(head: &'g L, guard: &'g Guard) -> Self {
+        Self {
+            current: Some(head),
+            prev: None,
+            guard,
+        }
+    }
+
+    /// Returns a reference to the entry that the [`Cursor`] points to.
+    ///
+    /// Returns `None` if the [`Cursor`] has moved past the last entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{LinkedCursor, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// let mut cursor = LinkedCursor::new(&head, &guard);
+    /// assert!(cursor.current().is_some());
+    /// assert!(cursor.move_next().is_none());
+    /// assert!(cursor.current().is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn current(&self) -> Option<&'g L> {
+        self.current
+    }
+
+    /// Moves the [`Cursor`] to the next valid entry, and returns a reference to it.
+    ///
+    /// Deleted entries are skipped and unlinked. Returns `None` if there is no next valid entry,
+    /// and the [`Cursor`] stays past the last entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{LinkedCursor, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Relaxed, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// let deleted: Shared<L> = Shared::new(L(AtomicShared::null(), 2));
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 3)), false, Release, &guard).is_ok());
+    /// assert!(head.push_back(deleted.clone(), false, Release, &guard).is_ok());
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    /// assert!(deleted.delete_self(Relaxed));
+    ///
+    /// let mut cursor = LinkedCursor::new(&head, &guard);
+    /// assert_eq!(cursor.move_next().map(|e| e.1), Some(1));
+    /// assert_eq!(cursor.move_next().map(|e| e.1), Some(3));
+    /// assert!(cursor.move_next().is_none());
+    /// ```
+    #[inline]
+    pub fn move_next(&mut self) -> Option<&'g L> {
+        let current = self.current?;
+        let next = current.next_ptr(Acquire, self.guard).as_ref();
+        if next.is_some() && !current.is_deleted(Relaxed) {
+            self.prev.replace(current);
+        }
+        self.current = next;
+        next
+    }
+
+    /// Inserts an entry right after the entry that the [`Cursor`] points to.
+    ///
+    /// If the current entry has been deleted, the entry is inserted after the previous entry
+    /// instead, which is the same position in the [`LinkedList`]. The [`Cursor`] does not move.
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied entry if the [`Cursor`] has moved past the last entry, or both the
+    /// current and previous entries have been deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{LinkedCursor, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// let mut cursor = LinkedCursor::new(&head, &guard);
+    /// assert!(cursor.insert_after(Shared::new(L(AtomicShared::null(), 2))).is_ok());
+    /// assert_eq!(cursor.move_next().map(|e| e.1), Some(2));
+    /// assert!(cursor.insert_after(Shared::new(L(AtomicShared::null(), 3))).is_ok());
+    ///
+    /// assert!(cursor.remove_current());
+    /// assert!(cursor.insert_after(Shared::new(L(AtomicShared::null(), 1))).is_ok());
+    /// assert_eq!(head.next_ptr(Acquire, &guard).as_ref().map(|e| e.1), Some(1));
+    /// assert_eq!(cursor.move_next().map(|e| e.1), Some(3));
+    /// ```
+    #[inline]
+    pub fn insert_after(&self, entry: Shared<L>) -> Result<Ptr<'g, L>, Shared<L>> {
+        let Some(current) = self.current else {
+            return Err(entry);
+        };
+        match current.push_back(entry, false, Release, self.guard) {
+            Ok(inserted) => Ok(inserted),
+            Err(entry) => {
+                // `current` has been deleted.
+                if let Some(prev) = self.prev {
+                    prev.push_back(entry, false, Release, self.guard)
+                } else {
+                    Err(entry)
+                }
+            }
+        }
+    }
+
+    /// Deletes the entry that the [`Cursor`] points to.
+    ///
+    /// The [`Cursor`] keeps pointing to the deleted entry until [`move_next`](Self::move_next)
+    /// is called. Returns `false` if the current entry had already been deleted or the [`Cursor`]
+    /// has moved past the last entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{LinkedCursor, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: L = L::default();
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    ///
+    /// let mut cursor = LinkedCursor::new(&head, &guard);
+    /// assert_eq!(cursor.move_next().map(|e| e.1), Some(1));
+    /// assert!(cursor.remove_current());
+    /// assert!(!cursor.remove_current());
+    /// assert!(head.next_ptr(Acquire, &guard).is_null());
+    /// assert!(cursor.move_next().is_none());
+    /// ```
+    #[inline]
+    pub fn remove_current(&mut self) -> bool {
+        let Some(current) = self.current else {
+            return false;
+        };
+        if !current.delete_self(Release) {
+            return false;
+        }
+        if let Some(prev) = self.prev {
+            // Unlink `current` from `prev`.
+            prev.next_ptr(Acquire, self.guard);
+        }
+        true
+    }
+}
+
+impl<L: LinkedList> Clone for Cursor<'_, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            current: self.current,
+            prev: self.prev,
+            guard: self.guard,
+        }
+    }
+}
+
+impl<L: LinkedList> Debug for Cursor<'_, L> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cursor")
+            .field("current", &self.current.map(|e| e as *const L))
+            .field("prev", &self.prev.map(|e| e as *const L))
+            .finish()
+    }
+}
+
+/// An iterator over the entries of a [`LinkedList`].
+///
+/// [`Iter`] skips deleted entries, and unlinks them while traversing the [`LinkedList`].
+pub struct Iter<'g, L: LinkedList> {
+    /// The next entry to return.
+    next: Ptr<'g, L>,
+
+    /// The [`Guard`] that protects the entries.
+    guard: &'g Guard,
+}
+
+impl<L: LinkedList> Clone for Iter<'_, L> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            guard: self.guard,
+        }
+    }
+}
+
+impl<L: LinkedList> Debug for Iter<'_, L> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("next", &self.next.as_ptr())
+            .finish()
+    }
+}
+
+impl<L: LinkedList> FusedIterator for Iter<'_, L> {}
+
+impl<'g, L: LinkedList> Iterator for Iter<'g, L> {
+    type Item = &'g L;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.next.as_ref() {
+            self.next = entry.next_ptr(Acquire, self.guard);
+            if !entry.is_deleted(Relaxed) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+}
+
+/// [`CountedHead`] is the head of a [`LinkedList`] that maintains the number of entries in the
+/// [`LinkedList`].
+///
+/// [`CountedHead`] owns the head entry, and only exposes counting versions of the
+/// [`LinkedList`] methods that link or delete entries along with read-only traversal methods. The
+/// number of entries is only accurate if no entry is deleted by calling
+/// [`LinkedList::delete_self`] on the entry instead of [`CountedHead::delete`].
+pub struct CountedHead<L: LinkedList> {
+    /// The head entry.
+    head: L,
+
+    /// The number of entries linked to the head entry.
+    len: AtomicUsize,
+}
+
+impl<L: LinkedList> CountedHead<L> {
+    /// Creates a new [`CountedHead`] from the head entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::AtomicShared;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let head: CountedHead<L> = CountedHead::new(L::default());
+    /// assert_eq!(head.len(), 0);
+    /// ```
+    #[inline]
+    pub const fn new(head: L) -> Self {
+        Self {
+            head,
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of entries.
+    ///
+    /// The value may be inaccurate while entries are being linked or deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.push_back(Shared::new(L::default()), false, Release, &guard).is_ok());
+    /// assert_eq!(head.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Relaxed)
+    }
+
+    /// Returns `true` if there are no entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.is_empty());
+    /// assert!(head.push_back(Shared::new(L::default()), false, Release, &guard).is_ok());
+    /// assert!(!head.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Appends the given entry to the head entry, and returns a pointer to the entry.
+    ///
+    /// See [`LinkedList::push_back`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied [`Shared`] when it finds the head entry deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    /// assert_eq!(head.next_ptr(Acquire, &guard).as_ref().unwrap().1, 1);
+    /// assert_eq!(head.len(), 1);
+    /// ```
+    #[inline]
+    pub fn push_back<'g>(
+        &self,
+        entry: Shared<L>,
+        mark: bool,
+        order: Ordering,
+        guard: &'g Guard,
+    ) -> Result<Ptr<'g, L>, Shared<L>> {
+        // Count the entry in advance so that deleting it never underflows the counter.
+        self.len.fetch_add(1, Relaxed);
+        self.head
+            .push_back(entry, mark, order, guard)
+            .map_err(|entry| {
+                self.len.fetch_sub(1, Relaxed);
+                entry
+            })
+    }
+
+    /// Inserts the given entry into the sorted linked list, and returns a pointer to the entry.
+    ///
+    /// See [`LinkedList::insert_sorted_list`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied [`Shared`] when it finds the head entry deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// for v in [3, 1, 2] {
+    ///     let entry = Shared::new(L(AtomicShared::null(), v));
+    ///     assert!(head.insert_sorted(entry, |a, b| a.1.cmp(&b.1), Release, &guard).is_ok());
+    /// }
+    /// assert_eq!(head.len(), 3);
+    /// ```
+    #[inline]
+    pub fn insert_sorted<'g, F: FnMut(&L, &L) -> cmp::Ordering>(
+        &self,
+        entry: Shared<L>,
+        cmp: F,
+        order: Ordering,
+        guard: &'g Guard,
+    ) -> Result<Ptr<'g, L>, Shared<L>> {
+        self.len.fetch_add(1, Relaxed);
+        self.head
+            .insert_sorted_list(entry, cmp, order, guard)
+            .map_err(|entry| {
+                self.len.fetch_sub(1, Relaxed);
+                entry
+            })
+    }
+
+    /// Appends the chain of entries starting from `chain` to the last entry of the linked list,
+    /// and returns a pointer to `chain`.
+    ///
+    /// The chain must not be accessible by other threads. See [`LinkedList::append_list`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied [`Shared`] when it finds the head entry deleted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.push_back(Shared::new(L::default()), false, Release, &guard).is_ok());
+    ///
+    /// let chain: L = L::default();
+    /// assert!(chain.push_back(Shared::new(L::default()), false, Release, &guard).is_ok());
+    /// assert!(chain.push_back(Shared::new(L::default()), false, Release, &guard).is_ok());
+    ///
+    /// assert!(head.append(chain.next_shared(Acquire, &guard).unwrap(), Release, &guard).is_ok());
+    /// assert_eq!(head.len(), 3);
+    /// ```
+    #[inline]
+    pub fn append<'g>(
+        &self,
+        chain: Shared<L>,
+        order: Ordering,
+        guard: &'g Guard,
+    ) -> Result<Ptr<'g, L>, Shared<L>> {
+        let mut num_entries = 1;
+        let mut current = chain.next_ptr(Acquire, guard);
+        while let Some(entry) = current.as_ref() {
+            num_entries += 1;
+            current = entry.next_ptr(Acquire, guard);
+        }
+        self.len.fetch_add(num_entries, Relaxed);
+        self.head.append_list(chain, order, guard).map_err(|chain| {
+            self.len.fetch_sub(num_entries, Relaxed);
+            chain
+        })
+    }
+
+    /// Deletes the given entry of the linked list.
+    ///
+    /// Returns `false` if the entry had already been deleted. See [`LinkedList::delete_self`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// let entry: Shared<L> = Shared::new(L::default());
+    /// assert!(head.push_back(entry.clone(), false, Release, &guard).is_ok());
+    ///
+    /// assert!(head.delete(&entry, Release));
+    /// assert!(!head.delete(&entry, Release));
+    /// assert_eq!(head.len(), 0);
+    /// ```
+    #[inline]
+    pub fn delete(&self, entry: &L, order: Ordering) -> bool {
+        if entry.delete_self(order) {
+            self.len.fetch_sub(1, Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Deletes all the entries that satisfy the given condition, and returns the number of
+    /// deleted entries.
+    ///
+    /// See [`LinkedList::remove_if_list`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// for v in 0..4 {
+    ///     let entry = Shared::new(L(AtomicShared::null(), v));
+    ///     assert!(head.push_back(entry, false, Release, &guard).is_ok());
+    /// }
+    ///
+    /// assert_eq!(head.remove_if(|e| e.1 % 2 == 1, Release, &guard), 2);
+    /// assert_eq!(head.len(), 2);
+    /// ```
+    #[inline]
+    pub fn remove_if<F: FnMut(&L) -> bool>(
+        &self,
+        cond: F,
+        order: Ordering,
+        guard: &Guard,
+    ) -> usize {
+        let removed = self.head.remove_if_list(cond, order, guard);
+        self.len.fetch_sub(removed, Relaxed);
+        removed
+    }
+
+    /// Returns a pointer to the first valid entry.
+    ///
+    /// See [`LinkedList::next_ptr`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.next_ptr(Acquire, &guard).is_null());
+    ///
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    /// assert_eq!(head.next_ptr(Acquire, &guard).as_ref().unwrap().1, 1);
+    /// ```
+    #[inline]
+    pub fn next_ptr<'g>(&self, order: Ordering, guard: &'g Guard) -> Ptr<'g, L> {
+        self.head.next_ptr(order, guard)
+    }
+
+    /// Returns a [`Shared`] handle to the first valid entry.
+    ///
+    /// See [`LinkedList::next_shared`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::{Acquire, Release};
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// assert!(head.push_back(Shared::new(L(AtomicShared::null(), 1)), false, Release, &guard).is_ok());
+    /// assert_eq!(head.next_shared(Acquire, &guard).unwrap().1, 1);
+    /// ```
+    #[inline]
+    pub fn next_shared(&self, order: Ordering, guard: &Guard) -> Option<Shared<L>> {
+        self.head.next_shared(order, guard)
+    }
+
+    /// Returns an [`Iter`] over the entries.
+    ///
+    /// See [`LinkedList::iter_list`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::{CountedHead, LinkedList};
+    /// use scc::ebr::{AtomicShared, Guard, Shared};
+    /// use std::sync::atomic::Ordering::Release;
+    ///
+    /// #[derive(Default)]
+    /// struct L(AtomicShared<L>, usize);
+    /// impl LinkedList for L {
+    ///     fn link_ref(&self) -> &AtomicShared<L> {
+    ///         &self.0
+    ///     }
+    /// }
+    ///
+    /// let guard = Guard::new();
+    ///
+    /// let head: CountedHead<L> = CountedHead::default();
+    /// for v in 0..4 {
+    ///     let entry = Shared::new(L(AtomicShared::null(), v));
+    ///     assert!(head.push_back(entry, false, Release, &guard).is_ok());
+    /// }
+    /// assert!(head.iter(&guard).map(|e| e.1).eq((0..4).rev()));
+    /// ```
+    #[inline]
+    pub fn iter<'g>(&self, guard: &'g Guard) -> Iter<'g, L> {
+        self.head.iter_list(guard)
+    }
+}
+
+impl<L: LinkedList + Debug> Debug for CountedHead<L> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountedHead")
+            .field("head", &self.head)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<L: LinkedList + Default> Default for CountedHead<L> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(L::default())
+    }
+}
+
 /// Recursively cleans up the linked list starting from the supplied head.
 fn next_ptr_recursive<'g, T: LinkedList>(
     head: &T,
@@ -0,0 +1,434 @@
+//! [`Sender`] and [`Receiver`] form a multi-producer multi-consumer channel backed by a [`Queue`].
+
+use super::Queue;
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug};
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Release, SeqCst};
+use std::sync::atomic::{fence, AtomicBool, AtomicUsize};
+use std::sync::Arc;
+
+/// [`Sender`] sends instances to the [`Receiver`] instances of the channel.
+///
+/// The channel is closed when all the [`Sender`] instances are dropped.
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+/// [`Receiver`] receives instances sent by the [`Sender`] instances of the channel.
+///
+/// The channel is closed when all the [`Receiver`] instances are dropped.
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+}
+
+/// The state shared among [`Sender`] and [`Receiver`] instances.
+struct Channel<T> {
+    /// The instances that are sent and not yet received.
+    queue: Queue<Slot<T>>,
+
+    /// The channel does not accept new instances once closed.
+    closed: AtomicBool,
+
+    /// The number of [`Sender`] instances.
+    num_senders: AtomicUsize,
+
+    /// The number of [`Receiver`] instances.
+    num_receivers: AtomicUsize,
+}
+
+/// [`Slot`] stores an instance until the instance is received.
+///
+/// Only the receiver that popped the entry of the [`Slot`] accesses the instance.
+struct Slot<T>(UnsafeCell<Option<T>>);
+
+/// Creates a new channel, and returns a [`Sender`] and a [`Receiver`] of the channel.
+///
+/// # Examples
+///
+/// ```
+/// use scc::mpmc;
+///
+/// let (sender, receiver) = mpmc::channel();
+///
+/// assert!(sender.send(11).is_ok());
+/// assert_eq!(receiver.try_recv(), Some(11));
+///
+/// drop(sender);
+/// assert!(receiver.is_closed());
+/// ```
+#[inline]
+#[must_use]
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel {
+        queue: Queue::default(),
+        closed: AtomicBool::new(false),
+        num_senders: AtomicUsize::new(1),
+        num_receivers: AtomicUsize::new(1),
+    });
+    (
+        Sender {
+            channel: channel.clone(),
+        },
+        Receiver { channel },
+    )
+}
+
+impl<T: 'static> Sender<T> {
+    /// Sends an instance of `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the instance if the channel is closed, including the case where the channel is
+    /// closed while the instance is being sent and no [`Receiver`] has received it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(sender.send(3).is_ok());
+    ///
+    /// drop(receiver);
+    /// assert_eq!(sender.send(7), Err(7));
+    /// ```
+    #[inline]
+    pub fn send(&self, val: T) -> Result<(), T> {
+        if self.channel.is_closed() {
+            return Err(val);
+        }
+        let entry = self.channel.queue.push(Slot(UnsafeCell::new(Some(val))));
+
+        // Either the sender observes the channel closed, or receivers observing the channel closed
+        // observe the entry.
+        fence(SeqCst);
+        if self.channel.is_closed() && self.channel.queue.remove_entry(&entry) {
+            // The entry was removed before any receiver popped it.
+            return Err(unsafe { (*entry.0.get()).take().unwrap_unchecked() });
+        }
+        Ok(())
+    }
+}
+
+impl<T> Sender<T> {
+    /// Closes the channel.
+    ///
+    /// Instances that were sent before the channel is closed can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// sender.close();
+    ///
+    /// assert_eq!(sender.send(7), Err(7));
+    /// assert_eq!(receiver.recv(), Some(3));
+    /// assert!(receiver.recv().is_none());
+    /// ```
+    #[inline]
+    pub fn close(&self) {
+        self.channel.close();
+    }
+
+    /// Returns `true` if the channel is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel::<usize>();
+    /// assert!(!sender.is_closed());
+    ///
+    /// drop(receiver);
+    /// assert!(sender.is_closed());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.channel.is_closed()
+    }
+
+    /// Returns the number of instances that are sent and not yet received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// assert_eq!(sender.len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.channel.queue.len()
+    }
+
+    /// Returns `true` if there are no instances to receive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    /// assert!(sender.is_empty());
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// assert!(!sender.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.channel.queue.is_empty()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.channel.num_senders.fetch_add(1, AcqRel);
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Sender<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("len", &self.len())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.channel.num_senders.fetch_sub(1, AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receives an instance if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(receiver.try_recv().is_none());
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// assert_eq!(receiver.try_recv(), Some(3));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn try_recv(&self) -> Option<T> {
+        self.channel.queue.pop().and_then(|entry| {
+            // The entry can only be popped once.
+            unsafe { (*entry.0.get()).take() }
+        })
+    }
+
+    /// Receives an instance, blocking the current thread until an instance is sent.
+    ///
+    /// Returns `None` if the channel is closed and there are no instances to receive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// let thread = std::thread::spawn(move || {
+    ///     assert!(sender.send(3).is_ok());
+    /// });
+    ///
+    /// assert_eq!(receiver.recv(), Some(3));
+    /// assert!(receiver.recv().is_none());
+    /// assert!(thread.join().is_ok());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn recv(&self) -> Option<T> {
+        self.channel
+            .queue
+            .pop_wait_unless(None, || self.is_closed())
+            .and_then(|entry| {
+                // The entry can only be popped once.
+                unsafe { (*entry.0.get()).take() }
+            })
+    }
+
+    /// Receives an instance, waiting for an instance to be sent.
+    ///
+    /// Returns `None` if the channel is closed and there are no instances to receive. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel::<usize>();
+    ///
+    /// let future_recv = receiver.recv_async();
+    /// ```
+    #[inline]
+    pub async fn recv_async(&self) -> Option<T> {
+        self.channel
+            .queue
+            .pop_async_unless(|| self.is_closed())
+            .await
+            .and_then(|entry| {
+                // The entry can only be popped once.
+                unsafe { (*entry.0.get()).take() }
+            })
+    }
+
+    /// Closes the channel.
+    ///
+    /// Instances that were sent before the channel is closed can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// receiver.close();
+    ///
+    /// assert_eq!(sender.send(7), Err(7));
+    /// assert_eq!(receiver.try_recv(), Some(3));
+    /// ```
+    #[inline]
+    pub fn close(&self) {
+        self.channel.close();
+    }
+
+    /// Returns `true` if the channel is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel::<usize>();
+    /// assert!(!receiver.is_closed());
+    ///
+    /// drop(sender);
+    /// assert!(receiver.is_closed());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_closed(&self) -> bool {
+        self.channel.is_closed()
+    }
+
+    /// Returns the number of instances that are sent and not yet received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// assert_eq!(receiver.len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.channel.queue.len()
+    }
+
+    /// Returns `true` if there are no instances to receive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::mpmc;
+    ///
+    /// let (sender, receiver) = mpmc::channel();
+    /// assert!(receiver.is_empty());
+    ///
+    /// assert!(sender.send(3).is_ok());
+    /// assert!(!receiver.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.channel.queue.is_empty()
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.channel.num_receivers.fetch_add(1, AcqRel);
+        Self {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Receiver<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .field("closed", &self.is_closed())
+            .finish()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.channel.num_receivers.fetch_sub(1, AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Channel<T> {
+    /// Closes the channel, and wakes up all the waiting receivers.
+    fn close(&self) {
+        self.closed.store(true, Release);
+        self.queue.wake_consumers();
+    }
+
+    /// Returns `true` if the channel is closed.
+    ///
+    /// Once it returns `true`, instances sent by senders that observed the channel open are
+    /// visible to the caller.
+    fn is_closed(&self) -> bool {
+        if self.closed.load(Acquire) {
+            fence(SeqCst);
+            return true;
+        }
+        false
+    }
+}
+
+unsafe impl<T: Send> Send for Slot<T> {}
+unsafe impl<T: Send> Sync for Slot<T> {}
@@ -2,9 +2,13 @@
 
 use super::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
 use super::linked_list::{Entry, LinkedList};
+use super::wait_queue::{AsyncWait, DeriveAsyncWait, WaitQueue};
 use std::fmt::{self, Debug};
 use std::iter::FusedIterator;
+use std::pin::Pin;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
+use std::time::{Duration, Instant};
 
 /// [`Queue`] is a lock-free concurrent first-in-first-out container.
 pub struct Queue<T> {
@@ -13,6 +17,27 @@ pub struct Queue<T> {
 
     /// `newest` *eventually* points to the newest entry in the [`Queue`].
     newest: AtomicShared<Entry<T>>,
+
+    /// The number of entries including those being pushed or reserved by a [`BoundedQueue`].
+    len: AtomicUsize,
+
+    /// Consumers waiting for an entry to be pushed.
+    waiting_consumers: WaitQueue,
+}
+
+/// [`BoundedQueue`] is a [`Queue`] that can hold up to a fixed number of entries.
+///
+/// Pushing an entry into a full [`BoundedQueue`] either fails or waits until an entry is popped,
+/// thereby applying backpressure to producers.
+pub struct BoundedQueue<T> {
+    /// The underlying [`Queue`].
+    queue: Queue<T>,
+
+    /// The maximum number of entries.
+    capacity: usize,
+
+    /// Producers waiting for an entry to be popped.
+    waiting_producers: WaitQueue,
 }
 
 /// An iterator over the entries of a [`Queue`].
@@ -23,6 +48,15 @@ pub struct Iter<'g, T> {
     guard: &'g Guard,
 }
 
+/// An owning iterator over the entries of a [`Queue`].
+///
+/// [`IntoIter`] yields the oldest entry first. Each yielded entry is detached from the rest, and
+/// therefore the instance can be moved out of it through [`Entry::take_inner`] unless the entry
+/// is referenced elsewhere.
+pub struct IntoIter<T> {
+    current: Option<Shared<Entry<T>>>,
+}
+
 impl<T: 'static> Queue<T> {
     /// Pushes an instance of `T`.
     ///
@@ -74,6 +108,48 @@ impl<T: 'static> Queue<T> {
         self.push_if_internal(val, cond, &Guard::new())
     }
 
+    /// Pushes all the instances of `T` yielded by the iterator.
+    ///
+    /// The instances are linked together before being appended to the [`Queue`] at once,
+    /// therefore they are not interleaved with entries pushed by other threads.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    ///
+    /// let queue: Queue<usize> = Queue::default();
+    ///
+    /// queue.push(1);
+    /// queue.push_many([2, 3, 4]);
+    ///
+    /// assert_eq!(queue.len(), 4);
+    /// assert_eq!(queue.pop().map(|e| **e), Some(1));
+    /// assert_eq!(queue.pop().map(|e| **e), Some(2));
+    /// ```
+    #[inline]
+    pub fn push_many<I: IntoIterator<Item = T>>(&self, iter: I) {
+        let mut iter = iter.into_iter();
+        let Some(val) = iter.next() else {
+            return;
+        };
+        let first = unsafe { Shared::new_unchecked(Entry::new(val)) };
+        let mut last = first.clone();
+        let mut num_entries = 1;
+        for val in iter {
+            let new_entry = unsafe { Shared::new_unchecked(Entry::new(val)) };
+            last.next()
+                .swap((Some(new_entry.clone()), Tag::None), Relaxed);
+            last = new_entry;
+            num_entries += 1;
+        }
+
+        let guard = Guard::new();
+        self.len.fetch_add(num_entries, Relaxed);
+        let appended = self.append(&first, &last, self.newest_ptr(&guard), |_| true, &guard);
+        debug_assert!(appended);
+    }
+
     /// Returns a guarded reference to the oldest entry.
     ///
     /// Returns `None` if the [`Queue`] is empty. The returned reference can survive as long as the
@@ -197,6 +273,101 @@ impl<T> Queue<T> {
         }
     }
 
+    /// Pops up to `max_entries` oldest entries.
+    ///
+    /// Unlike [`push_many`](Self::push_many), it does not complete in a constant number of atomic
+    /// operations: each popped entry is claimed by marking it deleted, which costs one
+    /// compare-and-swap per entry, and then the popped entries are unlinked from the [`Queue`] at
+    /// once. Entries that are popped by other threads in the meantime are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    ///
+    /// let queue: Queue<usize> = Queue::default();
+    ///
+    /// queue.push_many([1, 2, 3]);
+    ///
+    /// let popped = queue.pop_many(2);
+    /// assert_eq!(popped.iter().map(|e| ***e).collect::<Vec<_>>(), [1, 2]);
+    /// assert_eq!(queue.pop_many(2).len(), 1);
+    /// assert!(queue.pop_many(2).is_empty());
+    /// ```
+    #[inline]
+    pub fn pop_many(&self, max_entries: usize) -> Vec<Shared<Entry<T>>> {
+        let guard = Guard::new();
+        let mut popped = Vec::new();
+        let mut current = self.oldest.load(Acquire, &guard);
+        while popped.len() < max_entries {
+            let Some(entry) = current.as_ref() else {
+                break;
+            };
+            if let Some(entry) = current.get_shared() {
+                if entry.delete_self(Relaxed) {
+                    popped.push(entry);
+                }
+            }
+            current = entry.next_ptr(Acquire, &guard);
+        }
+        if !popped.is_empty() {
+            self.len.fetch_sub(popped.len(), Relaxed);
+            self.cleanup_oldest(&guard);
+        }
+        popped
+    }
+
+    /// Pops the oldest entry, waiting for an entry to be pushed if the [`Queue`] is empty.
+    ///
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    ///
+    /// let queue: Queue<usize> = Queue::default();
+    ///
+    /// let future_pop = queue.pop_async();
+    /// ```
+    #[inline]
+    pub async fn pop_async(&self) -> Shared<Entry<T>> {
+        match self.pop_async_unless(|| false).await {
+            Some(popped) => popped,
+            None => unreachable!(),
+        }
+    }
+
+    /// Pops the oldest entry, blocking the current thread until an entry is pushed or the
+    /// timeout elapses.
+    ///
+    /// Returns `None` if no entries were pushed before the timeout elapsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+    ///
+    /// assert!(queue.pop_wait(Duration::from_millis(1)).is_none());
+    ///
+    /// let queue_clone = queue.clone();
+    /// let thread = thread::spawn(move || {
+    ///     queue_clone.push(3);
+    /// });
+    ///
+    /// assert_eq!(queue.pop_wait(Duration::from_secs(60)).map(|e| **e), Some(3));
+    /// assert!(thread.join().is_ok());
+    /// ```
+    #[inline]
+    pub fn pop_wait(&self, timeout: Duration) -> Option<Shared<Entry<T>>> {
+        self.pop_wait_unless(Instant::now().checked_add(timeout), || false)
+    }
+
     /// Pops the oldest entry if the entry satisfies the given condition.
     ///
     /// Returns `None` if the [`Queue`] is empty.
@@ -232,6 +403,7 @@ impl<T> Queue<T> {
                     return Err(oldest_entry);
                 }
                 if oldest_entry.delete_self(Relaxed) {
+                    self.len.fetch_sub(1, Relaxed);
                     self.cleanup_oldest(&guard);
                     return Ok(Some(oldest_entry));
                 }
@@ -241,6 +413,47 @@ impl<T> Queue<T> {
         Ok(None)
     }
 
+    /// Removes all the entries satisfying the given condition.
+    ///
+    /// Returns the number of removed entries. Matching entries are marked deleted in place, and
+    /// then unlinked from the [`Queue`]; entries that are concurrently pushed or popped may or
+    /// may not be examined.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    ///
+    /// let queue: Queue<usize> = Queue::default();
+    ///
+    /// queue.push_many([1, 2, 3, 4]);
+    ///
+    /// assert_eq!(queue.remove_if(|v| *v % 2 == 0), 2);
+    /// assert_eq!(queue.len(), 2);
+    /// assert_eq!(queue.pop().map(|e| **e), Some(1));
+    /// assert_eq!(queue.pop().map(|e| **e), Some(3));
+    /// assert!(queue.pop().is_none());
+    /// ```
+    #[inline]
+    pub fn remove_if<F: FnMut(&T) -> bool>(&self, mut cond: F) -> usize {
+        let guard = Guard::new();
+        let mut removed = 0;
+        let mut current = self.cleanup_oldest(&guard);
+        while let Some(entry) = current.as_ref() {
+            if !entry.is_deleted(Relaxed) && cond(entry) && entry.delete_self(Relaxed) {
+                removed += 1;
+            }
+            current = entry.next_ptr(Acquire, &guard);
+        }
+        if removed != 0 {
+            self.len.fetch_sub(removed, Relaxed);
+
+            // Unlink the removed entries: `next_ptr` detaches deleted entries on traversal.
+            Self::traverse(self.cleanup_oldest(&guard), &guard);
+        }
+        removed
+    }
+
     /// Peeks the oldest entry.
     ///
     /// # Examples
@@ -273,8 +486,8 @@ impl<T> Queue<T> {
 
     /// Returns the number of entries in the [`Queue`].
     ///
-    /// This method iterates over all the entries in the [`Queue`] to count them, therefore its
-    /// time complexity is `O(N)`.
+    /// The number is read from a counter, therefore its time complexity is `O(1)`; entries being
+    /// pushed by other threads are counted before they become visible.
     ///
     /// # Examples
     ///
@@ -294,7 +507,7 @@ impl<T> Queue<T> {
     /// ```
     #[inline]
     pub fn len(&self) -> usize {
-        self.iter(&Guard::new()).count()
+        self.len.load(Relaxed)
     }
 
     /// Returns `true` if the [`Queue`] is empty.
@@ -345,6 +558,31 @@ impl<T> Queue<T> {
         }
     }
 
+    /// Scans all the entries from the oldest one without popping them.
+    ///
+    /// Entries pushed or popped by other threads during the scan may or may not be visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Queue;
+    ///
+    /// let queue: Queue<usize> = Queue::default();
+    ///
+    /// queue.push(7);
+    /// queue.push(11);
+    ///
+    /// let mut pending = Vec::new();
+    /// queue.scan(|v| pending.push(*v));
+    /// assert_eq!(pending, [7, 11]);
+    /// assert_eq!(queue.len(), 2);
+    /// ```
+    #[inline]
+    pub fn scan<F: FnMut(&T)>(&self, scanner: F) {
+        let guard = Guard::new();
+        self.iter(&guard).for_each(scanner);
+    }
+
     /// Pushes an entry into the [`Queue`].
     fn push_if_internal<F: FnMut(Option<&Entry<T>>) -> bool>(
         &self,
@@ -352,24 +590,148 @@ impl<T> Queue<T> {
         mut cond: F,
         guard: &Guard,
     ) -> Result<Shared<Entry<T>>, T> {
-        let mut newest_ptr = self.newest.load(Acquire, guard);
-        if newest_ptr.is_null() {
-            // Traverse from the oldest.
-            newest_ptr = self.oldest.load(Acquire, guard);
-        }
-        newest_ptr = Self::traverse(newest_ptr, guard);
-
+        let newest_ptr = self.newest_ptr(guard);
         if !cond(newest_ptr.as_ref()) {
             // The condition is not met.
             return Err(val);
         }
 
+        // Count the entry in advance so that popping it never underflows the counter.
+        self.len.fetch_add(1, Relaxed);
         let mut new_entry = unsafe { Shared::new_unchecked(Entry::new(val)) };
+        if self.append(&new_entry, &new_entry, newest_ptr, cond, guard) {
+            return Ok(new_entry);
+        }
+
+        // Extract the instance from the temporary entry.
+        self.len.fetch_sub(1, Relaxed);
+        Err(unsafe { new_entry.get_mut().unwrap_unchecked().take_inner() })
+    }
+
+    /// Pushes an instance of `T` that has already been counted.
+    fn push_reserved(&self, val: T) -> Shared<Entry<T>> {
+        let guard = Guard::new();
+        let new_entry = unsafe { Shared::new_unchecked(Entry::new(val)) };
+        let appended = self.append(
+            &new_entry,
+            &new_entry,
+            self.newest_ptr(&guard),
+            |_| true,
+            &guard,
+        );
+        debug_assert!(appended);
+        new_entry
+    }
+
+    /// Pops the oldest entry, blocking the current thread until an entry is pushed, the deadline
+    /// is reached, or `stop` returns `true`.
+    pub(crate) fn pop_wait_unless<S: Fn() -> bool>(
+        &self,
+        deadline: Option<Instant>,
+        stop: S,
+    ) -> Option<Shared<Entry<T>>> {
+        loop {
+            if let Some(popped) = self.pop() {
+                return Some(popped);
+            } else if stop() {
+                // Entries may have been pushed right before `stop` returned `true`.
+                return self.pop();
+            } else if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                return None;
+            }
+            let mut popped = None;
+            let f = || {
+                popped = self.pop();
+                if popped.is_some() || stop() {
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            };
+            let _result = if let Some(deadline) = deadline {
+                self.waiting_consumers.wait_sync_until(f, deadline)
+            } else {
+                self.waiting_consumers.wait_sync(f)
+            };
+            if popped.is_some() {
+                return popped;
+            }
+        }
+    }
+
+    /// Pops the oldest entry, waiting for an entry to be pushed until `stop` returns `true`.
+    pub(crate) async fn pop_async_unless<S: Fn() -> bool>(
+        &self,
+        stop: S,
+    ) -> Option<Shared<Entry<T>>> {
+        loop {
+            if let Some(popped) = self.pop() {
+                return Some(popped);
+            } else if stop() {
+                // Entries may have been pushed right before `stop` returned `true`.
+                return self.pop();
+            }
+            let mut popped = None;
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Some(async_wait) = async_wait_pinned.derive() {
+                let _result = self.waiting_consumers.push_async_entry(async_wait, || {
+                    popped = self.pop();
+                    if popped.is_some() || stop() {
+                        Ok(())
+                    } else {
+                        Err(())
+                    }
+                });
+            }
+            if popped.is_some() {
+                return popped;
+            }
+            async_wait_pinned.await;
+        }
+    }
+
+    /// Wakes up all the consumers waiting for an entry to be pushed.
+    pub(crate) fn wake_consumers(&self) {
+        self.waiting_consumers.signal();
+    }
+
+    /// Removes the entry unless it has already been popped or removed.
+    pub(crate) fn remove_entry(&self, entry: &Entry<T>) -> bool {
+        if !entry.delete_self(Relaxed) {
+            return false;
+        }
+        self.len.fetch_sub(1, Relaxed);
+        self.cleanup_oldest(&Guard::new());
+        true
+    }
+
+    /// Returns a pointer to the newest entry.
+    fn newest_ptr<'g>(&self, guard: &'g Guard) -> Ptr<'g, Entry<T>> {
+        let mut newest_ptr = self.newest.load(Acquire, guard);
+        if newest_ptr.is_null() {
+            // Traverse from the oldest.
+            newest_ptr = self.oldest.load(Acquire, guard);
+        }
+        Self::traverse(newest_ptr, guard)
+    }
+
+    /// Appends a chain of entries from `first` to `last` after the newest entry.
+    ///
+    /// Returns `false` if the newest entry does not satisfy the given condition.
+    fn append<'g, F: FnMut(Option<&Entry<T>>) -> bool>(
+        &self,
+        first: &Shared<Entry<T>>,
+        last: &Shared<Entry<T>>,
+        mut newest_ptr: Ptr<'g, Entry<T>>,
+        mut cond: F,
+        guard: &'g Guard,
+    ) -> bool {
         loop {
             let result = if let Some(newest_entry) = newest_ptr.as_ref() {
                 newest_entry.next().compare_exchange(
                     Ptr::null(),
-                    (Some(new_entry.clone()), Tag::None),
+                    (Some(first.clone()), Tag::None),
                     AcqRel,
                     Acquire,
                     guard,
@@ -377,7 +739,7 @@ impl<T> Queue<T> {
             } else {
                 self.oldest.compare_exchange(
                     newest_ptr,
-                    (Some(new_entry.clone()), Tag::None),
+                    (Some(first.clone()), Tag::None),
                     AcqRel,
                     Acquire,
                     guard,
@@ -385,13 +747,13 @@ impl<T> Queue<T> {
             };
             match result {
                 Ok(_) => {
-                    self.newest
-                        .swap((Some(new_entry.clone()), Tag::None), AcqRel);
+                    self.newest.swap((Some(last.clone()), Tag::None), AcqRel);
                     if self.oldest.is_null(Relaxed) {
                         // The `Queue` was emptied in the meantime.
                         self.newest.swap((None, Tag::None), Acquire);
                     }
-                    return Ok(new_entry);
+                    self.waiting_consumers.signal_if_waiting();
+                    return true;
                 }
                 Err((_, actual_ptr)) => {
                     newest_ptr = if actual_ptr.tag() == Tag::First {
@@ -405,14 +767,11 @@ impl<T> Queue<T> {
 
                     if !cond(newest_ptr.as_ref()) {
                         // The condition is not met.
-                        break;
+                        return false;
                     }
                 }
             }
         }
-
-        // Extract the instance from the temporary entry.
-        Err(unsafe { new_entry.get_mut().unwrap_unchecked().take_inner() })
     }
 
     /// Cleans up logically removed entries that are attached to `oldest`.
@@ -493,10 +852,28 @@ impl<T> Default for Queue<T> {
         Self {
             oldest: AtomicShared::default(),
             newest: AtomicShared::default(),
+            len: AtomicUsize::new(0),
+            waiting_consumers: WaitQueue::default(),
         }
     }
 }
 
+impl<T: 'static> Extend<T> for Queue<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.push_many(iter);
+    }
+}
+
+impl<T: 'static> FromIterator<T> for Queue<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let queue = Self::default();
+        queue.push_many(iter);
+        queue
+    }
+}
+
 impl<T> Drop for Queue<T> {
     #[inline]
     fn drop(&mut self) {
@@ -511,6 +888,36 @@ impl<T> Drop for Queue<T> {
     }
 }
 
+impl<T> IntoIterator for Queue<T> {
+    type Item = Shared<Entry<T>>;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.newest.swap((None, Tag::None), Relaxed);
+        IntoIter {
+            current: self.oldest.swap((None, Tag::None), Acquire).0,
+        }
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = Shared<Entry<T>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(entry) = self.current.take() {
+            self.current = entry.next().swap((None, Tag::None), Acquire).0;
+            if !entry.is_deleted(Relaxed) {
+                return Some(entry);
+            }
+        }
+        None
+    }
+}
+
 impl<'g, T> FusedIterator for Iter<'g, T> {}
 
 impl<'g, T> Iterator for Iter<'g, T> {
@@ -526,3 +933,285 @@ impl<'g, T> Iterator for Iter<'g, T> {
         }
     }
 }
+
+impl<T: 'static> BoundedQueue<T> {
+    /// Pushes an instance of `T` if the [`BoundedQueue`] is not full.
+    ///
+    /// Returns a [`Shared`] holding a strong reference to the newly pushed entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied instance if the [`BoundedQueue`] is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(1);
+    ///
+    /// assert_eq!(**queue.push(11).unwrap(), 11);
+    /// assert_eq!(queue.push(17).unwrap_err(), 17);
+    /// ```
+    #[inline]
+    pub fn push(&self, val: T) -> Result<Shared<Entry<T>>, T> {
+        if self.try_reserve() {
+            Ok(self.queue.push_reserved(val))
+        } else {
+            Err(val)
+        }
+    }
+
+    /// Pushes an instance of `T`, waiting for an entry to be popped if the [`BoundedQueue`] is
+    /// full.
+    ///
+    /// Returns a [`Shared`] holding a strong reference to the newly pushed entry. It is an
+    /// asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(1);
+    ///
+    /// let future_push = queue.push_async(11);
+    /// ```
+    #[inline]
+    pub async fn push_async(&self, val: T) -> Shared<Entry<T>> {
+        loop {
+            if self.try_reserve() {
+                return self.queue.push_reserved(val);
+            }
+            let mut reserved = false;
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Some(async_wait) = async_wait_pinned.derive() {
+                let _result = self.waiting_producers.push_async_entry(async_wait, || {
+                    if self.try_reserve() {
+                        reserved = true;
+                        Ok(())
+                    } else {
+                        Err(())
+                    }
+                });
+            }
+            if reserved {
+                return self.queue.push_reserved(val);
+            }
+            async_wait_pinned.await;
+        }
+    }
+}
+
+impl<T> BoundedQueue<T> {
+    /// Creates an empty [`BoundedQueue`] that can hold up to `capacity` entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(16);
+    /// assert_eq!(queue.capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            queue: Queue::default(),
+            capacity,
+            waiting_producers: WaitQueue::default(),
+        }
+    }
+
+    /// Pops the oldest entry.
+    ///
+    /// Returns `None` if the [`BoundedQueue`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(2);
+    ///
+    /// assert!(queue.push(37).is_ok());
+    /// assert!(queue.push(3).is_ok());
+    /// assert!(queue.push(1).is_err());
+    ///
+    /// assert_eq!(queue.pop().map(|e| **e), Some(37));
+    /// assert!(queue.push(1).is_ok());
+    /// ```
+    #[inline]
+    pub fn pop(&self) -> Option<Shared<Entry<T>>> {
+        let popped = self.queue.pop()?;
+        self.waiting_producers.signal_if_waiting();
+        Some(popped)
+    }
+
+    /// Pops the oldest entry, waiting for an entry to be pushed if the [`BoundedQueue`] is empty.
+    ///
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(2);
+    ///
+    /// let future_pop = queue.pop_async();
+    /// ```
+    #[inline]
+    pub async fn pop_async(&self) -> Shared<Entry<T>> {
+        let popped = self.queue.pop_async().await;
+        self.waiting_producers.signal_if_waiting();
+        popped
+    }
+
+    /// Removes all the entries satisfying the given condition.
+    ///
+    /// Returns the number of removed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(2);
+    ///
+    /// assert!(queue.push(37).is_ok());
+    /// assert!(queue.push(3).is_ok());
+    /// assert!(queue.push(1).is_err());
+    ///
+    /// assert_eq!(queue.remove_if(|v| *v == 37), 1);
+    /// assert!(queue.push(1).is_ok());
+    /// ```
+    #[inline]
+    pub fn remove_if<F: FnMut(&T) -> bool>(&self, cond: F) -> usize {
+        let removed = self.queue.remove_if(cond);
+        if removed != 0 {
+            self.waiting_producers.signal_if_waiting();
+        }
+        removed
+    }
+
+    /// Peeks the oldest entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(2);
+    ///
+    /// assert!(queue.peek_with(|v| v.is_none()));
+    ///
+    /// assert!(queue.push(37).is_ok());
+    /// assert_eq!(queue.peek_with(|v| **v.unwrap()), 37);
+    /// ```
+    #[inline]
+    pub fn peek_with<R, F: FnOnce(Option<&Entry<T>>) -> R>(&self, reader: F) -> R {
+        self.queue.peek_with(reader)
+    }
+
+    /// Returns the maximum number of entries the [`BoundedQueue`] can hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+    /// assert_eq!(queue.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries in the [`BoundedQueue`].
+    ///
+    /// Entries being pushed are also counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+    /// assert_eq!(queue.len(), 0);
+    ///
+    /// assert!(queue.push(7).is_ok());
+    /// assert!(queue.push(11).is_ok());
+    /// assert_eq!(queue.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if the [`BoundedQueue`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+    /// assert!(queue.is_empty());
+    ///
+    /// assert!(queue.push(7).is_ok());
+    /// assert!(!queue.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Returns an [`Iter`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::queue::BoundedQueue;
+    ///
+    /// let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+    ///
+    /// assert!(queue.push(7).is_ok());
+    /// assert!(queue.push(11).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = queue.iter(&guard);
+    /// assert_eq!(*iter.next().unwrap(), 7);
+    /// assert_eq!(*iter.next().unwrap(), 11);
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn iter<'g>(&self, guard: &'g Guard) -> Iter<'g, T> {
+        self.queue.iter(guard)
+    }
+
+    /// Tries to reserve a slot for a new entry by counting it in the underlying [`Queue`].
+    fn try_reserve(&self) -> bool {
+        self.queue
+            .len
+            .fetch_update(AcqRel, Acquire, |len| {
+                if len < self.capacity {
+                    Some(len + 1)
+                } else {
+                    None
+                }
+            })
+            .is_ok()
+    }
+}
+
+impl<T: Debug> Debug for BoundedQueue<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.queue.fmt(f)
+    }
+}
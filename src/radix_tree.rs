@@ -0,0 +1,1050 @@
+//! [`RadixTree`] is a concurrent adaptive radix tree keyed by byte strings.
+
+use crate::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::panic::UnwindSafe;
+use std::ptr;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed, SeqCst};
+use std::sync::atomic::{fence, AtomicUsize};
+
+/// Scalable concurrent adaptive radix tree.
+///
+/// [`RadixTree`] is a read-optimized ordered map keyed by byte strings. Keys sharing a prefix
+/// share the nodes for the prefix, and a chain of nodes having a single child is compressed into
+/// one node, therefore a lookup examines each byte of the key at most once regardless of how many
+/// keys share the prefix. It also enables [`RadixTree`] to efficiently enumerate the keys under
+/// a prefix and to find the longest key that is a prefix of a given key. Keys are not stored
+/// separately since the path to a node spells the key, and [`Iter`] reassembles them.
+///
+/// ## Notes
+///
+/// [`RadixTree`] methods are linearizable, however its iterator methods are not; [`Iter`] is only
+/// guaranteed to observe events happened before the first call to [`Iterator::next`].
+///
+/// ## The key features of [`RadixTree`]
+///
+/// * Lock-free-read: read and scan operations do not modify shared data and are never blocked.
+/// * Lock-free write: a node is replaced with an updated copy when its layout changes, and a
+///   thread that encounters a node being replaced helps complete the replacement.
+/// * Adaptive nodes: a node keeps its children in a sorted array until the number of children
+///   exceeds the threshold, and then switches to an array directly indexed by the next byte.
+/// * Compaction: removing an entry prunes the nodes left without entries and children, and merges
+///   a node left without an entry into its only child.
+///
+/// ## The key statistics for [`RadixTree`]
+///
+/// * The maximum number of children kept in a sorted array: 48.
+///
+/// ### Unwind safety
+///
+/// [`RadixTree`] is impervious to out-of-memory errors and panics in user specified code on one
+/// condition; `V::drop` must not panic.
+pub struct RadixTree<V> {
+    /// The root node.
+    root: AtomicShared<Node<V>>,
+
+    /// The number of entries in the [`RadixTree`].
+    len: AtomicUsize,
+}
+
+/// An iterator over the entries of a [`RadixTree`].
+///
+/// An [`Iter`] iterates over all the entries, or all the entries under a prefix, that survive the
+/// [`Iter`] in lexicographic order of their keys. The keys are reassembled from the paths to the
+/// entries, and therefore they are returned by value.
+pub struct Iter<'g, V> {
+    /// The nodes being scanned, the position in each node, and the length of the key up to the
+    /// end of the prefix of each node.
+    stack: Vec<(&'g Node<V>, usize, usize)>,
+
+    /// The key of the node being scanned.
+    key: Vec<u8>,
+
+    guard: &'g Guard,
+}
+
+/// The maximum number of children kept in a sorted array.
+const SPARSE_CAPACITY: usize = 48;
+
+/// A node of a [`RadixTree`].
+///
+/// The links of a node are tagged with [`Tag::First`] when the node is frozen in order to be
+/// replaced with a copy; the entry link is always tagged first.
+struct Node<V> {
+    /// The compressed path following the byte that leads to the node.
+    prefix: Box<[u8]>,
+
+    /// The entry whose key ends at the node.
+    entry: AtomicShared<Entry<V>>,
+
+    /// The children of the node.
+    children: Children<V>,
+}
+
+/// The value stored in a [`RadixTree`].
+///
+/// It is only `None` if the value was moved out of an entry that has never been reachable.
+type Entry<V> = Option<V>;
+
+/// The children of a [`Node`].
+enum Children<V> {
+    /// Links to the children sorted by the byte leading to each child.
+    Sparse(Box<[u8]>, Box<[AtomicShared<Node<V>>]>),
+
+    /// A link for every byte value.
+    Dense(Box<[AtomicShared<Node<V>>]>),
+}
+
+impl<V> RadixTree<V> {
+    /// Creates an empty [`RadixTree`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            root: AtomicShared::null(),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of entries in the [`RadixTree`].
+    ///
+    /// It reads an internal counter, and therefore the time complexity is O(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    /// assert_eq!(radixtree.len(), 0);
+    ///
+    /// assert!(radixtree.insert("a", 10).is_ok());
+    /// assert_eq!(radixtree.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Relaxed)
+    }
+
+    /// Returns `true` if the [`RadixTree`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an [`Iter`].
+    ///
+    /// Key-value pairs are scanned in lexicographic order of their keys, and key-value pairs that
+    /// have existed since the invocation of the method are guaranteed to be visited if they are
+    /// not removed. However, it is possible to visit removed key-value pairs momentarily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    /// use scc::ebr::Guard;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("ab", 20).is_ok());
+    /// assert!(radixtree.insert("a", 10).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = radixtree.iter(&guard);
+    /// assert_eq!(iter.next(), Some((b"a".to_vec(), &10)));
+    /// assert_eq!(iter.next(), Some((b"ab".to_vec(), &20)));
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn iter<'g>(&self, guard: &'g Guard) -> Iter<'g, V> {
+        Iter::new(self.root.load(Acquire, guard), &[], guard)
+    }
+
+    /// Returns an [`Iter`] that scans keys starting with the given prefix.
+    ///
+    /// Key-value pairs are scanned in lexicographic order of their keys, and key-value pairs that
+    /// have existed since the invocation of the method are guaranteed to be visited if they are
+    /// not removed. However, it is possible to visit removed key-value pairs momentarily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    /// use scc::ebr::Guard;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("car", 1).is_ok());
+    /// assert!(radixtree.insert("cart", 2).is_ok());
+    /// assert!(radixtree.insert("cat", 3).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = radixtree.iter_prefix("car", &guard);
+    /// assert_eq!(iter.next(), Some((b"car".to_vec(), &1)));
+    /// assert_eq!(iter.next(), Some((b"cart".to_vec(), &2)));
+    /// assert!(iter.next().is_none());
+    /// assert_eq!(radixtree.iter_prefix("ca", &guard).count(), 3);
+    /// assert_eq!(radixtree.iter_prefix("dog", &guard).count(), 0);
+    /// ```
+    #[inline]
+    pub fn iter_prefix<'g, Q: AsRef<[u8]> + ?Sized>(
+        &self,
+        prefix: &Q,
+        guard: &'g Guard,
+    ) -> Iter<'g, V> {
+        let prefix = prefix.as_ref();
+        let mut link = &self.root;
+        let mut rest = prefix;
+        loop {
+            let node_ptr = link.load(Acquire, guard);
+            let Some(node) = node_ptr.as_ref() else {
+                break;
+            };
+            if node.prefix.starts_with(rest) {
+                // Every key in the sub-tree starts with the prefix.
+                return Iter::new(node_ptr, &prefix[..prefix.len() - rest.len()], guard);
+            }
+            let Some((&byte, tail)) = rest
+                .strip_prefix(&*node.prefix)
+                .and_then(<[u8]>::split_first)
+            else {
+                break;
+            };
+            let Some(child) = node.children.link(byte) else {
+                break;
+            };
+            link = child;
+            rest = tail;
+        }
+        Iter::new(Ptr::null(), &[], guard)
+    }
+}
+
+impl<V: 'static> RadixTree<V> {
+    /// Inserts a key-value pair.
+    ///
+    /// The key is copied into the [`RadixTree`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied value if the key exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("/api/v1", 10).is_ok());
+    /// assert!(radixtree.insert(b"/api/v2", 20).is_ok());
+    /// assert_eq!(radixtree.insert("/api/v1", 11).err().unwrap(), 11);
+    /// assert_eq!(radixtree.peek_with("/api/v1", |_, v| *v).unwrap(), 10);
+    /// ```
+    #[inline]
+    pub fn insert<Q: AsRef<[u8]> + ?Sized>(&self, key: &Q, val: V) -> Result<(), V> {
+        let key = key.as_ref();
+        let guard = Guard::new();
+        let mut entry = Shared::new(Some(val));
+        self.len.fetch_add(1, Relaxed);
+        'retry: loop {
+            let mut link = &self.root;
+            let mut rest = key;
+            loop {
+                let node_ptr = link.load(Acquire, &guard);
+                if node_ptr.tag() != Tag::None {
+                    // The parent node is frozen.
+                    continue 'retry;
+                }
+                let new_node = if let Some(node) = node_ptr.as_ref() {
+                    if node.is_frozen() {
+                        Node::replace(link, node_ptr, &guard);
+                        continue 'retry;
+                    }
+                    let common = common_prefix_len(&node.prefix, rest);
+                    if common < node.prefix.len() {
+                        Some(node.split(common, rest, entry, &guard))
+                    } else if let Some((&byte, tail)) = rest[common..].split_first() {
+                        if let Some(child) = node.children.link(byte) {
+                            link = child;
+                            rest = tail;
+                            continue;
+                        }
+
+                        // Replace the node with a copy having the new child.
+                        node.freeze();
+                        let leaf = Shared::new(Node::leaf(tail, entry));
+                        node.copy(&node.prefix, Some((byte, leaf)), &guard)
+                    } else {
+                        let entry_ptr = node.entry.load(Acquire, &guard);
+                        if entry_ptr.tag() != Tag::None {
+                            continue 'retry;
+                        } else if !entry_ptr.is_null() {
+                            // The entry has never been reachable, and therefore the value can be
+                            // moved out of it.
+                            self.len.fetch_sub(1, Relaxed);
+                            return Err(unsafe {
+                                entry.get_mut().and_then(Option::take).unwrap_unchecked()
+                            });
+                        }
+                        match node.entry.compare_exchange(
+                            entry_ptr,
+                            (Some(entry), Tag::None),
+                            AcqRel,
+                            Acquire,
+                            &guard,
+                        ) {
+                            Ok(_) => return Ok(()),
+                            Err((passed, _)) => {
+                                entry = unsafe { passed.unwrap_unchecked() };
+                                continue 'retry;
+                            }
+                        }
+                    }
+                } else {
+                    Some(Node::leaf(rest, entry))
+                };
+                match link.compare_exchange(
+                    node_ptr,
+                    (new_node.map(Shared::new), Tag::None),
+                    AcqRel,
+                    Acquire,
+                    &guard,
+                ) {
+                    Ok(_) => return Ok(()),
+                    Err((passed, _)) => {
+                        entry = Node::reclaim(passed, rest);
+                        continue 'retry;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes a key-value pair.
+    ///
+    /// Returns `false` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(!radixtree.remove("a"));
+    /// assert!(radixtree.insert("a", 10).is_ok());
+    /// assert!(radixtree.remove("a"));
+    /// ```
+    #[inline]
+    pub fn remove<Q: AsRef<[u8]> + ?Sized>(&self, key: &Q) -> bool {
+        self.remove_if(key, |_| true)
+    }
+
+    /// Removes a key-value pair if the given condition is met.
+    ///
+    /// Returns `false` if the key does not exist or the condition was not met.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("a", 10).is_ok());
+    /// assert!(!radixtree.remove_if("a", |v| *v == 0));
+    /// assert!(radixtree.remove_if("a", |v| *v == 10));
+    /// ```
+    #[inline]
+    pub fn remove_if<Q: AsRef<[u8]> + ?Sized, F: FnMut(&V) -> bool>(
+        &self,
+        key: &Q,
+        mut condition: F,
+    ) -> bool {
+        let key = key.as_ref();
+        let guard = Guard::new();
+        let mut path = Vec::new();
+        'retry: loop {
+            let mut link = &self.root;
+            let mut rest = key;
+            path.clear();
+            loop {
+                let node_ptr = link.load(Acquire, &guard);
+                if node_ptr.tag() != Tag::None {
+                    // The parent node is frozen.
+                    continue 'retry;
+                }
+                let Some(node) = node_ptr.as_ref() else {
+                    return false;
+                };
+                if node.is_frozen() {
+                    Node::replace(link, node_ptr, &guard);
+                    continue 'retry;
+                }
+                let Some(remaining) = rest.strip_prefix(&*node.prefix) else {
+                    return false;
+                };
+                path.push((link, node_ptr));
+                if let Some((&byte, tail)) = remaining.split_first() {
+                    let Some(child) = node.children.link(byte) else {
+                        return false;
+                    };
+                    link = child;
+                    rest = tail;
+                    continue;
+                }
+
+                let entry_ptr = node.entry.load(Acquire, &guard);
+                if entry_ptr.tag() != Tag::None {
+                    continue 'retry;
+                }
+                let Some(val) = entry_ptr.as_ref().and_then(Option::as_ref) else {
+                    return false;
+                };
+                if !condition(val) {
+                    return false;
+                }
+                if node
+                    .entry
+                    .compare_exchange(entry_ptr, (None, Tag::None), AcqRel, Acquire, &guard)
+                    .is_err()
+                {
+                    continue 'retry;
+                }
+                self.len.fetch_sub(1, Relaxed);
+
+                // Compact the nodes on the path bottom-up until a node is left intact; the fence
+                // makes sure that either this thread or another one removing a neighbouring entry
+                // finds the parent node compactable.
+                fence(SeqCst);
+                for (link, node_ptr) in path.iter().rev() {
+                    if !node_ptr.as_ref().map_or(false, Node::is_compactable) {
+                        break;
+                    }
+                    Node::replace(link, *node_ptr, &guard);
+                    if ptr::eq(link.load(Acquire, &guard).as_ptr(), node_ptr.as_ptr()) {
+                        break;
+                    }
+                }
+                return true;
+            }
+        }
+    }
+
+    /// Returns a guarded reference to the value for the specified key without acquiring locks.
+    ///
+    /// Returns `None` if the key does not exist. The returned reference can survive as long as the
+    /// associated [`Guard`] is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// let guard = Guard::new();
+    /// assert!(radixtree.peek("foo", &guard).is_none());
+    ///
+    /// radixtree.insert("foo", 1).expect("insert in empty RadixTree");
+    /// assert_eq!(radixtree.peek("foo", &guard), Some(&1));
+    /// ```
+    #[inline]
+    pub fn peek<'g, Q: AsRef<[u8]> + ?Sized>(&self, key: &Q, guard: &'g Guard) -> Option<&'g V> {
+        self.search(key.as_ref(), guard)
+    }
+
+    /// Peeks a key-value pair without acquiring locks.
+    ///
+    /// Returns `None` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.peek_with("foo", |k, v| *v).is_none());
+    ///
+    /// radixtree.insert("foo", 1).expect("insert in empty RadixTree");
+    ///
+    /// let key: Vec<u8> = radixtree
+    ///     .peek_with("foo", |k, _v| k.to_vec())
+    ///     .expect("peek_with by borrowed key");
+    /// assert_eq!(key, b"foo");
+    /// ```
+    #[inline]
+    pub fn peek_with<Q: AsRef<[u8]> + ?Sized, R, F: FnOnce(&[u8], &V) -> R>(
+        &self,
+        key: &Q,
+        reader: F,
+    ) -> Option<R> {
+        let key = key.as_ref();
+        let guard = Guard::new();
+        self.search(key, &guard).map(|val| reader(key, val))
+    }
+
+    /// Returns a guarded reference to the key-value pair having the longest key that is a prefix of
+    /// the specified key.
+    ///
+    /// Returns `None` if no keys are a prefix of the specified key. The returned key is a prefix of
+    /// the specified key, and the returned value reference can survive as long as the associated
+    /// [`Guard`] is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("/", 0).is_ok());
+    /// assert!(radixtree.insert("/api", 1).is_ok());
+    /// assert!(radixtree.insert("/api/v1/users", 2).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// assert_eq!(radixtree.longest_prefix("/api/v1/orders", &guard), Some((&b"/api"[..], &1)));
+    /// assert_eq!(radixtree.longest_prefix("/index.html", &guard), Some((&b"/"[..], &0)));
+    /// assert!(radixtree.longest_prefix("index.html", &guard).is_none());
+    /// ```
+    #[inline]
+    pub fn longest_prefix<'g, 'k, Q: AsRef<[u8]> + ?Sized>(
+        &self,
+        key: &'k Q,
+        guard: &'g Guard,
+    ) -> Option<(&'k [u8], &'g V)> {
+        let key = key.as_ref();
+        let mut longest = None;
+        let mut link = &self.root;
+        let mut rest = key;
+        while let Some(node) = link.load(Acquire, guard).as_ref() {
+            let Some(remaining) = rest.strip_prefix(&*node.prefix) else {
+                break;
+            };
+            if let Some(val) = node.entry(guard) {
+                longest.replace((&key[..key.len() - remaining.len()], val));
+            }
+            let Some((&byte, tail)) = remaining.split_first() else {
+                break;
+            };
+            let Some(child) = node.children.link(byte) else {
+                break;
+            };
+            link = child;
+            rest = tail;
+        }
+        longest
+    }
+
+    /// Returns `true` if the [`RadixTree`] contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::default();
+    ///
+    /// assert!(!radixtree.contains("a"));
+    /// assert!(radixtree.insert("a", 0).is_ok());
+    /// assert!(radixtree.contains("a"));
+    /// ```
+    #[inline]
+    pub fn contains<Q: AsRef<[u8]> + ?Sized>(&self, key: &Q) -> bool {
+        self.peek(key, &Guard::new()).is_some()
+    }
+
+    /// Clears the [`RadixTree`].
+    ///
+    /// Entries are removed one by one in lexicographic order, therefore concurrent insertions may
+    /// survive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::new();
+    ///
+    /// assert!(radixtree.insert("a", 10).is_ok());
+    /// radixtree.clear();
+    /// assert_eq!(radixtree.len(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        let guard = Guard::new();
+        for (key, _) in self.iter(&guard) {
+            self.remove(&key);
+        }
+    }
+
+    /// Searches for the key without modifying the [`RadixTree`].
+    fn search<'g>(&self, key: &[u8], guard: &'g Guard) -> Option<&'g V> {
+        let mut link = &self.root;
+        let mut rest = key;
+        loop {
+            let node = link.load(Acquire, guard).as_ref()?;
+            rest = rest.strip_prefix(&*node.prefix)?;
+            let Some((&byte, tail)) = rest.split_first() else {
+                return node.entry(guard);
+            };
+            link = node.children.link(byte)?;
+            rest = tail;
+        }
+    }
+}
+
+impl<V: 'static + Clone> Clone for RadixTree<V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let self_clone = Self::default();
+        for (k, v) in self.iter(&Guard::new()) {
+            let _result = self_clone.insert(&k, v.clone());
+        }
+        self_clone
+    }
+}
+
+impl<V: Debug> Debug for RadixTree<V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = Guard::new();
+        f.debug_map().entries(self.iter(&guard)).finish()
+    }
+}
+
+impl<V> Default for RadixTree<V> {
+    /// Creates an empty [`RadixTree`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RadixTree;
+    ///
+    /// let radixtree: RadixTree<u32> = RadixTree::default();
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: PartialEq> PartialEq for RadixTree<V> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // The key order is preserved, therefore comparing iterators suffices.
+        let guard = Guard::new();
+        Iterator::eq(self.iter(&guard), other.iter(&guard))
+    }
+}
+
+impl<V> UnwindSafe for RadixTree<V> {}
+
+impl<V> Node<V> {
+    /// Creates a new [`Node`] having no children.
+    fn leaf(prefix: &[u8], entry: Shared<Entry<V>>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            entry: AtomicShared::from(entry),
+            children: Children::Sparse(Box::default(), Box::default()),
+        }
+    }
+
+    /// Returns a reference to the value whose key ends at the node.
+    fn entry<'g>(&self, guard: &'g Guard) -> Option<&'g V> {
+        self.entry.load(Acquire, guard).as_ref()?.as_ref()
+    }
+
+    /// Returns `true` if the node can be replaced with a smaller copy.
+    ///
+    /// It is the case if the sorted array of children has a vacant slot, the array indexed by the
+    /// next byte has become sparse, or the node has no entry and at most one child.
+    fn is_compactable(&self) -> bool {
+        let links = self.children.links();
+        let num_children = links.iter().filter(|link| !link.is_null(Relaxed)).count();
+        let vacant = match self.children {
+            Children::Sparse(..) => num_children < links.len(),
+            Children::Dense(_) => num_children <= SPARSE_CAPACITY / 2,
+        };
+        vacant || (num_children <= 1 && self.entry.is_null(Relaxed))
+    }
+
+    /// Returns `true` if the node is frozen.
+    fn is_frozen(&self) -> bool {
+        self.entry.tag(Acquire) != Tag::None
+    }
+
+    /// Freezes the node by tagging every link of the node.
+    fn freeze(&self) {
+        self.entry
+            .update_tag_if(Tag::First, |ptr| ptr.tag() == Tag::None, AcqRel, Relaxed);
+        for link in self.children.links() {
+            link.update_tag_if(Tag::First, |ptr| ptr.tag() == Tag::None, AcqRel, Relaxed);
+        }
+    }
+
+    /// Copies the frozen node with a new prefix, adding the supplied child if any.
+    ///
+    /// Returns `None` if the copy would have neither an entry nor children.
+    fn copy(
+        &self,
+        prefix: &[u8],
+        new_child: Option<(u8, Shared<Node<V>>)>,
+        guard: &Guard,
+    ) -> Option<Self> {
+        let entry = self.entry.load(Acquire, guard).without_tag().get_shared();
+        let mut children: Vec<(u8, Shared<Node<V>>)> = self
+            .children
+            .links()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, link)| {
+                let child = link.load(Acquire, guard).without_tag().get_shared()?;
+                Some((self.children.byte(index), child))
+            })
+            .collect();
+        if let Some((byte, child)) = new_child {
+            let pos = children.partition_point(|(b, _)| *b < byte);
+            children.insert(pos, (byte, child));
+        }
+        if entry.is_none() && children.is_empty() {
+            return None;
+        }
+        Some(Self {
+            prefix: prefix.into(),
+            entry: entry.map_or_else(AtomicShared::null, AtomicShared::from),
+            children: Children::new(children),
+        })
+    }
+}
+
+impl<V: 'static> Node<V> {
+    /// Splits the frozen node at the first differing byte, and puts the entry for the key in the
+    /// new node.
+    fn split(&self, common: usize, key: &[u8], entry: Shared<Entry<V>>, guard: &Guard) -> Self {
+        self.freeze();
+        let mut children = Vec::with_capacity(2);
+        if let Some(copy) = self.copy(&self.prefix[common + 1..], None, guard) {
+            children.push((self.prefix[common], Shared::new(copy)));
+        }
+        let new_entry = if let Some((&byte, tail)) = key[common..].split_first() {
+            children.push((byte, Shared::new(Self::leaf(tail, entry))));
+            children.sort_unstable_by_key(|(byte, _)| *byte);
+            AtomicShared::null()
+        } else {
+            AtomicShared::from(entry)
+        };
+        Self {
+            prefix: key[..common].into(),
+            entry: new_entry,
+            children: Children::new(children),
+        }
+    }
+
+    /// Freezes the node, and replaces it with an unfrozen copy.
+    ///
+    /// The node is unlinked instead if it has neither an entry nor children, and it is merged
+    /// with its only child if it has no entry.
+    fn replace(link: &AtomicShared<Node<V>>, node_ptr: Ptr<Node<V>>, guard: &Guard) {
+        let Some(node) = node_ptr.as_ref() else {
+            return;
+        };
+        node.freeze();
+        let new_node = node
+            .copy(&node.prefix, None, guard)
+            .and_then(|copy| copy.merge(guard))
+            .map(Shared::new);
+        let _result =
+            link.compare_exchange(node_ptr, (new_node, Tag::None), AcqRel, Acquire, guard);
+    }
+
+    /// Merges the only child into the copy of a frozen node if the copy has no entry.
+    ///
+    /// Returns `None` if the child turns out to have neither an entry nor children.
+    fn merge(self, guard: &Guard) -> Option<Self> {
+        let links = self.children.links();
+        if !self.entry.is_null(Relaxed) || links.len() != 1 {
+            return Some(self);
+        }
+        let Some(child) = links[0].load(Acquire, guard).as_ref() else {
+            return Some(self);
+        };
+
+        // The child is only reachable through the frozen node and the copy.
+        child.freeze();
+        let mut prefix = Vec::with_capacity(self.prefix.len() + 1 + child.prefix.len());
+        prefix.extend_from_slice(&self.prefix);
+        prefix.push(self.children.byte(0));
+        prefix.extend_from_slice(&child.prefix);
+        child.copy(&prefix, None, guard)
+    }
+
+    /// Takes the entry for the key back out of a node that has never been reachable.
+    fn reclaim(new_node: Option<Shared<Node<V>>>, key: &[u8]) -> Shared<Entry<V>> {
+        // The entry was moved into a newly created node on the path to the key.
+        let mut node = unsafe { new_node.unwrap_unchecked() };
+        let mut rest = &key[node.prefix.len()..];
+        while let Some((&byte, tail)) = rest.split_first() {
+            node = unsafe {
+                node.children
+                    .link(byte)
+                    .and_then(|link| link.swap((None, Tag::None), Relaxed).0)
+                    .unwrap_unchecked()
+            };
+            rest = &tail[node.prefix.len()..];
+        }
+        unsafe {
+            node.entry
+                .swap((None, Tag::None), Relaxed)
+                .0
+                .unwrap_unchecked()
+        }
+    }
+}
+
+impl<V> Children<V> {
+    /// Creates [`Children`] from child nodes sorted by the byte leading to each child.
+    fn new(children: Vec<(u8, Shared<Node<V>>)>) -> Self {
+        if children.len() > SPARSE_CAPACITY {
+            let links: Box<[AtomicShared<Node<V>>]> =
+                (0..=u8::MAX).map(|_| AtomicShared::null()).collect();
+            for (byte, child) in children {
+                links[byte as usize].swap((Some(child), Tag::None), Relaxed);
+            }
+            Self::Dense(links)
+        } else {
+            let (bytes, links): (Vec<u8>, Vec<AtomicShared<Node<V>>>) = children
+                .into_iter()
+                .map(|(byte, child)| (byte, AtomicShared::from(child)))
+                .unzip();
+            Self::Sparse(bytes.into_boxed_slice(), links.into_boxed_slice())
+        }
+    }
+
+    /// Returns the links to the children.
+    fn links(&self) -> &[AtomicShared<Node<V>>] {
+        match self {
+            Self::Sparse(_, links) | Self::Dense(links) => links,
+        }
+    }
+
+    /// Returns the byte leading to the child at the index.
+    #[allow(clippy::cast_possible_truncation)]
+    fn byte(&self, index: usize) -> u8 {
+        match self {
+            Self::Sparse(bytes, _) => bytes[index],
+            Self::Dense(_) => index as u8,
+        }
+    }
+
+    /// Returns the link to the child for the byte.
+    fn link(&self, byte: u8) -> Option<&AtomicShared<Node<V>>> {
+        match self {
+            Self::Sparse(bytes, links) => {
+                bytes.binary_search(&byte).ok().map(|index| &links[index])
+            }
+            Self::Dense(links) => links.get(byte as usize),
+        }
+    }
+}
+
+impl<'g, V> Iter<'g, V> {
+    /// Creates a new [`Iter`] scanning the sub-tree rooted at the node.
+    ///
+    /// `path` is the part of the keys in the sub-tree that precedes the prefix of the node.
+    fn new(root: Ptr<'g, Node<V>>, path: &[u8], guard: &'g Guard) -> Self {
+        let mut key = path.to_vec();
+        let stack = root
+            .as_ref()
+            .map(|node| {
+                key.extend_from_slice(&node.prefix);
+                (node, 0, key.len())
+            })
+            .into_iter()
+            .collect();
+        Self { stack, key, guard }
+    }
+}
+
+impl<V> Debug for Iter<'_, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("finished", &self.stack.is_empty())
+            .finish()
+    }
+}
+
+impl<'g, V> Iterator for Iter<'g, V> {
+    type Item = (Vec<u8>, &'g V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, pos, key_len)) = self.stack.last_mut() {
+            let node = *node;
+            let index = *pos;
+            let key_len = *key_len;
+            *pos += 1;
+            if index == 0 {
+                // The entry of a node precedes those of its children.
+                if let Some(val) = node.entry(self.guard) {
+                    self.key.truncate(key_len);
+                    return Some((self.key.clone(), val));
+                }
+            } else if let Some(link) = node.children.links().get(index - 1) {
+                if let Some(child) = link.load(Acquire, self.guard).as_ref() {
+                    self.key.truncate(key_len);
+                    self.key.push(node.children.byte(index - 1));
+                    self.key.extend_from_slice(&child.prefix);
+                    self.stack.push((child, 0, self.key.len()));
+                }
+            } else {
+                self.stack.pop();
+            }
+        }
+        None
+    }
+}
+
+impl<V> FusedIterator for Iter<'_, V> {}
+
+impl<V> UnwindSafe for Iter<'_, V> {}
+
+/// Returns the length of the common prefix of the two byte strings.
+fn common_prefix_len(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).take_while(|(x, y)| x == y).count()
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    /// Counts the nodes reachable from the link.
+    fn num_nodes<V>(link: &AtomicShared<Node<V>>, guard: &Guard) -> usize {
+        link.load(Acquire, guard).as_ref().map_or(0, |node| {
+            1 + node
+                .children
+                .links()
+                .iter()
+                .map(|link| num_nodes(link, guard))
+                .sum::<usize>()
+        })
+    }
+
+    #[test]
+    fn compaction() {
+        let radixtree: RadixTree<usize> = RadixTree::default();
+        assert!(radixtree.insert("romane", 0).is_ok());
+        assert!(radixtree.insert("romanus", 1).is_ok());
+        let baseline = num_nodes(&radixtree.root, &Guard::new());
+        assert_eq!(baseline, 3);
+
+        for k in [
+            "rom",
+            "romulus",
+            "rubens",
+            "ruber",
+            "rubicon",
+            "rubicundus",
+            "r",
+        ] {
+            assert!(radixtree.insert(k, k.len()).is_ok());
+        }
+        for k in [
+            "rubicundus",
+            "rom",
+            "r",
+            "ruber",
+            "romulus",
+            "rubicon",
+            "rubens",
+        ] {
+            assert!(radixtree.remove(k));
+        }
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), baseline);
+
+        assert!(radixtree.remove("romane"));
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), 1);
+        assert_eq!(radixtree.peek_with("romanus", |_, v| *v), Some(1));
+        assert!(radixtree.remove("romanus"));
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), 0);
+    }
+
+    #[test]
+    fn compaction_dense() {
+        let radixtree: RadixTree<usize> = RadixTree::default();
+        for k in 0..=u8::MAX {
+            assert!(radixtree.insert(&[b'k', k], usize::from(k)).is_ok());
+        }
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), 257);
+        for k in 1..=u8::MAX {
+            assert!(radixtree.remove(&[b'k', k]));
+        }
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), 1);
+        assert_eq!(radixtree.peek_with(&[b'k', 0], |_, v| *v), Some(0));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn compaction_churn() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 256;
+        let radixtree: Arc<RadixTree<usize>> = Arc::new(RadixTree::default());
+        for k in 0..workload_size {
+            assert!(radixtree.insert(&format!("base/{k}"), k).is_ok());
+        }
+        let baseline = num_nodes(&radixtree.root, &Guard::new());
+
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for thread_id in 0..NUM_THREADS {
+            let radixtree_clone = radixtree.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for _ in 0..16 {
+                    for k in 0..workload_size {
+                        let key = format!("base/{k}/{}", k % (thread_id + 1));
+                        let _result = radixtree_clone.insert(&key, k);
+                    }
+                    for k in 0..workload_size {
+                        let key = format!("base/{k}/{}", k % (thread_id + 1));
+                        radixtree_clone.remove(&key);
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(radixtree.len(), workload_size);
+        assert_eq!(num_nodes(&radixtree.root, &Guard::new()), baseline);
+    }
+}
@@ -0,0 +1,705 @@
+//! [`RangeMap`] is a read-optimized concurrent interval map.
+
+use crate::ebr::{AtomicShared, Guard, Shared, Tag};
+use crate::Comparable;
+use std::fmt::{self, Debug};
+use std::iter::FusedIterator;
+use std::ops::Range;
+use std::panic::UnwindSafe;
+use std::slice;
+use std::sync::atomic::Ordering::{AcqRel, Acquire};
+
+/// Scalable concurrent interval map.
+///
+/// [`RangeMap`] maps half-open key ranges to values, and answers stabbing queries, i.e., finds
+/// every range that contains a given key. Ranges may overlap, and each distinct range can be
+/// inserted only once.
+///
+/// ## Notes
+///
+/// [`RangeMap`] keeps its entries in an immutable snapshot sorted by the start of the ranges,
+/// augmented with the maximum end of every sub-tree of an implicit balanced binary tree. A write
+/// operation builds a new snapshot and replaces the old one, therefore each
+/// [`insert`](RangeMap::insert) or [`remove`](RangeMap::remove) copies all the `N` entries and
+/// takes O(N) time, and conflicting write operations are retried; loading `M` ranges one by one
+/// takes O(M * N) time, whereas [`insert_many`](RangeMap::insert_many) loads them in a single
+/// O(N + M * log(M)) write operation. [`RangeMap`] is designed for read-mostly workloads, such as
+/// routing tables.
+///
+/// ## The key features of [`RangeMap`]
+///
+/// * Lock-free-read: read and stabbing operations do not modify shared data and are never blocked.
+/// * Lock-free write: write operations never block.
+/// * Immutability: the data in the container is immutable until it becomes unreachable.
+///
+/// ### Unwind safety
+///
+/// [`RangeMap`] is impervious to out-of-memory errors and panics in user specified code on one
+/// condition; `K::drop` and `V::drop` must not panic.
+pub struct RangeMap<K, V> {
+    snapshot: AtomicShared<Snapshot<K, V>>,
+}
+
+/// An iterator over the entries of a [`RangeMap`].
+///
+/// Entries are sorted by the start and then the end of the ranges.
+pub struct Iter<'g, K, V> {
+    entries: slice::Iter<'g, Shared<Entry<K, V>>>,
+}
+
+/// An iterator over the entries of a [`RangeMap`] of which the range contains a key.
+///
+/// Entries are sorted by the start and then the end of the ranges.
+pub struct Stab<'g, 'k, K, V, Q: ?Sized> {
+    snapshot: Option<&'g Snapshot<K, V>>,
+    key: &'k Q,
+    stack: Vec<(usize, usize)>,
+}
+
+/// A range and its value.
+///
+/// It is only `None` if the entry was built for an insertion attempt and then turned out to be
+/// unnecessary; such an entry is never reachable.
+type Entry<K, V> = Option<(Range<K>, V)>;
+
+/// An immutable set of entries.
+struct Snapshot<K, V> {
+    /// The entries sorted by the start and then the end of the ranges.
+    entries: Vec<Shared<Entry<K, V>>>,
+
+    /// `max_end[mid]` is the index of the entry having the maximum end in `entries[lo..hi]` where
+    /// `mid` is the middle of `lo..hi` of the implicit binary tree.
+    max_end: Vec<usize>,
+}
+
+impl<K, V> RangeMap<K, V> {
+    /// Creates an empty [`RangeMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    /// ```
+    #[cfg(not(feature = "loom"))]
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            snapshot: AtomicShared::null(),
+        }
+    }
+
+    /// Creates an empty [`RangeMap`].
+    #[cfg(feature = "loom")]
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            snapshot: AtomicShared::null(),
+        }
+    }
+
+    /// Clears the [`RangeMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// rangemap.clear();
+    /// assert_eq!(rangemap.len(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        self.snapshot.swap((None, Tag::None), AcqRel);
+    }
+
+    /// Returns the number of entries in the [`RangeMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    /// assert_eq!(rangemap.len(), 0);
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert!(rangemap.insert(2..3, 11).is_ok());
+    /// assert_eq!(rangemap.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        let guard = Guard::new();
+        self.snapshot
+            .load(Acquire, &guard)
+            .as_ref()
+            .map_or(0, |snapshot| snapshot.entries.len())
+    }
+
+    /// Returns `true` if the [`RangeMap`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an [`Iter`].
+    ///
+    /// The returned [`Iter`] scans the entries that existed when the method was invoked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(2..4, 11).is_ok());
+    /// assert!(rangemap.insert(1..8, 10).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = rangemap.iter(&guard);
+    /// assert_eq!(iter.next(), Some((&(1..8), &10)));
+    /// assert_eq!(iter.next(), Some((&(2..4), &11)));
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn iter<'g>(&self, guard: &'g Guard) -> Iter<'g, K, V> {
+        Iter {
+            entries: self
+                .snapshot
+                .load(Acquire, guard)
+                .as_ref()
+                .map_or_else(Default::default, |snapshot| snapshot.entries.iter()),
+        }
+    }
+}
+
+impl<K, V> RangeMap<K, V>
+where
+    K: 'static + Ord,
+    V: 'static,
+{
+    /// Inserts a range and its value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied range and value if the range is empty or it
+    /// exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert!(rangemap.insert(2..8, 11).is_ok());
+    /// assert_eq!(rangemap.insert(1..4, 12).err().unwrap(), (1..4, 12));
+    /// assert_eq!(rangemap.insert(5..5, 13).err().unwrap(), (5..5, 13));
+    /// ```
+    #[inline]
+    pub fn insert(&self, range: Range<K>, val: V) -> Result<(), (Range<K>, V)> {
+        if range.is_empty() {
+            return Err((range, val));
+        }
+        let guard = Guard::new();
+        let mut entry = Shared::new(Some((range, val)));
+        loop {
+            let current = self.snapshot.load(Acquire, &guard);
+            let entries = current
+                .as_ref()
+                .map_or(&[][..], |snapshot| &snapshot.entries[..]);
+            let Err(pos) =
+                entries.binary_search_by(|e| Self::cmp_range(entry_ref(e).0, entry_ref(&entry).0))
+            else {
+                // The entry has never been reachable, and therefore it can be moved out of it.
+                return Err(unsafe { entry.get_mut().and_then(Option::take).unwrap_unchecked() });
+            };
+            let mut new_entries = Vec::with_capacity(entries.len() + 1);
+            new_entries.extend_from_slice(&entries[..pos]);
+            new_entries.push(entry.clone());
+            new_entries.extend_from_slice(&entries[pos..]);
+            match self.snapshot.compare_exchange(
+                current,
+                (Some(Shared::new(Snapshot::new(new_entries))), Tag::None),
+                AcqRel,
+                Acquire,
+                &guard,
+            ) {
+                Ok(_) => return Ok(()),
+                Err((mut passed, _)) => {
+                    // Release the entries of the snapshot that has never been reachable.
+                    if let Some(snapshot) = passed.as_mut().and_then(|s| unsafe { s.get_mut() }) {
+                        snapshot.entries.clear();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Inserts multiple ranges and their values.
+    ///
+    /// The supplied ranges are sorted and merged with the existing entries, and the result is
+    /// published as a single snapshot, therefore it takes O(N + M * log(M)) time where `M` is the
+    /// number of the supplied ranges.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied ranges and values if any of the ranges is empty
+    /// or exists, including the ranges that appear more than once in the supplied ranges; the
+    /// other ranges are inserted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert_many((0..1024).map(|k| (k..k + 2, 0))).is_ok());
+    /// assert_eq!(rangemap.len(), 1024);
+    /// assert_eq!(
+    ///     rangemap.insert_many([(1..3, 1), (2048..2049, 1)]).unwrap_err(),
+    ///     [(1..3, 1)]
+    /// );
+    /// assert!(rangemap.contains(&2048));
+    /// ```
+    #[inline]
+    pub fn insert_many<I: IntoIterator<Item = (Range<K>, V)>>(
+        &self,
+        entries: I,
+    ) -> Result<(), Vec<(Range<K>, V)>> {
+        let mut rejected = Vec::new();
+        let mut new_entries = Vec::new();
+        for (range, val) in entries {
+            if range.is_empty() {
+                rejected.push((range, val));
+            } else {
+                new_entries.push(Shared::new(Some((range, val))));
+            }
+        }
+        new_entries.sort_by(|a, b| Self::cmp_range(entry_ref(a).0, entry_ref(b).0));
+
+        let guard = Guard::new();
+        let mut duplicates = Vec::new();
+        loop {
+            let current = self.snapshot.load(Acquire, &guard);
+            let entries = current
+                .as_ref()
+                .map_or(&[][..], |snapshot| &snapshot.entries[..]);
+            duplicates.clear();
+            let mut merged = Vec::with_capacity(entries.len() + new_entries.len());
+            let mut existing = entries.iter().peekable();
+            for (index, entry) in new_entries.iter().enumerate() {
+                let range = entry_ref(entry).0;
+                while let Some(e) =
+                    existing.next_if(|e| Self::cmp_range(entry_ref(e).0, range).is_lt())
+                {
+                    merged.push(e.clone());
+                }
+                let exists = existing
+                    .peek()
+                    .copied()
+                    .into_iter()
+                    .chain(merged.last())
+                    .any(|e| Self::cmp_range(entry_ref(e).0, range).is_eq());
+                if exists {
+                    duplicates.push(index);
+                } else {
+                    merged.push(entry.clone());
+                }
+            }
+            if duplicates.len() == new_entries.len() {
+                break;
+            }
+            merged.extend(existing.cloned());
+            match self.snapshot.compare_exchange(
+                current,
+                (Some(Shared::new(Snapshot::new(merged))), Tag::None),
+                AcqRel,
+                Acquire,
+                &guard,
+            ) {
+                Ok(_) => break,
+                Err((mut passed, _)) => {
+                    // Release the entries of the snapshot that has never been reachable.
+                    if let Some(snapshot) = passed.as_mut().and_then(|s| unsafe { s.get_mut() }) {
+                        snapshot.entries.clear();
+                    }
+                }
+            }
+        }
+        for index in duplicates {
+            // The entry has never been reachable, and therefore it can be moved out of it.
+            let entry = &mut new_entries[index];
+            rejected.push(unsafe { entry.get_mut().and_then(Option::take).unwrap_unchecked() });
+        }
+        if rejected.is_empty() {
+            Ok(())
+        } else {
+            Err(rejected)
+        }
+    }
+
+    /// Removes a range.
+    ///
+    /// Returns `false` if the range does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(!rangemap.remove(&(1..4)));
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert!(rangemap.remove(&(1..4)));
+    /// ```
+    #[inline]
+    pub fn remove(&self, range: &Range<K>) -> bool {
+        self.remove_if(range, |_| true)
+    }
+
+    /// Removes a range if the given condition is met.
+    ///
+    /// Returns `false` if the range does not exist or the condition was not met.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert!(!rangemap.remove_if(&(1..4), |v| *v == 0));
+    /// assert!(rangemap.remove_if(&(1..4), |v| *v == 10));
+    /// ```
+    #[inline]
+    pub fn remove_if<F: FnMut(&V) -> bool>(&self, range: &Range<K>, mut condition: F) -> bool {
+        let guard = Guard::new();
+        loop {
+            let current = self.snapshot.load(Acquire, &guard);
+            let Some(snapshot) = current.as_ref() else {
+                return false;
+            };
+            let Ok(pos) = snapshot
+                .entries
+                .binary_search_by(|e| Self::cmp_range(entry_ref(e).0, range))
+            else {
+                return false;
+            };
+            if !condition(entry_ref(&snapshot.entries[pos]).1) {
+                return false;
+            }
+            let new_snapshot = if snapshot.entries.len() == 1 {
+                None
+            } else {
+                let mut new_entries = Vec::with_capacity(snapshot.entries.len() - 1);
+                new_entries.extend_from_slice(&snapshot.entries[..pos]);
+                new_entries.extend_from_slice(&snapshot.entries[pos + 1..]);
+                Some(Shared::new(Snapshot::new(new_entries)))
+            };
+            if self
+                .snapshot
+                .compare_exchange(current, (new_snapshot, Tag::None), AcqRel, Acquire, &guard)
+                .is_ok()
+            {
+                return true;
+            }
+        }
+    }
+
+    /// Returns a guarded reference to the value for the specified range.
+    ///
+    /// Returns `None` if the range does not exist. The returned reference can survive as long as
+    /// the associated [`Guard`] is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// let guard = Guard::new();
+    /// assert!(rangemap.peek(&(1..4), &guard).is_none());
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert_eq!(rangemap.peek(&(1..4), &guard), Some(&10));
+    /// assert!(rangemap.peek(&(1..3), &guard).is_none());
+    /// ```
+    #[inline]
+    pub fn peek<'g>(&self, range: &Range<K>, guard: &'g Guard) -> Option<&'g V> {
+        let snapshot = self.snapshot.load(Acquire, guard).as_ref()?;
+        let pos = snapshot
+            .entries
+            .binary_search_by(|e| Self::cmp_range(entry_ref(e).0, range))
+            .ok()?;
+        Some(entry_ref(&snapshot.entries[pos]).1)
+    }
+
+    /// Returns a [`Stab`] that scans the entries of which the range contains the key.
+    ///
+    /// The returned [`Stab`] scans the entries that existed when the method was invoked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(1..8, 10).is_ok());
+    /// assert!(rangemap.insert(2..4, 11).is_ok());
+    /// assert!(rangemap.insert(4..6, 12).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// assert!(rangemap.stab(&3, &guard).map(|(_, v)| *v).eq([10, 11]));
+    /// assert!(rangemap.stab(&4, &guard).map(|(_, v)| *v).eq([10, 12]));
+    /// assert_eq!(rangemap.stab(&8, &guard).count(), 0);
+    /// ```
+    #[inline]
+    pub fn stab<'g, 'k, Q>(&self, key: &'k Q, guard: &'g Guard) -> Stab<'g, 'k, K, V, Q>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let snapshot = self.snapshot.load(Acquire, guard).as_ref();
+        let mut stack = Vec::new();
+        if let Some(snapshot) = snapshot {
+            stack.push((0, snapshot.entries.len()));
+        }
+        Stab {
+            snapshot,
+            key,
+            stack,
+        }
+    }
+
+    /// Returns `true` if any range in the [`RangeMap`] contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::new();
+    ///
+    /// assert!(rangemap.insert(1..4, 10).is_ok());
+    /// assert!(rangemap.contains(&3));
+    /// assert!(!rangemap.contains(&4));
+    /// ```
+    #[inline]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.stab(key, &Guard::new()).next().is_some()
+    }
+
+    /// Compares two ranges by the start and then the end.
+    fn cmp_range(lhs: &Range<K>, rhs: &Range<K>) -> std::cmp::Ordering {
+        lhs.start
+            .cmp(&rhs.start)
+            .then_with(|| lhs.end.cmp(&rhs.end))
+    }
+}
+
+impl<K, V> Clone for RangeMap<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        // Snapshots are immutable, therefore they can be shared.
+        Self {
+            snapshot: self.snapshot.clone(Acquire, &Guard::new()),
+        }
+    }
+}
+
+impl<K, V> Debug for RangeMap<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = Guard::new();
+        f.debug_map().entries(self.iter(&guard)).finish()
+    }
+}
+
+impl<K, V> Default for RangeMap<K, V> {
+    /// Creates an empty [`RangeMap`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RangeMap;
+    ///
+    /// let rangemap: RangeMap<u64, u32> = RangeMap::default();
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> PartialEq for RangeMap<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // Entries are sorted, therefore comparing iterators suffices.
+        let guard = Guard::new();
+        Iterator::eq(self.iter(&guard), other.iter(&guard))
+    }
+}
+
+impl<K, V> UnwindSafe for RangeMap<K, V> {}
+
+impl<K: Ord, V> Snapshot<K, V> {
+    /// Creates a new [`Snapshot`] from sorted entries.
+    fn new(entries: Vec<Shared<Entry<K, V>>>) -> Self {
+        let mut max_end = vec![0; entries.len()];
+        Self::build(&entries, &mut max_end, 0, entries.len());
+        Self { entries, max_end }
+    }
+
+    /// Fills in `max_end` for `entries[lo..hi]`, and returns the index of the entry having the
+    /// maximum end.
+    fn build(
+        entries: &[Shared<Entry<K, V>>],
+        max_end: &mut [usize],
+        lo: usize,
+        hi: usize,
+    ) -> Option<usize> {
+        if lo >= hi {
+            return None;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let mut max = mid;
+        let children = [
+            Self::build(entries, max_end, lo, mid),
+            Self::build(entries, max_end, mid + 1, hi),
+        ];
+        for child in children.into_iter().flatten() {
+            if entry_ref(&entries[child]).0.end > entry_ref(&entries[max]).0.end {
+                max = child;
+            }
+        }
+        max_end[mid] = max;
+        Some(max)
+    }
+}
+
+impl<K, V> Debug for Iter<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("remaining", &self.entries.len())
+            .finish()
+    }
+}
+
+impl<'g, K, V> Iterator for Iter<'g, K, V> {
+    type Item = (&'g Range<K>, &'g V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| entry_ref(entry))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> ExactSizeIterator for Iter<'_, K, V> {}
+
+impl<K, V> FusedIterator for Iter<'_, K, V> {}
+
+impl<K, V> UnwindSafe for Iter<'_, K, V> {}
+
+impl<K, V, Q: ?Sized> Debug for Stab<'_, '_, K, V, Q> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stab").field("stack", &self.stack).finish()
+    }
+}
+
+impl<'g, K, V, Q> Iterator for Stab<'g, '_, K, V, Q>
+where
+    Q: Comparable<K> + ?Sized,
+{
+    type Item = (&'g Range<K>, &'g V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let snapshot = self.snapshot?;
+        while let Some((lo, hi)) = self.stack.pop() {
+            if hi - lo == 1 {
+                let (range, val) = entry_ref(&snapshot.entries[lo]);
+                if self.key.compare(&range.start).is_ge() && self.key.compare(&range.end).is_lt() {
+                    return Some((range, val));
+                }
+                continue;
+            }
+
+            // Skip the sub-tree if no ranges in it end after the key.
+            let mid = lo + (hi - lo) / 2;
+            let max_end = &entry_ref(&snapshot.entries[snapshot.max_end[mid]]).0.end;
+            if self.key.compare(max_end).is_ge() {
+                continue;
+            }
+
+            // Ranges in the right sub-tree start after the key if the middle one does.
+            if mid + 1 < hi
+                && self
+                    .key
+                    .compare(&entry_ref(&snapshot.entries[mid]).0.start)
+                    .is_ge()
+            {
+                self.stack.push((mid + 1, hi));
+            }
+            self.stack.push((mid, mid + 1));
+            if lo < mid {
+                self.stack.push((lo, mid));
+            }
+        }
+        None
+    }
+}
+
+impl<K, V, Q> FusedIterator for Stab<'_, '_, K, V, Q> where Q: Comparable<K> + ?Sized {}
+
+impl<K, V, Q: ?Sized> UnwindSafe for Stab<'_, '_, K, V, Q> {}
+
+/// Returns references to the range and the value of a reachable entry.
+fn entry_ref<K, V>(entry: &Entry<K, V>) -> (&Range<K>, &V) {
+    // Reachable entries always have a range and a value.
+    let (range, val) = unsafe { entry.as_ref().unwrap_unchecked() };
+    (range, val)
+}
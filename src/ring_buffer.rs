@@ -0,0 +1,311 @@
+//! [`RingBuffer`] is a lock-free concurrent fixed-capacity first-in-first-out container.
+
+use std::cell::UnsafeCell;
+use std::fmt::{self, Debug};
+use std::mem::MaybeUninit;
+use std::panic::UnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+
+/// [`RingBuffer`] is a lock-free concurrent fixed-capacity first-in-first-out container.
+///
+/// [`RingBuffer`] stores instances in a pre-allocated array of slots, and therefore it does not
+/// allocate memory when an instance is pushed. Each slot has a sequence number that tells
+/// producers and consumers whether the slot is ready to be written or read.
+pub struct RingBuffer<T> {
+    /// The slots storing instances.
+    slots: Box<[Slot<T>]>,
+
+    /// The position of the next instance to pop.
+    head: AtomicUsize,
+
+    /// The position of the next slot to push an instance into.
+    tail: AtomicUsize,
+}
+
+/// A slot in a [`RingBuffer`].
+struct Slot<T> {
+    /// `seq == pos << 1` denotes that the slot is ready to be written at `pos`, and
+    /// `seq == (pos << 1) | 1` denotes that the slot holds the instance pushed at `pos`.
+    seq: AtomicUsize,
+
+    /// The instance.
+    val: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> RingBuffer<T> {
+    /// Creates an empty [`RingBuffer`] with the specified capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(16);
+    /// assert_eq!(ring_buffer.capacity(), 16);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        assert_ne!(capacity, 0);
+        Self {
+            slots: (0..capacity)
+                .map(|pos| Slot {
+                    seq: AtomicUsize::new(Self::vacant(pos)),
+                    val: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pushes an instance of `T`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the supplied instance if the [`RingBuffer`] is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(1);
+    ///
+    /// assert!(ring_buffer.push(11).is_ok());
+    /// assert_eq!(ring_buffer.push(17), Err(17));
+    /// ```
+    #[inline]
+    pub fn push(&self, val: T) -> Result<(), T> {
+        let mut pos = self.tail.load(Relaxed);
+        loop {
+            let slot = self.slot(pos);
+            let seq = slot.seq.load(Acquire);
+            let expected = Self::vacant(pos);
+            if seq == expected {
+                match self
+                    .tail
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Relaxed, Relaxed)
+                {
+                    Ok(_) => {
+                        unsafe {
+                            (*slot.val.get()).as_mut_ptr().write(val);
+                        }
+                        slot.seq.store(Self::occupied(pos), Release);
+                        return Ok(());
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if Self::precedes(seq, expected) {
+                // The instance pushed one lap before has yet to be popped.
+                return Err(val);
+            } else {
+                pos = self.tail.load(Relaxed);
+            }
+        }
+    }
+
+    /// Pushes an instance of `T`, evicting the oldest instance if the [`RingBuffer`] is full.
+    ///
+    /// Returns the evicted instance. If other threads keep filling the [`RingBuffer`], more than
+    /// one instance may need to be evicted, in which case only the last one is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(2);
+    ///
+    /// assert!(ring_buffer.push_overwrite(1).is_none());
+    /// assert!(ring_buffer.push_overwrite(2).is_none());
+    /// assert_eq!(ring_buffer.push_overwrite(3), Some(1));
+    /// assert_eq!(ring_buffer.pop(), Some(2));
+    /// assert_eq!(ring_buffer.pop(), Some(3));
+    /// ```
+    #[inline]
+    pub fn push_overwrite(&self, mut val: T) -> Option<T> {
+        let mut evicted = None;
+        loop {
+            match self.push(val) {
+                Ok(()) => return evicted,
+                Err(returned) => {
+                    val = returned;
+                    if let Some(oldest) = self.pop() {
+                        evicted.replace(oldest);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Pops the oldest instance.
+    ///
+    /// Returns `None` if the [`RingBuffer`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(4);
+    ///
+    /// assert!(ring_buffer.push(37).is_ok());
+    /// assert!(ring_buffer.push(3).is_ok());
+    ///
+    /// assert_eq!(ring_buffer.pop(), Some(37));
+    /// assert_eq!(ring_buffer.pop(), Some(3));
+    /// assert!(ring_buffer.pop().is_none());
+    /// ```
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        let mut pos = self.head.load(Relaxed);
+        loop {
+            let slot = self.slot(pos);
+            let seq = slot.seq.load(Acquire);
+            let expected = Self::occupied(pos);
+            if seq == expected {
+                match self
+                    .head
+                    .compare_exchange_weak(pos, pos.wrapping_add(1), Relaxed, Relaxed)
+                {
+                    Ok(_) => {
+                        let val = unsafe { (*slot.val.get()).as_ptr().read() };
+                        slot.seq
+                            .store(Self::vacant(pos.wrapping_add(self.slots.len())), Release);
+                        return Some(val);
+                    }
+                    Err(actual) => pos = actual,
+                }
+            } else if Self::precedes(seq, expected) {
+                // No instances have been pushed at the position.
+                return None;
+            } else {
+                pos = self.head.load(Relaxed);
+            }
+        }
+    }
+
+    /// Returns the number of instances in the [`RingBuffer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(4);
+    /// assert_eq!(ring_buffer.len(), 0);
+    ///
+    /// assert!(ring_buffer.push(7).is_ok());
+    /// assert!(ring_buffer.push(11).is_ok());
+    /// assert_eq!(ring_buffer.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        loop {
+            let tail = self.tail.load(Acquire);
+            let head = self.head.load(Acquire);
+            if self.tail.load(Relaxed) == tail {
+                return tail.wrapping_sub(head).min(self.slots.len());
+            }
+        }
+    }
+
+    /// Returns `true` if the [`RingBuffer`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(4);
+    /// assert!(ring_buffer.is_empty());
+    ///
+    /// assert!(ring_buffer.push(7).is_ok());
+    /// assert!(!ring_buffer.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the [`RingBuffer`] is full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(1);
+    /// assert!(!ring_buffer.is_full());
+    ///
+    /// assert!(ring_buffer.push(7).is_ok());
+    /// assert!(ring_buffer.is_full());
+    /// ```
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.slots.len()
+    }
+
+    /// Returns the capacity of the [`RingBuffer`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::RingBuffer;
+    ///
+    /// let ring_buffer: RingBuffer<usize> = RingBuffer::new(7);
+    /// assert_eq!(ring_buffer.capacity(), 7);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns a reference to the slot for the position.
+    fn slot(&self, pos: usize) -> &Slot<T> {
+        &self.slots[pos % self.slots.len()]
+    }
+
+    /// Returns the sequence number of a slot that is ready to be written at the position.
+    const fn vacant(pos: usize) -> usize {
+        pos.wrapping_shl(1)
+    }
+
+    /// Returns the sequence number of a slot holding the instance pushed at the position.
+    const fn occupied(pos: usize) -> usize {
+        pos.wrapping_shl(1) | 1
+    }
+
+    /// Returns `true` if `seq` precedes `expected` taking wrap-around into account.
+    #[allow(clippy::cast_possible_wrap)]
+    const fn precedes(seq: usize, expected: usize) -> bool {
+        (seq.wrapping_sub(expected) as isize) < 0
+    }
+}
+
+impl<T> Debug for RingBuffer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RingBuffer")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> Drop for RingBuffer<T> {
+    #[inline]
+    fn drop(&mut self) {
+        while self.pop().is_some() {}
+    }
+}
+
+unsafe impl<T: Send> Send for RingBuffer<T> {}
+unsafe impl<T: Send> Sync for RingBuffer<T> {}
+impl<T: UnwindSafe> UnwindSafe for RingBuffer<T> {}
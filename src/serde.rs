@@ -1,7 +1,7 @@
 //! This module implements helper types and traits for `serde`.
 
 use super::ebr::Guard;
-use super::{HashCache, HashIndex, HashMap, HashSet, TreeIndex};
+use super::{Bag, HashCache, HashIndex, HashMap, HashSet, Queue, Stack, TreeIndex};
 use serde::de::{Deserialize, MapAccess, SeqAccess, Visitor};
 use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
 use serde::Deserializer;
@@ -420,3 +420,196 @@ where
         map.end()
     }
 }
+
+/// Helper type to allow `serde` to access [`Queue`] entries.
+pub struct QueueVisitor<T: 'static> {
+    marker: PhantomData<fn() -> Queue<T>>,
+}
+
+impl<T: 'static> QueueVisitor<T> {
+    fn new() -> Self {
+        QueueVisitor {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'d, T: 'static + Deserialize<'d>> Visitor<'d> for QueueVisitor<T> {
+    type Value = Queue<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Queue")
+    }
+
+    fn visit_seq<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: SeqAccess<'d>,
+    {
+        let queue = Queue::default();
+        while let Some(val) = access.next_element()? {
+            queue.push(val);
+        }
+        Ok(queue)
+    }
+}
+
+impl<'d, T: 'static + Deserialize<'d>> Deserialize<'d> for Queue<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        deserializer.deserialize_seq(QueueVisitor::<T>::new())
+    }
+}
+
+impl<T: Serialize> Serialize for Queue<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut error = None;
+        self.iter(&Guard::new()).any(|v| {
+            if let Err(e) = seq.serialize_element(v) {
+                error.replace(e);
+                true
+            } else {
+                false
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+        seq.end()
+    }
+}
+
+/// Helper type to allow `serde` to access [`Stack`] entries.
+pub struct StackVisitor<T: 'static> {
+    marker: PhantomData<fn() -> Stack<T>>,
+}
+
+impl<T: 'static> StackVisitor<T> {
+    fn new() -> Self {
+        StackVisitor {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'d, T: 'static + Deserialize<'d>> Visitor<'d> for StackVisitor<T> {
+    type Value = Stack<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Stack")
+    }
+
+    fn visit_seq<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: SeqAccess<'d>,
+    {
+        // Entries are serialized from the newest, therefore they are pushed in reverse order.
+        let mut entries = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some(val) = access.next_element()? {
+            entries.push(val);
+        }
+        let stack = Stack::default();
+        for val in entries.into_iter().rev() {
+            stack.push(val);
+        }
+        Ok(stack)
+    }
+}
+
+impl<'d, T: 'static + Deserialize<'d>> Deserialize<'d> for Stack<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        deserializer.deserialize_seq(StackVisitor::<T>::new())
+    }
+}
+
+impl<T: Serialize> Serialize for Stack<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut error = None;
+        self.iter(&Guard::new()).any(|v| {
+            if let Err(e) = seq.serialize_element(v) {
+                error.replace(e);
+                true
+            } else {
+                false
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+        seq.end()
+    }
+}
+
+/// Helper type to allow `serde` to access [`Bag`] entries.
+pub struct BagVisitor<T, const ARRAY_LEN: usize> {
+    marker: PhantomData<fn() -> Bag<T, ARRAY_LEN>>,
+}
+
+impl<T, const ARRAY_LEN: usize> BagVisitor<T, ARRAY_LEN> {
+    fn new() -> Self {
+        BagVisitor {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'d, T: Deserialize<'d>, const ARRAY_LEN: usize> Visitor<'d> for BagVisitor<T, ARRAY_LEN> {
+    type Value = Bag<T, ARRAY_LEN>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Bag")
+    }
+
+    fn visit_seq<M>(self, mut access: M) -> Result<Self::Value, M::Error>
+    where
+        M: SeqAccess<'d>,
+    {
+        let bag = Bag::new();
+        while let Some(val) = access.next_element()? {
+            bag.push(val);
+        }
+        Ok(bag)
+    }
+}
+
+impl<'d, T: Deserialize<'d>, const ARRAY_LEN: usize> Deserialize<'d> for Bag<T, ARRAY_LEN> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'d>,
+    {
+        deserializer.deserialize_seq(BagVisitor::<T, ARRAY_LEN>::new())
+    }
+}
+
+impl<T: Serialize, const ARRAY_LEN: usize> Serialize for Bag<T, ARRAY_LEN> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut error = None;
+        self.scan(|v| {
+            if error.is_none() {
+                if let Err(e) = seq.serialize_element(v) {
+                    error.replace(e);
+                }
+            }
+        });
+        if let Some(e) = error {
+            return Err(e);
+        }
+        seq.end()
+    }
+}
@@ -0,0 +1,736 @@
+//! [`CountMinSketch`] and [`HyperLogLog`] are concurrent probabilistic data structures estimating
+//! the frequency of items and the number of distinct items.
+
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
+
+/// Scalable concurrent Count-Min sketch.
+///
+/// [`CountMinSketch`] estimates the frequency of items in a stream using a fixed amount of memory.
+/// Each row of counters is indexed by a different hash of the item, and the estimate is the
+/// minimum of the counters for the item, therefore it never underestimates the frequency. With
+/// `width = ⌈e / ε⌉` and `depth = ⌈ln(1 / δ)⌉`, the estimate exceeds the true frequency by at most
+/// `ε * N` with probability `1 - δ` where `N` is the sum of all the counts.
+///
+/// ## The key features of [`CountMinSketch`]
+///
+/// * Lock-free: counters are updated with relaxed atomic operations.
+/// * Mergeable: the counters of a [`CountMinSketch`] can be added to another [`CountMinSketch`]
+///   having the same dimensions and [`BuildHasher`].
+/// * Compact: the counter type is configurable, e.g., [`AtomicU8`] counters saturating at `255`
+///   take an eighth of the memory of the default [`AtomicUsize`] counters.
+/// * Aging: the counters can be halved automatically after a fixed number of increments.
+pub struct CountMinSketch<H = RandomState, C = AtomicUsize>
+where
+    H: BuildHasher,
+    C: Counter,
+{
+    counters: Box<[C]>,
+    width: usize,
+    depth: usize,
+    num_increments: AtomicUsize,
+    sample_size: usize,
+    build_hasher: H,
+}
+
+/// [`Counter`] is an atomic unsigned integer type that can be used for the counters of a
+/// [`CountMinSketch`].
+///
+/// Counters saturate at the maximum value of the type instead of wrapping around.
+pub trait Counter: Send + Sync + Sized {
+    /// Creates a new counter having the specified value.
+    fn with_count(count: usize) -> Self;
+
+    /// Returns the value of the counter.
+    fn count(&self) -> usize;
+
+    /// Adds the specified number to the counter, saturating at the maximum value.
+    fn saturating_add(&self, count: usize);
+
+    /// Halves the counter.
+    fn halve(&self);
+
+    /// Resets the counter.
+    fn reset(&self);
+}
+
+/// Scalable concurrent cardinality estimator.
+///
+/// [`HyperLogLog`] estimates the number of distinct items in a stream using `2^precision` one-byte
+/// registers. The standard error of the estimate is `1.04 / sqrt(2^precision)`, e.g., about 1.6%
+/// when the precision is `12`.
+///
+/// ## The key features of [`HyperLogLog`]
+///
+/// * Lock-free: registers are updated with relaxed atomic operations.
+/// * Mergeable: the registers of a [`HyperLogLog`] can be merged into another [`HyperLogLog`]
+///   having the same precision and [`BuildHasher`].
+pub struct HyperLogLog<H = RandomState>
+where
+    H: BuildHasher,
+{
+    registers: Box<[AtomicU8]>,
+    build_hasher: H,
+}
+
+impl CountMinSketch<RandomState> {
+    /// Creates an empty [`CountMinSketch`] with the specified dimensions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `depth` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    /// assert_eq!(sketch.width(), 1024);
+    /// assert_eq!(sketch.depth(), 4);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(width: usize, depth: usize) -> Self {
+        Self::with_hasher(width, depth, RandomState::new())
+    }
+}
+
+impl<H> CountMinSketch<H>
+where
+    H: BuildHasher,
+{
+    /// Creates an empty [`CountMinSketch`] with the specified dimensions and [`BuildHasher`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `depth` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let sketch: CountMinSketch<RandomState> =
+    ///     CountMinSketch::with_hasher(1024, 4, RandomState::new());
+    /// ```
+    #[inline]
+    pub fn with_hasher(width: usize, depth: usize, build_hasher: H) -> Self {
+        Self::with_counter(width, depth, build_hasher)
+    }
+}
+
+impl<H, C> CountMinSketch<H, C>
+where
+    H: BuildHasher,
+    C: Counter,
+{
+    /// Creates an empty [`CountMinSketch`] with the specified dimensions, [`BuildHasher`], and
+    /// [`Counter`] type.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` or `depth` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    /// use std::collections::hash_map::RandomState;
+    /// use std::sync::atomic::AtomicU8;
+    ///
+    /// let sketch: CountMinSketch<RandomState, AtomicU8> =
+    ///     CountMinSketch::with_counter(1024, 4, RandomState::new());
+    ///
+    /// sketch.add("foo", 1000);
+    /// assert_eq!(sketch.estimate("foo"), 255);
+    /// ```
+    #[inline]
+    pub fn with_counter(width: usize, depth: usize, build_hasher: H) -> Self {
+        assert_ne!(width, 0);
+        assert_ne!(depth, 0);
+        Self {
+            counters: (0..width * depth).map(|_| C::with_count(0)).collect(),
+            width,
+            depth,
+            num_increments: AtomicUsize::new(0),
+            sample_size: 0,
+            build_hasher,
+        }
+    }
+
+    /// Makes the [`CountMinSketch`] halve every counter each time the sum of the counts added
+    /// since the counters were last halved reaches `sample_size`.
+    ///
+    /// The sum is halved along with the counters, so that recent items keep weighing more than
+    /// old ones; a count that makes the sum exceed `sample_size` by far halves the counters as
+    /// many times as the sum needs to fall below `sample_size`. A `sample_size` of `0` disables
+    /// automatic halving.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4).with_halving(16);
+    ///
+    /// sketch.add("foo", 15);
+    /// assert!(sketch.estimate("foo") >= 15);
+    /// sketch.increment("bar");
+    /// assert!(sketch.estimate("foo") >= 7 && sketch.estimate("foo") < 15);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_halving(mut self, sample_size: usize) -> Self {
+        self.sample_size = sample_size;
+        self
+    }
+
+    /// Increments the count of the item by one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    ///
+    /// sketch.increment("foo");
+    /// sketch.increment("foo");
+    /// assert!(sketch.estimate("foo") >= 2);
+    /// ```
+    #[inline]
+    pub fn increment<T: Hash + ?Sized>(&self, item: &T) {
+        self.add(item, 1);
+    }
+
+    /// Adds the specified number to the count of the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    ///
+    /// sketch.add(&7, 10);
+    /// assert!(sketch.estimate(&7) >= 10);
+    /// ```
+    #[inline]
+    pub fn add<T: Hash + ?Sized>(&self, item: &T, count: usize) {
+        self.add_hash(self.hash(item), count);
+    }
+
+    /// Returns the estimated count of the item.
+    ///
+    /// The estimate is never less than the true count unless the counts have been halved.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    ///
+    /// assert_eq!(sketch.estimate("foo"), 0);
+    /// sketch.add("foo", 3);
+    /// assert!(sketch.estimate("foo") >= 3);
+    /// ```
+    #[inline]
+    pub fn estimate<T: Hash + ?Sized>(&self, item: &T) -> usize {
+        self.estimate_hash(self.hash(item))
+    }
+
+    /// Halves every counter.
+    ///
+    /// It is used for aging the counts so that recent items weigh more than old ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    ///
+    /// sketch.add("foo", 8);
+    /// sketch.halve();
+    /// assert!(sketch.estimate("foo") >= 4);
+    /// ```
+    #[inline]
+    pub fn halve(&self) {
+        for counter in self.counters.iter() {
+            counter.halve();
+        }
+    }
+
+    /// Adds the counters of another [`CountMinSketch`] to the [`CountMinSketch`].
+    ///
+    /// The sum of the counts since the counters were last halved is not affected. The two
+    /// instances must use [`BuildHasher`] instances producing the same hash values,
+    /// otherwise the estimates become meaningless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions of the two instances differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let build_hasher = RandomState::new();
+    /// let sketch = CountMinSketch::with_hasher(1024, 4, build_hasher.clone());
+    /// let other = CountMinSketch::with_hasher(1024, 4, build_hasher);
+    ///
+    /// sketch.add("foo", 2);
+    /// other.add("foo", 3);
+    /// sketch.merge(&other);
+    /// assert!(sketch.estimate("foo") >= 5);
+    /// ```
+    #[inline]
+    pub fn merge(&self, other: &Self) {
+        assert_eq!(self.width, other.width);
+        assert_eq!(self.depth, other.depth);
+        for (counter, other_counter) in self.counters.iter().zip(other.counters.iter()) {
+            let count = other_counter.count();
+            if count != 0 {
+                counter.saturating_add(count);
+            }
+        }
+    }
+
+    /// Resets every counter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    ///
+    /// sketch.add("foo", 3);
+    /// sketch.clear();
+    /// assert_eq!(sketch.estimate("foo"), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        for counter in self.counters.iter() {
+            counter.reset();
+        }
+        self.num_increments.store(0, Relaxed);
+    }
+
+    /// Returns the number of counters in a row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    /// assert_eq!(sketch.width(), 1024);
+    /// ```
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Returns the number of rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    /// assert_eq!(sketch.depth(), 4);
+    /// ```
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the number of counts that triggers halving the counters.
+    ///
+    /// Returns `0` if automatic halving is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::CountMinSketch;
+    ///
+    /// let sketch = CountMinSketch::new(1024, 4);
+    /// assert_eq!(sketch.sample_size(), 0);
+    ///
+    /// let sketch = sketch.with_halving(4096);
+    /// assert_eq!(sketch.sample_size(), 4096);
+    /// ```
+    #[inline]
+    pub fn sample_size(&self) -> usize {
+        self.sample_size
+    }
+
+    /// Adds the specified number to the count of the item having the hash value.
+    #[inline]
+    pub(crate) fn add_hash(&self, hash: u64, count: usize) {
+        for row in 0..self.depth {
+            self.counters[self.index(hash, row)].saturating_add(count);
+        }
+        if self.sample_size != 0 {
+            let _: Result<usize, usize> = self
+                .num_increments
+                .fetch_update(Relaxed, Relaxed, |n| Some(n.saturating_add(count)));
+
+            // A large count may have to be halved multiple times to fall below the sample size.
+            while self
+                .num_increments
+                .fetch_update(Relaxed, Relaxed, |n| {
+                    (n >= self.sample_size).then_some(n / 2)
+                })
+                .is_ok()
+            {
+                self.halve();
+            }
+        }
+    }
+
+    /// Returns the estimated count of the item having the hash value.
+    #[inline]
+    pub(crate) fn estimate_hash(&self, hash: u64) -> usize {
+        (0..self.depth)
+            .map(|row| self.counters[self.index(hash, row)].count())
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Hashes the item.
+    fn hash<T: Hash + ?Sized>(&self, item: &T) -> u64 {
+        let mut hasher = self.build_hasher.build_hasher();
+        item.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the index of the counter for the hash value in the row.
+    ///
+    /// The hash value is split into two halves to derive a hash value for each row.
+    #[allow(clippy::cast_possible_truncation)]
+    fn index(&self, hash: u64, row: usize) -> usize {
+        let row_hash =
+            (hash & 0xFFFF_FFFF).wrapping_add((row as u64).wrapping_mul((hash >> 32) | 1));
+        row * self.width + (row_hash % self.width as u64) as usize
+    }
+}
+
+impl<H, C> Clone for CountMinSketch<H, C>
+where
+    H: BuildHasher + Clone,
+    C: Counter,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            counters: self
+                .counters
+                .iter()
+                .map(|counter| C::with_count(counter.count()))
+                .collect(),
+            width: self.width,
+            depth: self.depth,
+            num_increments: AtomicUsize::new(self.num_increments.load(Relaxed)),
+            sample_size: self.sample_size,
+            build_hasher: self.build_hasher.clone(),
+        }
+    }
+}
+
+impl<H, C> Debug for CountMinSketch<H, C>
+where
+    H: BuildHasher,
+    C: Counter,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CountMinSketch")
+            .field("width", &self.width)
+            .field("depth", &self.depth)
+            .field("sample_size", &self.sample_size)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Implements [`Counter`] for atomic unsigned integer types.
+macro_rules! impl_counter {
+    ($atomic:ty, $int:ty) => {
+        impl Counter for $atomic {
+            #[inline]
+            fn with_count(count: usize) -> Self {
+                <$atomic>::new(<$int>::try_from(count).unwrap_or(<$int>::MAX))
+            }
+
+            #[inline]
+            fn count(&self) -> usize {
+                usize::try_from(self.load(Relaxed)).unwrap_or(usize::MAX)
+            }
+
+            #[inline]
+            fn saturating_add(&self, count: usize) {
+                let count = <$int>::try_from(count).unwrap_or(<$int>::MAX);
+                let _result = self.fetch_update(Relaxed, Relaxed, |c| {
+                    (c != <$int>::MAX).then(|| c.saturating_add(count))
+                });
+            }
+
+            #[inline]
+            fn halve(&self) {
+                let _result = self.fetch_update(Relaxed, Relaxed, |c| (c != 0).then_some(c / 2));
+            }
+
+            #[inline]
+            fn reset(&self) {
+                self.store(0, Relaxed);
+            }
+        }
+    };
+}
+
+impl_counter!(AtomicU8, u8);
+impl_counter!(AtomicU16, u16);
+impl_counter!(AtomicU32, u32);
+impl_counter!(AtomicU64, u64);
+impl_counter!(AtomicUsize, usize);
+
+impl HyperLogLog<RandomState> {
+    /// Creates an empty [`HyperLogLog`] with the specified precision.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is not in `4..=16`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    ///
+    /// let hyperloglog = HyperLogLog::new(12);
+    /// assert_eq!(hyperloglog.precision(), 12);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new(precision: u32) -> Self {
+        Self::with_hasher(precision, RandomState::new())
+    }
+}
+
+impl<H> HyperLogLog<H>
+where
+    H: BuildHasher,
+{
+    /// Creates an empty [`HyperLogLog`] with the specified precision and [`BuildHasher`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is not in `4..=16`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let hyperloglog: HyperLogLog<RandomState> = HyperLogLog::with_hasher(12, RandomState::new());
+    /// ```
+    #[inline]
+    pub fn with_hasher(precision: u32, build_hasher: H) -> Self {
+        assert!((4..=16).contains(&precision));
+        Self {
+            registers: (0..1_usize << precision)
+                .map(|_| AtomicU8::new(0))
+                .collect(),
+            build_hasher,
+        }
+    }
+
+    /// Inserts an item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    ///
+    /// let hyperloglog = HyperLogLog::new(12);
+    ///
+    /// hyperloglog.insert("foo");
+    /// hyperloglog.insert("foo");
+    /// assert_eq!(hyperloglog.estimate(), 1);
+    /// ```
+    #[inline]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn insert<T: Hash + ?Sized>(&self, item: &T) {
+        let mut hasher = self.build_hasher.build_hasher();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        // The leading bits choose a register, and the rest are used to count leading zeros.
+        let precision = self.precision();
+        let index = (hash >> (u64::BITS - precision)) as usize;
+        let rank = ((hash << precision) | (1 << (precision - 1))).leading_zeros() + 1;
+        self.registers[index].fetch_max(rank as u8, Relaxed);
+    }
+
+    /// Returns the estimated number of distinct items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    ///
+    /// let hyperloglog = HyperLogLog::new(12);
+    ///
+    /// assert_eq!(hyperloglog.estimate(), 0);
+    /// for i in 0..1000 {
+    ///     hyperloglog.insert(&i);
+    /// }
+    /// let estimate = hyperloglog.estimate();
+    /// assert!(estimate > 900 && estimate < 1100);
+    /// ```
+    #[inline]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn estimate(&self) -> usize {
+        let num_registers = self.registers.len() as f64;
+        let mut sum = 0.0;
+        let mut num_zeros = 0_usize;
+        for register in self.registers.iter() {
+            let rank = register.load(Relaxed);
+            sum += 2.0_f64.powi(-i32::from(rank));
+            if rank == 0 {
+                num_zeros += 1;
+            }
+        }
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1.0 + 1.079 / num_registers),
+        };
+        let mut estimate = alpha * num_registers * num_registers / sum;
+        if estimate <= 2.5 * num_registers && num_zeros != 0 {
+            // Linear counting is more accurate for small cardinalities.
+            estimate = num_registers * (num_registers / num_zeros as f64).ln();
+        }
+        estimate.round() as usize
+    }
+
+    /// Merges the registers of another [`HyperLogLog`] into the [`HyperLogLog`].
+    ///
+    /// The two instances must use [`BuildHasher`] instances producing the same hash values,
+    /// otherwise the estimate becomes meaningless.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the precisions of the two instances differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    /// use std::collections::hash_map::RandomState;
+    ///
+    /// let build_hasher = RandomState::new();
+    /// let hyperloglog = HyperLogLog::with_hasher(12, build_hasher.clone());
+    /// let other = HyperLogLog::with_hasher(12, build_hasher);
+    ///
+    /// for i in 0..1000 {
+    ///     hyperloglog.insert(&i);
+    ///     other.insert(&(i + 500));
+    /// }
+    /// hyperloglog.merge(&other);
+    /// let estimate = hyperloglog.estimate();
+    /// assert!(estimate > 1350 && estimate < 1650);
+    /// ```
+    #[inline]
+    pub fn merge(&self, other: &Self) {
+        assert_eq!(self.registers.len(), other.registers.len());
+        for (register, other_register) in self.registers.iter().zip(other.registers.iter()) {
+            let rank = other_register.load(Relaxed);
+            if rank != 0 {
+                register.fetch_max(rank, Relaxed);
+            }
+        }
+    }
+
+    /// Resets every register.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    ///
+    /// let hyperloglog = HyperLogLog::new(12);
+    ///
+    /// hyperloglog.insert("foo");
+    /// hyperloglog.clear();
+    /// assert_eq!(hyperloglog.estimate(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        for register in self.registers.iter() {
+            register.store(0, Relaxed);
+        }
+    }
+
+    /// Returns the precision.
+    ///
+    /// The number of registers is `2^precision`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::HyperLogLog;
+    ///
+    /// let hyperloglog = HyperLogLog::new(10);
+    /// assert_eq!(hyperloglog.precision(), 10);
+    /// ```
+    #[inline]
+    pub fn precision(&self) -> u32 {
+        self.registers.len().trailing_zeros()
+    }
+}
+
+impl<H> Clone for HyperLogLog<H>
+where
+    H: BuildHasher + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            registers: self
+                .registers
+                .iter()
+                .map(|register| AtomicU8::new(register.load(Relaxed)))
+                .collect(),
+            build_hasher: self.build_hasher.clone(),
+        }
+    }
+}
+
+impl<H> Debug for HyperLogLog<H>
+where
+    H: BuildHasher,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HyperLogLog")
+            .field("precision", &self.precision())
+            .field("estimate", &self.estimate())
+            .finish_non_exhaustive()
+    }
+}
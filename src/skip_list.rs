@@ -0,0 +1,866 @@
+//! [`SkipList`] is a lock-free concurrent ordered map.
+
+use crate::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
+use crate::Comparable;
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::fmt::{self, Debug};
+use std::hash::{BuildHasher, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::ops::RangeBounds;
+use std::panic::UnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
+
+/// Scalable concurrent skip list.
+///
+/// [`SkipList`] is a lock-free ordered map that is optimized for read operations. Unlike
+/// [`TreeIndex`](crate::TreeIndex), inserting an entry only links a new node to its neighbors, and
+/// therefore concurrent insertions of random keys never wait for a node split.
+///
+/// ## Notes
+///
+/// [`SkipList`] methods are linearizable, however its iterator methods are not; [`Iter`] and
+/// [`Range`] are only guaranteed to observe events happened before the first call to
+/// [`Iterator::next`].
+///
+/// ## The key features of [`SkipList`]
+///
+/// * Lock-free-read: read and scan operations do not modify shared data and are never blocked.
+/// * Lock-free write: insert and remove operations never block.
+/// * Immutability: the data in the container is immutable until it becomes unreachable.
+///
+/// ## The key statistics for [`SkipList`]
+///
+/// * The maximum height of a node: 16.
+/// * The probability that a node reaches the next level: 1/4.
+///
+/// ### Unwind safety
+///
+/// [`SkipList`] is impervious to out-of-memory errors and panics in user specified code on one
+/// condition; `K::drop` and `V::drop` must not panic.
+pub struct SkipList<K, V> {
+    /// The links of the head node.
+    head: [AtomicShared<Node<K, V>>; MAX_HEIGHT],
+
+    /// The number of entries in the [`SkipList`].
+    len: AtomicUsize,
+}
+
+/// An iterator over the entries of a [`SkipList`].
+///
+/// An [`Iter`] iterates over all the entries that survive the [`Iter`] in monotonically increasing
+/// order.
+pub struct Iter<'s, 'g, K, V> {
+    head: &'s [AtomicShared<Node<K, V>>],
+    current: Option<&'g Node<K, V>>,
+    started: bool,
+    guard: &'g Guard,
+}
+
+/// An iterator over a sub-range of entries in a [`SkipList`].
+pub struct Range<'s, 'g, K, V, Q: ?Sized, R: RangeBounds<Q>> {
+    head: &'s [AtomicShared<Node<K, V>>],
+    current: Option<&'g Node<K, V>>,
+    started: bool,
+    bounds: R,
+    guard: &'g Guard,
+    query: PhantomData<fn() -> Q>,
+}
+
+/// The maximum height of a node.
+const MAX_HEIGHT: usize = 16;
+
+/// A node of a [`SkipList`].
+///
+/// A link of a node is tagged with [`Tag::First`] when the node is removed.
+struct Node<K, V> {
+    /// The key-value pair.
+    ///
+    /// It is only `None` if the node was built for an insertion attempt and then turned out to be
+    /// unnecessary; such a node is never reachable.
+    entry: Option<(K, V)>,
+
+    /// The links of the node; the length of the slice is the height of the node.
+    next: Box<[AtomicShared<Node<K, V>>]>,
+}
+
+impl<K, V> SkipList<K, V> {
+    /// Creates an empty [`SkipList`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            head: Default::default(),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of entries in the [`SkipList`].
+    ///
+    /// It reads an internal counter, and therefore the time complexity is O(1).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    /// assert_eq!(skiplist.len(), 0);
+    ///
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// assert_eq!(skiplist.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Relaxed)
+    }
+
+    /// Returns `true` if the [`SkipList`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an [`Iter`].
+    ///
+    /// The returned [`Iter`] starts scanning from the minimum key-value pair. Key-value pairs
+    /// are scanned in ascending order, and key-value pairs that have existed since the invocation
+    /// of the method are guaranteed to be visited if they are not removed. However, it is possible
+    /// to visit removed key-value pairs momentarily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    /// use scc::ebr::Guard;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.insert(2, 20).is_ok());
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    ///
+    /// let guard = Guard::new();
+    /// let mut iter = skiplist.iter(&guard);
+    /// assert_eq!(iter.next(), Some((&1, &10)));
+    /// assert_eq!(iter.next(), Some((&2, &20)));
+    /// assert!(iter.next().is_none());
+    /// ```
+    #[inline]
+    pub fn iter<'s, 'g>(&'s self, guard: &'g Guard) -> Iter<'s, 'g, K, V> {
+        Iter {
+            head: &self.head,
+            current: None,
+            started: false,
+            guard,
+        }
+    }
+
+    /// Returns a [`Range`] that scans keys in the given range.
+    ///
+    /// Key-value pairs in the range are scanned in ascending order, and key-value pairs that have
+    /// existed since the invocation of the method are guaranteed to be visited if they are not
+    /// removed. However, it is possible to visit removed key-value pairs momentarily.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    /// use scc::ebr::Guard;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// for i in 0..10 {
+    ///     assert!(skiplist.insert(i, 10).is_ok());
+    /// }
+    ///
+    /// let guard = Guard::new();
+    /// assert_eq!(skiplist.range(1..1, &guard).count(), 0);
+    /// assert_eq!(skiplist.range(4..8, &guard).count(), 4);
+    /// assert_eq!(skiplist.range(4..=8, &guard).count(), 5);
+    /// ```
+    #[inline]
+    pub fn range<'s, 'g, Q, R: RangeBounds<Q>>(
+        &'s self,
+        range: R,
+        guard: &'g Guard,
+    ) -> Range<'s, 'g, K, V, Q, R>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        Range {
+            head: &self.head,
+            current: None,
+            started: false,
+            bounds: range,
+            guard,
+            query: PhantomData,
+        }
+    }
+}
+
+impl<K, V> SkipList<K, V>
+where
+    K: 'static + Ord,
+    V: 'static,
+{
+    /// Inserts a key-value pair.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error along with the supplied key-value pair if the key exists.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// assert_eq!(skiplist.insert(1, 11).err().unwrap(), (1, 11));
+    /// assert_eq!(skiplist.peek_with(&1, |k, v| *v).unwrap(), 10);
+    /// ```
+    #[inline]
+    pub fn insert(&self, key: K, val: V) -> Result<(), (K, V)> {
+        let guard = Guard::new();
+        let mut preds = [&self.head[..]; MAX_HEIGHT];
+        let mut succs = [Ptr::null(); MAX_HEIGHT];
+        if self.find(&key, &mut preds, &mut succs, &guard) {
+            return Err((key, val));
+        }
+
+        self.len.fetch_add(1, Relaxed);
+        let mut new_node = Shared::new(Node {
+            entry: Some((key, val)),
+            next: (0..Self::random_height())
+                .map(|_| AtomicShared::null())
+                .collect(),
+        });
+        let height = new_node.next.len();
+        let node_ptr = new_node.get_guarded_ptr(&guard);
+        loop {
+            for (link, succ) in new_node.next.iter().zip(succs.iter()) {
+                link.swap((succ.get_shared(), Tag::None), Relaxed);
+            }
+            match preds[0][0].compare_exchange(
+                succs[0],
+                (Some(new_node), Tag::None),
+                AcqRel,
+                Acquire,
+                &guard,
+            ) {
+                Ok(_) => break,
+                Err((passed, _)) => {
+                    new_node = unsafe { passed.unwrap_unchecked() };
+                }
+            }
+            if self.find(new_node.key(), &mut preds, &mut succs, &guard) {
+                // The node has never been reachable, and therefore the key-value pair can be
+                // moved out of it.
+                self.len.fetch_sub(1, Relaxed);
+                return Err(unsafe {
+                    new_node
+                        .get_mut()
+                        .and_then(|node| node.entry.take())
+                        .unwrap_unchecked()
+                });
+            }
+        }
+
+        // Link the node to upper levels.
+        let Some(node_ref) = node_ptr.as_ref() else {
+            return Ok(());
+        };
+        for level in 1..height {
+            loop {
+                let current = node_ref.next[level].load(Acquire, &guard);
+                if current.tag() != Tag::None {
+                    // The node is being removed.
+                    return Ok(());
+                }
+                if current != succs[level]
+                    && node_ref.next[level]
+                        .compare_exchange(
+                            current,
+                            (succs[level].get_shared(), Tag::None),
+                            AcqRel,
+                            Acquire,
+                            &guard,
+                        )
+                        .is_err()
+                {
+                    continue;
+                }
+                if preds[level][level]
+                    .compare_exchange(
+                        succs[level],
+                        (node_ptr.get_shared(), Tag::None),
+                        AcqRel,
+                        Acquire,
+                        &guard,
+                    )
+                    .is_ok()
+                {
+                    break;
+                }
+                if !self.find(node_ref.key(), &mut preds, &mut succs, &guard)
+                    || succs[0] != node_ptr
+                {
+                    // The node has been removed.
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a key-value pair.
+    ///
+    /// Returns `false` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(!skiplist.remove(&1));
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// assert!(skiplist.remove(&1));
+    /// ```
+    #[inline]
+    pub fn remove<Q>(&self, key: &Q) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.remove_if(key, |_| true)
+    }
+
+    /// Removes a key-value pair if the given condition is met.
+    ///
+    /// Returns `false` if the key does not exist or the condition was not met.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// assert!(!skiplist.remove_if(&1, |v| *v == 0));
+    /// assert!(skiplist.remove_if(&1, |v| *v == 10));
+    /// ```
+    #[inline]
+    pub fn remove_if<Q, F: FnMut(&V) -> bool>(&self, key: &Q, mut condition: F) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let guard = Guard::new();
+        let mut preds = [&self.head[..]; MAX_HEIGHT];
+        let mut succs = [Ptr::null(); MAX_HEIGHT];
+        if !self.find(key, &mut preds, &mut succs, &guard) {
+            return false;
+        }
+        let Some(node_ref) = succs[0].as_ref() else {
+            return false;
+        };
+        if !condition(node_ref.val()) {
+            return false;
+        }
+        if !self.mark(node_ref) {
+            // Another thread has removed the node.
+            return false;
+        }
+
+        // Unlink the node from every level.
+        self.find(key, &mut preds, &mut succs, &guard);
+        true
+    }
+
+    /// Returns a guarded reference to the value for the specified key without acquiring locks.
+    ///
+    /// Returns `None` if the key does not exist. The returned reference can survive as long as the
+    /// associated [`Guard`] is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::SkipList;
+    /// use std::sync::Arc;
+    ///
+    /// let skiplist: SkipList<Arc<str>, u32> = SkipList::new();
+    ///
+    /// let guard = Guard::new();
+    /// assert!(skiplist.peek("foo", &guard).is_none());
+    ///
+    /// skiplist.insert("foo".into(), 1).expect("insert in empty SkipList");
+    /// assert_eq!(skiplist.peek("foo", &guard), Some(&1));
+    /// ```
+    #[inline]
+    pub fn peek<'g, Q>(&self, key: &Q, guard: &'g Guard) -> Option<&'g V>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.search(key, guard).map(Node::val)
+    }
+
+    /// Peeks a key-value pair without acquiring locks.
+    ///
+    /// Returns `None` if the key does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    /// use std::sync::Arc;
+    ///
+    /// let skiplist: SkipList<Arc<str>, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.peek_with("foo", |k, v| *v).is_none());
+    ///
+    /// skiplist.insert("foo".into(), 1).expect("insert in empty SkipList");
+    ///
+    /// let key: Arc<str> = skiplist
+    ///     .peek_with("foo", |k, _v| Arc::clone(k))
+    ///     .expect("peek_with by borrowed key");
+    /// ```
+    #[inline]
+    pub fn peek_with<Q, R, F: FnOnce(&K, &V) -> R>(&self, key: &Q, reader: F) -> Option<R>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let guard = Guard::new();
+        self.peek_entry(key, &guard).map(|(k, v)| reader(k, v))
+    }
+
+    /// Returns a guarded reference to the key-value pair for the specified key without acquiring locks.
+    ///
+    /// Returns `None` if the key does not exist. The returned reference can survive as long as the
+    /// associated [`Guard`] is alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::ebr::Guard;
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// let guard = Guard::new();
+    /// assert!(skiplist.peek_entry(&1, &guard).is_none());
+    ///
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// assert_eq!(skiplist.peek_entry(&1, &guard), Some((&1, &10)));
+    /// ```
+    #[inline]
+    pub fn peek_entry<'g, Q>(&self, key: &Q, guard: &'g Guard) -> Option<(&'g K, &'g V)>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.search(key, guard).map(Node::entry)
+    }
+
+    /// Returns `true` if the [`SkipList`] contains the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::default();
+    ///
+    /// assert!(!skiplist.contains(&1));
+    /// assert!(skiplist.insert(1, 0).is_ok());
+    /// assert!(skiplist.contains(&1));
+    /// ```
+    #[inline]
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        self.peek(key, &Guard::new()).is_some()
+    }
+
+    /// Clears the [`SkipList`].
+    ///
+    /// Entries are removed one by one in ascending order, therefore concurrent insertions may
+    /// survive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::new();
+    ///
+    /// assert!(skiplist.insert(1, 10).is_ok());
+    /// skiplist.clear();
+    /// assert_eq!(skiplist.len(), 0);
+    /// ```
+    #[inline]
+    pub fn clear(&self) {
+        let guard = Guard::new();
+        let mut preds = [&self.head[..]; MAX_HEIGHT];
+        let mut succs = [Ptr::null(); MAX_HEIGHT];
+        while let Some(node_ref) = self.head[0].load(Acquire, &guard).as_ref() {
+            self.mark(node_ref);
+            self.find(node_ref.key(), &mut preds, &mut succs, &guard);
+        }
+    }
+
+    /// Searches for the key, and records the predecessor and successor links at every level.
+    ///
+    /// Removed nodes found on the way are unlinked. Returns `true` if a node having the key is
+    /// found.
+    fn find<'g, Q>(
+        &'g self,
+        key: &Q,
+        preds: &mut [&'g [AtomicShared<Node<K, V>>]; MAX_HEIGHT],
+        succs: &mut [Ptr<'g, Node<K, V>>; MAX_HEIGHT],
+        guard: &'g Guard,
+    ) -> bool
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        'retry: loop {
+            let mut pred: &[AtomicShared<Node<K, V>>] = &self.head;
+            for level in (0..MAX_HEIGHT).rev() {
+                let mut curr = pred[level].load(Acquire, guard).without_tag();
+                while let Some(curr_ref) = curr.as_ref() {
+                    let succ = curr_ref.next[level].load(Acquire, guard);
+                    if succ.tag() != Tag::None {
+                        // `curr` has been removed, therefore unlink it.
+                        let succ = succ.without_tag();
+                        let succ_shared = succ.get_shared();
+                        if succ_shared.is_none() && !succ.is_null() {
+                            continue 'retry;
+                        }
+                        if pred[level]
+                            .compare_exchange(
+                                curr,
+                                (succ_shared, Tag::None),
+                                AcqRel,
+                                Acquire,
+                                guard,
+                            )
+                            .is_err()
+                        {
+                            continue 'retry;
+                        }
+                        curr = succ;
+                    } else if key.compare(curr_ref.key()).is_gt() {
+                        pred = &curr_ref.next;
+                        curr = succ;
+                    } else {
+                        break;
+                    }
+                }
+                preds[level] = pred;
+                succs[level] = curr;
+            }
+            return succs[0]
+                .as_ref()
+                .map_or(false, |node_ref| key.compare(node_ref.key()).is_eq());
+        }
+    }
+
+    /// Searches for the key without modifying the [`SkipList`].
+    fn search<'g, Q>(&self, key: &Q, guard: &'g Guard) -> Option<&'g Node<K, V>>
+    where
+        Q: Comparable<K> + ?Sized,
+    {
+        let mut pred: &[AtomicShared<Node<K, V>>] = &self.head;
+        for level in (0..MAX_HEIGHT).rev() {
+            let mut curr = pred[level].load(Acquire, guard);
+            while let Some(curr_ref) = curr.as_ref() {
+                let succ = curr_ref.next[level].load(Acquire, guard);
+                if succ.tag() != Tag::None {
+                    // Skip removed nodes.
+                    curr = succ;
+                    continue;
+                }
+                match key.compare(curr_ref.key()) {
+                    Ordering::Greater => {
+                        pred = &curr_ref.next;
+                        curr = succ;
+                    }
+                    Ordering::Equal => return Some(curr_ref),
+                    Ordering::Less => break,
+                }
+            }
+        }
+        None
+    }
+
+    /// Marks every link of the node from the top.
+    ///
+    /// Returns `true` if the node was removed by the current thread.
+    fn mark(&self, node_ref: &Node<K, V>) -> bool {
+        for link in node_ref.next.iter().skip(1).rev() {
+            link.update_tag_if(Tag::First, |ptr| ptr.tag() == Tag::None, AcqRel, Relaxed);
+        }
+        if node_ref.next[0].update_tag_if(Tag::First, |ptr| ptr.tag() == Tag::None, AcqRel, Relaxed)
+        {
+            self.len.fetch_sub(1, Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// Returns a random node height.
+    fn random_height() -> usize {
+        thread_local! {
+            static STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+        }
+        let random = STATE.with(|state| {
+            // `xorshift64`.
+            let mut x = state.get();
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            state.set(x);
+            x
+        });
+
+        // Every two bits decide whether the node reaches the next level.
+        (random.trailing_zeros() as usize / 2 + 1).min(MAX_HEIGHT)
+    }
+}
+
+impl<K, V> Clone for SkipList<K, V>
+where
+    K: 'static + Clone + Ord,
+    V: 'static + Clone,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        let self_clone = Self::default();
+        for (k, v) in self.iter(&Guard::new()) {
+            let _result = self_clone.insert(k.clone(), v.clone());
+        }
+        self_clone
+    }
+}
+
+impl<K, V> Debug for SkipList<K, V>
+where
+    K: Debug,
+    V: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let guard = Guard::new();
+        f.debug_map().entries(self.iter(&guard)).finish()
+    }
+}
+
+impl<K, V> Default for SkipList<K, V> {
+    /// Creates an empty [`SkipList`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::SkipList;
+    ///
+    /// let skiplist: SkipList<u64, u32> = SkipList::default();
+    /// ```
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> PartialEq for SkipList<K, V>
+where
+    K: PartialEq,
+    V: PartialEq,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        // The key order is preserved, therefore comparing iterators suffices.
+        let guard = Guard::new();
+        Iterator::eq(self.iter(&guard), other.iter(&guard))
+    }
+}
+
+impl<K, V> UnwindSafe for SkipList<K, V> {}
+
+impl<K, V> Node<K, V> {
+    /// Returns a reference to the key.
+    fn key(&self) -> &K {
+        self.entry().0
+    }
+
+    /// Returns a reference to the value.
+    fn val(&self) -> &V {
+        self.entry().1
+    }
+
+    /// Returns references to the key-value pair.
+    fn entry(&self) -> (&K, &V) {
+        // Reachable nodes always have an entry.
+        let (key, val) = unsafe { self.entry.as_ref().unwrap_unchecked() };
+        (key, val)
+    }
+
+    /// Returns the first node that is not removed starting from the link at level `0`.
+    fn next_valid<'g>(link: &AtomicShared<Node<K, V>>, guard: &'g Guard) -> Option<&'g Self> {
+        let mut next = link.load(Acquire, guard);
+        while let Some(next_ref) = next.as_ref() {
+            let succ = next_ref.next[0].load(Acquire, guard);
+            if succ.tag() == Tag::None {
+                return Some(next_ref);
+            }
+            next = succ;
+        }
+        None
+    }
+}
+
+impl<K, V> Debug for Iter<'_, '_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Iter")
+            .field("started", &self.started)
+            .field("finished", &(self.started && self.current.is_none()))
+            .finish()
+    }
+}
+
+impl<'g, K, V> Iterator for Iter<'_, 'g, K, V> {
+    type Item = (&'g K, &'g V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let link = if self.started {
+            &self.current?.next[0]
+        } else {
+            self.started = true;
+            &self.head[0]
+        };
+        self.current = Node::next_valid(link, self.guard);
+        self.current.map(Node::entry)
+    }
+}
+
+impl<K, V> FusedIterator for Iter<'_, '_, K, V> {}
+
+impl<K, V> UnwindSafe for Iter<'_, '_, K, V> {}
+
+impl<'g, K, V, Q, R> Range<'_, 'g, K, V, Q, R>
+where
+    Q: Comparable<K> + ?Sized,
+    R: RangeBounds<Q>,
+{
+    /// Returns the first node that is not smaller than the start bound.
+    fn first(&self) -> Option<&'g Node<K, V>> {
+        let mut pred = self.head;
+        for level in (1..MAX_HEIGHT).rev() {
+            let mut curr = pred[level].load(Acquire, self.guard);
+            while let Some(curr_ref) = curr.as_ref() {
+                let succ = curr_ref.next[level].load(Acquire, self.guard);
+                if succ.tag() != Tag::None {
+                    // Skip removed nodes.
+                    curr = succ;
+                    continue;
+                }
+                if !self.precedes(curr_ref.key()) {
+                    break;
+                }
+                pred = &curr_ref.next;
+                curr = succ;
+            }
+        }
+
+        let mut current = Node::next_valid(&pred[0], self.guard);
+        while let Some(current_ref) = current {
+            if !self.precedes(current_ref.key()) {
+                break;
+            }
+            current = Node::next_valid(&current_ref.next[0], self.guard);
+        }
+        current
+    }
+
+    /// Returns `true` if the key precedes the start bound.
+    fn precedes(&self, key: &K) -> bool {
+        match self.bounds.start_bound() {
+            Included(start) => start.compare(key).is_gt(),
+            Excluded(start) => start.compare(key).is_ge(),
+            Unbounded => false,
+        }
+    }
+}
+
+impl<K, V, Q: ?Sized, R: RangeBounds<Q>> Debug for Range<'_, '_, K, V, Q, R> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Range")
+            .field("started", &self.started)
+            .field("finished", &(self.started && self.current.is_none()))
+            .finish()
+    }
+}
+
+impl<'g, K, V, Q, R> Iterator for Range<'_, 'g, K, V, Q, R>
+where
+    Q: Comparable<K> + ?Sized,
+    R: RangeBounds<Q>,
+{
+    type Item = (&'g K, &'g V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = if self.started {
+            Node::next_valid(&self.current?.next[0], self.guard)
+        } else {
+            self.started = true;
+            self.first()
+        };
+        self.current = next.filter(|next_ref| match self.bounds.end_bound() {
+            Included(key) => key.compare(next_ref.key()).is_ge(),
+            Excluded(key) => key.compare(next_ref.key()).is_gt(),
+            Unbounded => true,
+        });
+        self.current.map(Node::entry)
+    }
+}
+
+impl<K, V, Q, R> FusedIterator for Range<'_, '_, K, V, Q, R>
+where
+    Q: Comparable<K> + ?Sized,
+    R: RangeBounds<Q>,
+{
+}
+
+impl<K, V, Q, R> UnwindSafe for Range<'_, '_, K, V, Q, R>
+where
+    Q: ?Sized,
+    R: RangeBounds<Q> + UnwindSafe,
+{
+}
@@ -2,14 +2,23 @@
 
 use super::ebr::{AtomicShared, Guard, Ptr, Shared, Tag};
 use super::linked_list::{Entry, LinkedList};
+use super::wait_queue::{AsyncWait, DeriveAsyncWait, WaitQueue};
 use std::fmt::{self, Debug};
 use std::iter::FusedIterator;
+use std::pin::Pin;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering::{AcqRel, Acquire, Relaxed};
 
 /// [`Stack`] is a lock-free concurrent last-in-first-out container.
 pub struct Stack<T> {
     /// `newest` points to the newest entry in the [`Stack`].
     newest: AtomicShared<Entry<T>>,
+
+    /// The number of entries including those being pushed.
+    len: AtomicUsize,
+
+    /// Consumers waiting for an entry to be pushed.
+    waiting_consumers: WaitQueue,
 }
 
 /// An iterator over the entries of a [`Stack`].
@@ -20,6 +29,13 @@ pub struct Iter<'g, T> {
     guard: &'g Guard,
 }
 
+/// An owning iterator over the entries of a [`Stack`].
+///
+/// [`IntoIter`] pops the newest entry first.
+pub struct IntoIter<T> {
+    stack: Stack<T>,
+}
+
 impl<T: 'static> Stack<T> {
     /// Pushes an instance of `T`.
     ///
@@ -187,8 +203,49 @@ impl<T> Stack<T> {
         }
     }
 
-    /// Pops all the entries at once, and passes each one of the popped entries to the supplied
-    /// closure.
+    /// Pops the newest entry, waiting for an entry to be pushed if the [`Stack`] is empty.
+    ///
+    /// It is an asynchronous method returning an `impl Future` for the caller to await.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::Stack;
+    ///
+    /// let stack: Stack<usize> = Stack::default();
+    ///
+    /// let future_pop = stack.pop_async();
+    /// ```
+    #[inline]
+    pub async fn pop_async(&self) -> Shared<Entry<T>> {
+        loop {
+            if let Some(popped) = self.pop() {
+                return popped;
+            }
+            let mut popped = None;
+            let mut async_wait = AsyncWait::default();
+            let mut async_wait_pinned = Pin::new(&mut async_wait);
+            if let Some(async_wait) = async_wait_pinned.derive() {
+                let _result = self.waiting_consumers.push_async_entry(async_wait, || {
+                    popped = self.pop();
+                    if popped.is_some() {
+                        Ok(())
+                    } else {
+                        Err(())
+                    }
+                });
+            }
+            if let Some(popped) = popped {
+                return popped;
+            }
+            async_wait_pinned.await;
+        }
+    }
+
+    /// Pops all the entries at once.
+    ///
+    /// The entries are detached from the [`Stack`] by a single atomic operation, and returned as
+    /// a new [`Stack`] that can be turned into an iterator yielding the newest entry first.
     ///
     /// # Examples
     ///
@@ -211,14 +268,36 @@ impl<T> Stack<T> {
     /// assert_eq!(popped.pop().map(|e| **e), Some(3));
     /// assert_eq!(popped.pop().map(|e| **e), Some(37));
     /// assert!(popped.pop().is_none());
+    ///
+    /// stack.push(11);
+    /// stack.push(17);
+    ///
+    /// let popped: Vec<usize> = stack.pop_all().into_iter().map(|e| **e).collect();
+    /// assert_eq!(popped, [17, 11]);
     /// ```
-
     #[inline]
     #[must_use]
     pub fn pop_all(&self) -> Self {
         let head = self.newest.swap((None, Tag::None), AcqRel).0;
+
+        // Count the detached entries instead of taking over the counter, since the counter may
+        // include entries concurrently pushed onto the emptied stack.
+        let guard = Guard::new();
+        let mut len = 0;
+        let mut current = head
+            .as_ref()
+            .map_or_else(Ptr::null, |head| head.get_guarded_ptr(&guard));
+        while let Some(entry) = current.as_ref() {
+            if !entry.is_deleted(Relaxed) {
+                len += 1;
+            }
+            current = entry.next_ptr(Acquire, &guard);
+        }
+        self.sub_len(len);
         Self {
             newest: head.map_or_else(AtomicShared::default, AtomicShared::from),
+            len: AtomicUsize::new(len),
+            waiting_consumers: WaitQueue::default(),
         }
     }
 
@@ -259,6 +338,7 @@ impl<T> Stack<T> {
                     return Err(newest_entry);
                 }
                 if newest_entry.delete_self(Relaxed) {
+                    self.sub_len(1);
                     self.cleanup_newest(newest_ptr, &guard);
                     return Ok(Some(newest_entry));
                 }
@@ -295,8 +375,11 @@ impl<T> Stack<T> {
 
     /// Returns the number of entries in the [`Stack`].
     ///
-    /// This method iterates over all the entries in the [`Stack`] to count them, therefore its
-    /// time complexity is `O(N)`.
+    /// The number is read from a counter, therefore its time complexity is `O(1)`; entries being
+    /// pushed by other threads are counted before they become visible, and
+    /// [`pop_all`](Self::pop_all) moves the number of detached entries to the returned [`Stack`],
+    /// therefore an entry popped concurrently with [`pop_all`](Self::pop_all) may be counted in
+    /// the wrong [`Stack`]. The number never exceeds the number of entries ever pushed.
     ///
     /// # Examples
     ///
@@ -316,7 +399,11 @@ impl<T> Stack<T> {
     /// ```
     #[inline]
     pub fn len(&self) -> usize {
-        self.iter(&Guard::new()).count()
+        if self.newest.is_null(Relaxed) {
+            // Entries counted in the wrong stack must not outlive the entries.
+            return 0;
+        }
+        self.len.load(Relaxed)
     }
 
     /// Returns `true` if the [`Stack`] is empty.
@@ -382,6 +469,8 @@ impl<T> Stack<T> {
             return Err(val);
         }
 
+        // Count the entry in advance so that popping it never underflows the counter.
+        self.len.fetch_add(1, Relaxed);
         let mut new_entry = unsafe { Shared::new_unchecked(Entry::new(val)) };
         loop {
             new_entry
@@ -395,7 +484,10 @@ impl<T> Stack<T> {
                 guard,
             );
             match result {
-                Ok(_) => return Ok(new_entry),
+                Ok(_) => {
+                    self.waiting_consumers.signal_if_waiting();
+                    return Ok(new_entry);
+                }
                 Err((_, actual_ptr)) => {
                     newest_ptr = self.cleanup_newest(actual_ptr, guard);
                     if !cond(newest_ptr.as_ref()) {
@@ -407,9 +499,20 @@ impl<T> Stack<T> {
         }
 
         // Extract the instance from the temporary entry.
+        self.sub_len(1);
         Err(unsafe { new_entry.get_mut().unwrap_unchecked().take_inner() })
     }
 
+    /// Subtracts the number of popped entries from the counter.
+    ///
+    /// An entry popped by a thread that loaded `newest` before [`pop_all`](Self::pop_all) detached
+    /// it may be subtracted twice, therefore the counter saturates at zero.
+    fn sub_len(&self, num: usize) {
+        let _: Result<usize, usize> = self
+            .len
+            .fetch_update(Relaxed, Relaxed, |len| Some(len.saturating_sub(num)));
+    }
+
     /// Cleans up logically removed entries that are attached to `newest`.
     fn cleanup_newest<'g>(
         &self,
@@ -454,6 +557,7 @@ impl<T: Clone> Clone for Stack<T> {
                     .swap((Some(new_entry.clone()), Tag::None), Acquire);
             }
             oldest.replace(new_entry);
+            self_clone.len.fetch_add(1, Relaxed);
             current = entry.next_ptr(Acquire, &guard);
         }
         self_clone
@@ -480,10 +584,30 @@ impl<T> Default for Stack<T> {
     fn default() -> Self {
         Self {
             newest: AtomicShared::default(),
+            len: AtomicUsize::new(0),
+            waiting_consumers: WaitQueue::default(),
         }
     }
 }
 
+impl<T: 'static> Extend<T> for Stack<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|val| {
+            self.push(val);
+        });
+    }
+}
+
+impl<T: 'static> FromIterator<T> for Stack<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stack = Self::default();
+        stack.extend(iter);
+        stack
+    }
+}
+
 impl<T> Drop for Stack<T> {
     #[inline]
     fn drop(&mut self) {
@@ -498,6 +622,27 @@ impl<T> Drop for Stack<T> {
     }
 }
 
+impl<T> IntoIterator for Stack<T> {
+    type Item = Shared<Entry<T>>;
+    type IntoIter = IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { stack: self }
+    }
+}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = Shared<Entry<T>>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stack.pop()
+    }
+}
+
 impl<'g, T> FusedIterator for Iter<'g, T> {}
 
 impl<'g, T> Iterator for Iter<'g, T> {
@@ -1,3 +1,24 @@
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod common {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+
+    /// Counts the live instances in the supplied counter.
+    pub(super) struct R(&'static AtomicUsize);
+    impl R {
+        pub(super) fn new(cnt: &'static AtomicUsize) -> R {
+            cnt.fetch_add(1, Relaxed);
+            R(cnt)
+        }
+    }
+    impl Drop for R {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Relaxed);
+        }
+    }
+}
+
 #[cfg(not(feature = "loom"))]
 #[cfg(test)]
 mod hashmap_test {
@@ -827,8 +848,8 @@ mod hashmap_test {
 #[cfg(test)]
 mod hashindex_test {
     use crate::ebr::Guard;
-    use crate::hash_index::Iter;
-    use crate::{Equivalent, HashIndex};
+    use crate::hash_index::{Iter, Keys, Statistics, Values};
+    use crate::{Equivalent, Expiring, HashIndex};
     use proptest::strategy::{Strategy, ValueTree};
     use proptest::test_runner::TestRunner;
     use std::collections::BTreeSet;
@@ -838,10 +859,14 @@ mod hashindex_test {
     use std::sync::atomic::{fence, AtomicU64, AtomicUsize};
     use std::sync::Arc;
     use std::thread;
+    use std::time::Duration;
     use tokio::sync::Barrier as AsyncBarrier;
 
     static_assertions::assert_impl_all!(HashIndex<String, String>: Send, Sync, UnwindSafe);
     static_assertions::assert_impl_all!(Iter<'static, 'static, String, String>: UnwindSafe);
+    static_assertions::assert_impl_all!(Keys<'static, 'static, String, String>: UnwindSafe);
+    static_assertions::assert_impl_all!(Values<'static, 'static, String, String>: UnwindSafe);
+    static_assertions::assert_impl_all!(Statistics: Send, Sync, UnwindSafe);
     static_assertions::assert_not_impl_all!(HashIndex<String, *const String>: Send, Sync, UnwindSafe);
     static_assertions::assert_not_impl_all!(Iter<'static, 'static, String, *const String>: Send, Sync, UnwindSafe);
 
@@ -1019,6 +1044,12 @@ mod hashindex_test {
             }
             let str_borrowed = str_val.as_str();
             assert!(hashindex1.peek_with(str_borrowed, |_, _| ()).is_some());
+            assert_eq!(
+                hashindex1
+                    .peek_entry(str_borrowed, &Guard::new())
+                    .map(|(k, _)| k.as_str()),
+                Some(str_borrowed)
+            );
 
             if hashindex2.insert(i, str_val.clone()).is_ok() {
                 checker2.insert((i, str_val.clone()));
@@ -1036,6 +1067,332 @@ mod hashindex_test {
         assert_eq!(hashindex2.len(), 0);
     }
 
+    #[test]
+    fn borrowed_key() {
+        let hashindex: HashIndex<Vec<u8>, usize> = HashIndex::default();
+        for k in 0..16_u8 {
+            assert!(hashindex.insert(vec![k; k as usize], k as usize).is_ok());
+        }
+        let guard = Guard::new();
+        for k in 0..16_u8 {
+            let key: &[u8] = &[k; 16][..k as usize];
+            assert_eq!(hashindex.peek(key, &guard), Some(&(k as usize)));
+            assert_eq!(hashindex.peek_with(key, |_, v| *v), Some(k as usize));
+            assert!(hashindex.contains(key));
+            assert!(hashindex.get(key).is_some());
+            assert!(hashindex.remove(key));
+            assert!(!hashindex.contains(key));
+        }
+    }
+
+    #[test]
+    fn keys_values() {
+        let hashindex: HashIndex<usize, usize> = HashIndex::default();
+        let workload_size = if cfg!(miri) { 64 } else { 4096 };
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, k * 2).is_ok());
+        }
+
+        let guard = Guard::new();
+        let keys: BTreeSet<usize> = hashindex.keys(&guard).copied().collect();
+        assert_eq!(keys.len(), workload_size);
+        assert!(keys.iter().copied().eq(0..workload_size));
+
+        let values: BTreeSet<usize> = hashindex.values(&guard).copied().collect();
+        assert_eq!(values.len(), workload_size);
+        assert!(values.iter().copied().eq((0..workload_size).map(|k| k * 2)));
+
+        for k in 0..workload_size / 2 {
+            assert!(hashindex.remove(&k));
+        }
+        assert_eq!(hashindex.keys(&guard).count(), workload_size / 2);
+        assert!(hashindex.values(&guard).all(|v| *v >= workload_size));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn remove_if() {
+        let hashindex: Arc<HashIndex<usize, usize>> = Arc::new(HashIndex::default());
+        let num_tasks = 4;
+        let workload_size = 1024;
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, 0).is_ok());
+        }
+
+        let num_removed = Arc::new(AtomicUsize::new(0));
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for task_id in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashindex_clone = hashindex.clone();
+            let num_removed_clone = num_removed.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                for k in 0..workload_size {
+                    let removed = if task_id % 2 == 0 {
+                        hashindex_clone.remove_if(&k, |v| *v == 0)
+                    } else {
+                        hashindex_clone.remove_if_async(&k, |v| *v == 0).await
+                    };
+                    if removed {
+                        num_removed_clone.fetch_add(1, Relaxed);
+                    }
+                }
+            }));
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        assert_eq!(num_removed.load(Relaxed), workload_size);
+        assert!(hashindex.is_empty());
+
+        assert!(hashindex.insert(0, 1).is_ok());
+        assert!(!hashindex.remove_if(&0, |v| *v == 0));
+        assert!(hashindex.remove_if(&0, |v| *v == 1));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn modify() {
+        let hashindex: Arc<HashIndex<usize, usize>> = Arc::new(HashIndex::default());
+        let num_tasks = 4;
+        let workload_size = 256;
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, 0).is_ok());
+        }
+
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for task_id in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashindex_clone = hashindex.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                for k in 0..workload_size {
+                    if task_id % 2 == 0 {
+                        assert!(hashindex_clone.modify(&k, |v| Some(v + 1)));
+                    } else {
+                        assert!(hashindex_clone.modify_async(&k, |v| Some(v + 1)).await);
+                    }
+                    assert!(hashindex_clone.peek_with(&k, |_, v| *v).is_some());
+                }
+            }));
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        for k in 0..workload_size {
+            assert_eq!(hashindex.peek_with(&k, |_, v| *v), Some(num_tasks));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn expiring() {
+        let hashindex: HashIndex<usize, Expiring<usize>> = HashIndex::default();
+        let workload_size = 1024;
+        for k in 0..workload_size {
+            let ttl = if k % 2 == 0 {
+                Duration::from_millis(10)
+            } else {
+                Duration::from_secs(3600)
+            };
+            if k % 4 < 2 {
+                assert!(hashindex.insert_with_ttl(k, k, ttl).is_ok());
+            } else {
+                assert!(hashindex.insert_with_ttl_async(k, k, ttl).await.is_ok());
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let guard = Guard::new();
+        for k in 0..workload_size {
+            assert_eq!(
+                hashindex.peek(&k, &guard).map(|v| *v.get()),
+                (k % 2 == 1).then_some(k)
+            );
+            assert_eq!(hashindex.peek_with(&k, |_, _| ()).is_some(), k % 2 == 1);
+            assert_eq!(hashindex.contains(&k), k % 2 == 1);
+            assert_eq!(hashindex.get(&k).is_some(), k % 2 == 1);
+            assert_eq!(hashindex.get_async(&k).await.is_some(), k % 2 == 1);
+            assert_eq!(hashindex.modify(&k, |v| Some(v.clone())), k % 2 == 1);
+        }
+        assert_eq!(hashindex.len(), workload_size);
+        assert_eq!(hashindex.iter(&guard).count(), workload_size / 2);
+        #[cfg(feature = "rayon")]
+        {
+            let num_visited = AtomicUsize::new(0);
+            hashindex.par_for_each(|_, _| {
+                num_visited.fetch_add(1, Relaxed);
+            });
+            assert_eq!(num_visited.load(Relaxed), workload_size / 2);
+        }
+        assert!(hashindex.keys(&guard).all(|k| k % 2 == 1));
+
+        let cloned = hashindex.clone();
+        assert_eq!(cloned.iter(&guard).count(), workload_size / 2);
+        assert!(!cloned.contains(&0));
+
+        let values: Vec<&Expiring<usize>> = hashindex.values(&guard).collect();
+        hashindex.remove_expired_async().await;
+        assert_eq!(hashindex.len(), workload_size / 2);
+        assert!(values.iter().all(|v| !v.is_expired()));
+
+        assert!(hashindex.insert_with_ttl(0, 1, Duration::ZERO).is_ok());
+        assert!(hashindex.insert_with_ttl(0, 2, Duration::ZERO).is_ok());
+        assert_eq!(hashindex.insert_with_ttl(1, 2, Duration::ZERO), Err((1, 2)));
+        hashindex.remove_expired();
+        assert!(!hashindex.contains(&0));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn insert_many() {
+        let hashindex: Arc<HashIndex<usize, usize>> = Arc::new(HashIndex::default());
+        let num_tasks = 4;
+        let workload_size = 65536;
+
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for task_id in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashindex_clone = hashindex.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                let range = (task_id * workload_size)..((task_id + 1) * workload_size);
+                if task_id % 2 == 0 {
+                    assert!(hashindex_clone.insert_many(range.map(|k| (k, k))).is_ok());
+                } else {
+                    assert!(hashindex_clone
+                        .insert_many_async(range.map(|k| (k, k)))
+                        .await
+                        .is_ok());
+                }
+            }));
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        assert_eq!(hashindex.len(), num_tasks * workload_size);
+        for k in 0..num_tasks * workload_size {
+            assert_eq!(hashindex.peek_with(&k, |_, v| *v), Some(k));
+        }
+
+        let rejected = hashindex
+            .insert_many([(0, 1), (usize::MAX, 1), (usize::MAX, 2)])
+            .unwrap_err();
+        assert_eq!(rejected.len(), 2);
+        assert!(rejected.contains(&(0, 1)));
+        assert_eq!(hashindex.peek_with(&0, |_, v| *v), Some(0));
+        assert!(hashindex.contains(&usize::MAX));
+    }
+
+    #[test]
+    fn statistics() {
+        let hashindex: HashIndex<usize, usize> = HashIndex::default();
+        assert_eq!(hashindex.statistics().capacity(), 0);
+
+        let workload_size = if cfg!(miri) { 64 } else { 4096 };
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, k).is_ok());
+        }
+        let statistics = hashindex.statistics();
+        assert_eq!(statistics.num_entries(), workload_size);
+        assert_eq!(statistics.num_removed_entries(), 0);
+        assert_eq!(statistics.capacity(), statistics.num_buckets() * 32);
+        assert!(statistics.capacity() >= workload_size);
+
+        let guard = Guard::new();
+        for k in 0..workload_size / 2 {
+            assert!(hashindex.remove(&k));
+        }
+        let statistics = hashindex.statistics();
+        assert_eq!(statistics.num_entries(), workload_size / 2);
+        assert!(statistics.num_removed_entries() <= workload_size / 2);
+        drop(guard);
+
+        let num_resizes = statistics.num_resizes();
+        assert!(hashindex.compact());
+        let statistics = hashindex.statistics();
+        assert!(!statistics.is_resizing());
+        assert_eq!(statistics.num_resizes(), num_resizes + 1);
+        assert_eq!(statistics.num_entries(), workload_size / 2);
+        assert_eq!(statistics.num_removed_entries(), 0);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_for_each_retain() {
+        let hashindex: Arc<HashIndex<usize, usize>> = Arc::new(HashIndex::default());
+        let workload_size = if cfg!(miri) { 64 } else { 65536 };
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, k).is_ok());
+        }
+
+        let hashindex_clone = hashindex.clone();
+        let inserter = thread::spawn(move || {
+            for k in workload_size..workload_size * 2 {
+                assert!(hashindex_clone.insert(k, k).is_ok());
+            }
+        });
+        let num_visited = AtomicUsize::new(0);
+        hashindex.par_for_each(|k, v| {
+            assert_eq!(k, v);
+            num_visited.fetch_add(1, Relaxed);
+        });
+        assert!(num_visited.load(Relaxed) >= workload_size);
+        assert!(inserter.join().is_ok());
+
+        hashindex.par_retain(|k, _| k % 2 == 0);
+        assert_eq!(hashindex.len(), workload_size);
+        let num_visited = AtomicUsize::new(0);
+        hashindex.par_for_each(|k, _| {
+            assert_eq!(k % 2, 0);
+            num_visited.fetch_add(1, Relaxed);
+        });
+        assert_eq!(num_visited.load(Relaxed), workload_size);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn compact() {
+        let hashindex: Arc<HashIndex<usize, usize>> = Arc::new(HashIndex::default());
+        let num_tasks = 4;
+        let workload_size = 4096;
+        for k in 0..workload_size {
+            assert!(hashindex.insert(k, k).is_ok());
+        }
+        hashindex.retain(|k, _| k % 64 == 0);
+        let num_retained = workload_size / 64;
+
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for task_id in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashindex_clone = hashindex.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                if task_id == 0 {
+                    hashindex_clone.compact_async().await;
+                } else {
+                    for k in (0..workload_size).step_by(64) {
+                        assert_eq!(hashindex_clone.peek_with(&k, |_, v| *v), Some(k));
+                    }
+                }
+            }));
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+
+        assert!(hashindex.compact());
+        assert_eq!(hashindex.len(), num_retained);
+        assert_eq!(hashindex.capacity(), (num_retained * 2).next_power_of_two());
+        for k in 0..workload_size {
+            assert_eq!(hashindex.contains(&k), k % 64 == 0);
+        }
+    }
+
     #[cfg_attr(miri, ignore)]
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn read() {
@@ -1473,6 +1830,8 @@ mod hashset_test {
     use crate::{Equivalent, HashSet};
     use std::hash::{Hash, Hasher};
     use std::panic::UnwindSafe;
+    use std::sync::Arc;
+    use tokio::sync::Barrier as AsyncBarrier;
 
     static_assertions::assert_impl_all!(HashSet<String>: Send, Sync, UnwindSafe);
     static_assertions::assert_not_impl_all!(HashSet<*const String>: Send, Sync, UnwindSafe);
@@ -1500,6 +1859,26 @@ mod hashset_test {
         assert!(hashset.contains("HELLO"));
     }
 
+    #[test]
+    fn borrowed_key() {
+        let hashset: HashSet<String> = HashSet::default();
+        assert!(hashset.insert("foo".to_owned()).is_ok());
+        assert!(hashset.insert("bar".to_owned()).is_ok());
+
+        assert!(hashset.contains("foo"));
+        assert!(!hashset.contains("baz"));
+        assert_eq!(hashset.read("bar", String::len), Some(3));
+        assert!(hashset.remove_if("foo", || false).is_none());
+        assert_eq!(hashset.remove("foo").as_deref(), Some("foo"));
+        assert_eq!(hashset.take("bar").as_deref(), Some("bar"));
+        assert!(hashset.is_empty());
+
+        let hashset: HashSet<Vec<u8>> = HashSet::default();
+        assert!(hashset.insert(vec![1, 2, 3]).is_ok());
+        assert!(hashset.contains(&[1, 2, 3][..]));
+        assert!(hashset.remove(&[1, 2, 3][..]).is_some());
+    }
+
     #[test]
     fn compare() {
         let hashset1: HashSet<String> = HashSet::new();
@@ -1521,59 +1900,491 @@ mod hashset_test {
         assert!(hashset1.remove("Hi").is_some());
         assert_ne!(hashset1, hashset2);
     }
-}
 
-#[cfg(not(feature = "loom"))]
-#[cfg(test)]
-mod hashcache_test {
-    use crate::hash_cache;
-    use crate::{Equivalent, HashCache};
-    use proptest::prelude::*;
-    use std::hash::{Hash, Hasher};
-    use std::panic::UnwindSafe;
-    use std::sync::atomic::AtomicUsize;
-    use std::sync::atomic::Ordering::Relaxed;
-    use std::sync::Arc;
-    use tokio::sync::Barrier as AsyncBarrier;
+    #[test]
+    fn take_replace() {
+        let hashset: HashSet<String> = HashSet::default();
+        let first = "HELLO".to_owned();
+        let first_ptr = first.as_ptr();
+        assert!(hashset.replace(first).is_none());
+
+        let second = "HELLO".to_owned();
+        let second_ptr = second.as_ptr();
+        let replaced = hashset.replace(second).unwrap();
+        assert_eq!(replaced.as_ptr(), first_ptr);
+        assert_eq!(hashset.len(), 1);
+        assert_eq!(hashset.read("HELLO", |k| k.as_ptr()), Some(second_ptr));
+
+        assert!(hashset.take("NO").is_none());
+        let taken = hashset.take("HELLO").unwrap();
+        assert_eq!(taken.as_ptr(), second_ptr);
+        assert!(hashset.is_empty());
+    }
 
-    static_assertions::assert_impl_all!(HashCache<String, String>: Send, Sync, UnwindSafe);
-    static_assertions::assert_not_impl_all!(HashCache<String, *const String>: Send, Sync, UnwindSafe);
-    static_assertions::assert_impl_all!(hash_cache::OccupiedEntry<String, String>: Send, Sync);
-    static_assertions::assert_not_impl_all!(hash_cache::OccupiedEntry<String, *const String>: Send, Sync, UnwindSafe);
-    static_assertions::assert_impl_all!(hash_cache::VacantEntry<String, String>: Send, Sync);
-    static_assertions::assert_not_impl_all!(hash_cache::VacantEntry<String, *const String>: Send, Sync, UnwindSafe);
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn take_replace_async() {
+        let hashset: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let num_tasks = 4;
+        let workload_size = 256;
 
-    struct R(&'static AtomicUsize);
-    impl R {
-        fn new(cnt: &'static AtomicUsize) -> R {
-            cnt.fetch_add(1, Relaxed);
-            R(cnt)
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for _ in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashset_clone = hashset.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                let mut num_replaced = 0;
+                for k in 0..workload_size {
+                    if hashset_clone.replace_async(k).await.is_some() {
+                        num_replaced += 1;
+                    }
+                }
+                num_replaced
+            }));
         }
-    }
-    impl Clone for R {
-        fn clone(&self) -> Self {
-            self.0.fetch_add(1, Relaxed);
-            R(self.0)
+        let mut num_replaced = 0;
+        for r in futures::future::join_all(task_handles).await {
+            num_replaced += r.unwrap();
         }
-    }
-    impl Drop for R {
-        fn drop(&mut self) {
-            self.0.fetch_sub(1, Relaxed);
+        assert_eq!(num_replaced, (num_tasks - 1) * workload_size);
+        for k in 0..workload_size {
+            assert_eq!(hashset.take_async(&k).await, Some(k));
         }
+        assert!(hashset.is_empty());
     }
 
-    #[derive(Debug, Eq, PartialEq)]
-    struct EqTest(String, usize);
-
-    impl Equivalent<EqTest> for str {
-        fn equivalent(&self, key: &EqTest) -> bool {
-            key.0.eq(self)
-        }
+    #[test]
+    fn pop_any() {
+        let workload_size = 1024;
+        let hashset: HashSet<usize> = (0..workload_size).collect();
+        let mut popped = std::collections::HashSet::new();
+        while let Some(k) = hashset.pop_any() {
+            assert!(k < workload_size);
+            assert!(popped.insert(k));
+        }
+        assert_eq!(popped.len(), workload_size);
+        assert!(hashset.is_empty());
     }
 
-    impl Hash for EqTest {
-        fn hash<H: Hasher>(&self, state: &mut H) {
-            self.0.hash(state)
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn pop_any_async() {
+        let num_tasks = 4;
+        let workload_size = 4096;
+        let hashset: Arc<HashSet<usize>> = Arc::new((0..workload_size).collect());
+
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for _ in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashset_clone = hashset.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                let mut popped = Vec::new();
+                while let Some(k) = hashset_clone.pop_any_async().await {
+                    popped.push(k);
+                }
+                popped
+            }));
+        }
+        let mut popped = Vec::new();
+        for r in futures::future::join_all(task_handles).await {
+            popped.append(&mut r.unwrap());
+        }
+        popped.sort_unstable();
+        assert_eq!(popped, (0..workload_size).collect::<Vec<_>>());
+        assert!(hashset.is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_for_each_retain_intersection() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering::Relaxed;
+
+        let workload_size = if cfg!(miri) { 64 } else { 65536 };
+        let hashset: Arc<HashSet<usize>> = Arc::new((0..workload_size).collect());
+
+        let hashset_clone = hashset.clone();
+        let remover = std::thread::spawn(move || {
+            for k in (0..workload_size).step_by(2) {
+                assert!(hashset_clone.remove(&k).is_some());
+            }
+        });
+        let num_visited = AtomicUsize::new(0);
+        hashset.par_for_each(|k| {
+            assert!(*k < workload_size);
+            num_visited.fetch_add(1, Relaxed);
+        });
+        assert!(num_visited.load(Relaxed) >= workload_size / 2);
+        assert!(remover.join().is_ok());
+
+        hashset.par_retain(|k| k % 4 == 1);
+        assert_eq!(hashset.len(), workload_size / 4);
+
+        let other: HashSet<usize> = (0..workload_size).filter(|k| k % 8 == 1).collect();
+        hashset.par_intersection_with(&other);
+        hashset.par_intersection_with(&hashset);
+        assert_eq!(hashset.len(), workload_size / 8);
+        hashset.par_for_each(|k| assert_eq!(k % 8, 1));
+    }
+
+    #[test]
+    fn subset_superset() {
+        let empty: HashSet<usize> = HashSet::default();
+        let small: HashSet<usize> = (0..64).collect();
+        let large: HashSet<usize> = (0..1024).collect();
+
+        assert!(empty.is_subset(&small));
+        assert!(small.is_superset(&empty));
+        assert!(small.is_subset(&small));
+        assert!(small.is_superset(&small));
+        assert!(small.is_subset(&large));
+        assert!(!small.is_superset(&large));
+        assert!(large.is_superset(&small));
+        assert!(!large.is_subset(&small));
+
+        assert!(small.insert(1024).is_ok());
+        assert!(!small.is_subset(&large));
+        assert!(!large.is_superset(&small));
+    }
+
+    #[test]
+    fn reserve_shrink_to_fit() {
+        let hashset: HashSet<usize> = HashSet::default();
+        assert!(!hashset.shrink_to_fit());
+
+        for k in 0..16 {
+            assert!(hashset.insert(k).is_ok());
+        }
+        let reserved = hashset.reserve(1024);
+        assert!(hashset.shrink_to_fit());
+        assert!(hashset.capacity() >= 1024);
+        drop(reserved);
+
+        assert!(hashset.shrink_to_fit());
+        assert_eq!(hashset.capacity(), 64);
+        for k in 0..16 {
+            assert!(hashset.contains(&k));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn shrink_to_fit_async() {
+        let workload_size = 4096;
+        let mut hashset: HashSet<usize> = HashSet::default();
+        hashset.extend(0..workload_size);
+        let hashset = Arc::new(hashset);
+        hashset.retain_async(|k| k % 64 == 0).await;
+
+        let hashset_clone = hashset.clone();
+        let task_handle = tokio::task::spawn(async move {
+            for k in (0..workload_size).step_by(64) {
+                assert!(hashset_clone.contains_async(&k).await);
+            }
+        });
+        let _: bool = hashset.shrink_to_fit_async().await;
+        assert!(task_handle.await.is_ok());
+        while !hashset.shrink_to_fit_async().await {}
+        assert!(hashset.capacity() < workload_size);
+        assert_eq!(hashset.len(), workload_size / 64);
+    }
+
+    #[test]
+    fn from_iter_extend_clone() {
+        let workload_size = 1024;
+        let mut hashset: HashSet<usize> = (0..workload_size).collect();
+        assert_eq!(hashset.len(), workload_size);
+        assert!(hashset.capacity() >= workload_size);
+
+        hashset.extend(workload_size / 2..workload_size * 2);
+        assert_eq!(hashset.len(), workload_size * 2);
+
+        let cloned = hashset.clone();
+        assert_eq!(cloned, hashset);
+        assert!(hashset.remove(&0).is_some());
+        assert!(cloned.contains(&0));
+        assert_ne!(cloned, hashset);
+    }
+
+    #[test]
+    fn retain() {
+        let hashset: HashSet<usize> = HashSet::default();
+        let workload_size = 1024;
+        for k in 0..workload_size {
+            assert!(hashset.insert(k).is_ok());
+        }
+
+        let mut num_visited = 0;
+        hashset.retain(|k| {
+            num_visited += 1;
+            k % 4 == 0
+        });
+        assert_eq!(num_visited, workload_size);
+        assert_eq!(hashset.len(), workload_size / 4);
+        for k in 0..workload_size {
+            assert_eq!(hashset.contains(&k), k % 4 == 0);
+        }
+
+        hashset.retain(|_| false);
+        assert!(hashset.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn retain_async() {
+        let hashset: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let workload_size = 4096;
+        for k in 0..workload_size {
+            assert!(hashset.insert_async(k).await.is_ok());
+        }
+
+        let hashset_clone = hashset.clone();
+        let task_handle = tokio::task::spawn(async move {
+            for k in workload_size..workload_size * 2 {
+                assert!(hashset_clone.insert_async(k).await.is_ok());
+            }
+        });
+        hashset.retain_async(|k| k % 2 == 0).await;
+        assert!(task_handle.await.is_ok());
+
+        for k in 0..workload_size {
+            assert_eq!(hashset.contains_async(&k).await, k % 2 == 0);
+        }
+    }
+
+    #[test]
+    fn scan_any() {
+        let hashset: HashSet<usize> = HashSet::default();
+        let workload_size = 1024;
+        for k in 0..workload_size {
+            assert!(hashset.insert(k).is_ok());
+        }
+
+        let mut sum = 0;
+        hashset.scan(|k| sum += k);
+        assert_eq!(sum, (0..workload_size).sum());
+
+        let mut num_visited = 0;
+        assert!(hashset.any(|k| {
+            num_visited += 1;
+            *k == 7
+        }));
+        assert!(num_visited <= workload_size);
+        assert!(!hashset.any(|k| *k >= workload_size));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn scan_any_async() {
+        let hashset: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let workload_size = 4096;
+        for k in 0..workload_size {
+            assert!(hashset.insert_async(k).await.is_ok());
+        }
+
+        let hashset_clone = hashset.clone();
+        let task_handle = tokio::task::spawn(async move {
+            for k in workload_size..workload_size * 2 {
+                assert!(hashset_clone.insert_async(k).await.is_ok());
+            }
+        });
+
+        let mut num_visited = 0;
+        hashset
+            .scan_async(|k| {
+                if *k < workload_size {
+                    num_visited += 1;
+                }
+            })
+            .await;
+        assert_eq!(num_visited, workload_size);
+        assert!(hashset.any_async(|k| *k == workload_size - 1).await);
+        assert!(task_handle.await.is_ok());
+        assert!(!hashset.any_async(|k| *k >= workload_size * 2).await);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn insert_remove_async() {
+        let hashset: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let num_tasks = 4;
+        let workload_size = 4096;
+
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        for task_id in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashset_clone = hashset.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                let range = (task_id * workload_size)..((task_id + 1) * workload_size);
+                for k in range.clone() {
+                    assert!(hashset_clone.insert_async(k).await.is_ok());
+                    assert_eq!(hashset_clone.insert_async(k).await, Err(k));
+                }
+                for k in range.clone() {
+                    assert!(hashset_clone.contains_async(&k).await);
+                    assert_eq!(hashset_clone.read_async(&k, |k| *k).await, Some(k));
+                }
+                for k in range.clone().step_by(2) {
+                    assert_eq!(hashset_clone.remove_async(&k).await, Some(k));
+                }
+                for k in range {
+                    assert_eq!(hashset_clone.contains_async(&k).await, k % 2 == 1);
+                }
+            }));
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        assert_eq!(hashset.len(), num_tasks * workload_size / 2);
+    }
+
+    #[test]
+    fn set_operations() {
+        let hashset1: HashSet<usize> = HashSet::default();
+        let hashset2: HashSet<usize> = HashSet::default();
+        let workload_size = 1024;
+
+        for k in 0..workload_size {
+            assert!(hashset1.insert(k).is_ok());
+            assert!(hashset2.insert(k + workload_size / 2).is_ok());
+        }
+
+        let union: HashSet<usize> = HashSet::default();
+        union.union_with(&hashset1);
+        union.union_with(&hashset2);
+        union.union_with(&union);
+        assert_eq!(union.len(), workload_size + workload_size / 2);
+
+        let intersection = hashset1.clone();
+        intersection.intersection_with(&hashset2);
+        intersection.intersection_with(&intersection);
+        assert_eq!(intersection.len(), workload_size / 2);
+        assert!(intersection.contains(&(workload_size / 2)));
+        assert!(!intersection.contains(&0));
+
+        let difference = hashset1.clone();
+        difference.difference_with(&hashset2);
+        assert_eq!(difference.len(), workload_size / 2);
+        assert!(difference.contains(&0));
+        assert!(!difference.contains(&(workload_size / 2)));
+        difference.difference_with(&difference);
+        assert!(difference.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn set_operations_concurrent() {
+        let hashset1: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let hashset2: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let workload_size = 4096;
+
+        for k in 0..workload_size {
+            assert!(hashset1.insert(k).is_ok());
+            assert!(hashset2.insert(k + workload_size).is_ok());
+        }
+
+        let hashset1_clone = hashset1.clone();
+        let hashset2_clone = hashset2.clone();
+        let thread_handle = std::thread::spawn(move || {
+            hashset2_clone.union_with(&hashset1_clone);
+        });
+        hashset1.union_with(&hashset2);
+        assert!(thread_handle.join().is_ok());
+
+        for k in 0..workload_size * 2 {
+            assert!(hashset1.contains(&k));
+            assert!(hashset2.contains(&k));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn set_operations_async() {
+        let hashset1: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let hashset2: Arc<HashSet<usize>> = Arc::new(HashSet::default());
+        let workload_size = 1024;
+
+        for k in 0..workload_size {
+            assert!(hashset1.insert_async(k).await.is_ok());
+            assert!(hashset2.insert_async(k + workload_size / 2).await.is_ok());
+        }
+
+        let hashset1_clone = hashset1.clone();
+        let hashset2_clone = hashset2.clone();
+        let task_handle = tokio::task::spawn(async move {
+            let difference = HashSet::<usize>::default();
+            difference.union_with_async(&*hashset2_clone).await;
+            difference.difference_with_async(&*hashset1_clone).await;
+            difference.len()
+        });
+        let intersection = HashSet::<usize>::default();
+        intersection.union_with_async(&*hashset1).await;
+        intersection.intersection_with_async(&*hashset2).await;
+        assert_eq!(intersection.len(), workload_size / 2);
+        assert_eq!(task_handle.await.unwrap(), workload_size / 2);
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod hashcache_test {
+    use crate::hash_cache;
+    use crate::hash_cache::{AdmissionPolicy, EvictionCause};
+    use crate::Expiring;
+    use crate::{Equivalent, HashCache};
+    use proptest::prelude::*;
+    use std::hash::{BuildHasherDefault, Hash, Hasher};
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::Ordering::{Acquire, Relaxed, Release};
+    use std::sync::atomic::{AtomicPtr, AtomicUsize};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Barrier as AsyncBarrier;
+
+    static_assertions::assert_impl_all!(HashCache<String, String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_not_impl_all!(HashCache<String, *const String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(hash_cache::OccupiedEntry<String, String>: Send, Sync);
+    static_assertions::assert_not_impl_all!(hash_cache::OccupiedEntry<String, *const String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(hash_cache::VacantEntry<String, String>: Send, Sync);
+    static_assertions::assert_not_impl_all!(hash_cache::VacantEntry<String, *const String>: Send, Sync, UnwindSafe);
+
+    struct R(&'static AtomicUsize);
+    impl R {
+        fn new(cnt: &'static AtomicUsize) -> R {
+            cnt.fetch_add(1, Relaxed);
+            R(cnt)
+        }
+    }
+    impl Clone for R {
+        fn clone(&self) -> Self {
+            self.0.fetch_add(1, Relaxed);
+            R(self.0)
+        }
+    }
+    impl Drop for R {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Relaxed);
+        }
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct EqTest(String, usize);
+
+    impl Equivalent<EqTest> for str {
+        fn equivalent(&self, key: &EqTest) -> bool {
+            key.0.eq(self)
+        }
+    }
+
+    impl Hash for EqTest {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
         }
     }
 
@@ -1717,38 +2528,628 @@ mod hashcache_test {
     }
 
     #[test]
-    fn sparse_cache() {
-        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
-        let hashcache = HashCache::<usize, R>::with_capacity(64, 64);
-        for s in 0..16 {
-            for k in s * 4..s * 4 + 4 {
-                assert!(hashcache.put(k, R::new(&INST_CNT)).is_ok());
+    fn lru_capacity_bound() {
+        let hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        assert!(hashcache.put(0, 0).is_ok());
+
+        let mut num_evicted = 0;
+        for k in 1..1024 {
+            assert!(hashcache.get(&0).is_some());
+            if let Some((evicted_key, evicted_val)) = hashcache.put(k, k).unwrap() {
+                assert_ne!(evicted_key, 0);
+                assert_eq!(evicted_key, evicted_val);
+                num_evicted += 1;
             }
-            hashcache.retain(|k, _| *k % 2 == 0);
-            for k in s * 4..s * 4 + 4 {
-                if hashcache.put(k, R::new(&INST_CNT)).is_err() {
-                    assert!(k % 2 == 0);
+            assert!(hashcache.len() <= 64);
+            assert!(hashcache.capacity() <= 64);
+        }
+        assert!(hashcache.contains(&0));
+        assert_eq!(hashcache.len() + num_evicted, 1024);
+    }
+
+    /// Places even keys in the first bucket and odd keys in the second bucket of a two-bucket
+    /// array.
+    #[derive(Default)]
+    struct ParityHasher(u64);
+
+    impl Hasher for ParityHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, _bytes: &[u8]) {
+            unreachable!();
+        }
+        fn write_usize(&mut self, k: usize) {
+            self.0 = ((k as u64 % 2) << 63) | k as u64;
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn lru_per_bucket() {
+        let hashcache: Arc<HashCache<usize, usize, BuildHasherDefault<ParityHasher>>> = Arc::new(
+            HashCache::with_capacity_and_hasher(0, 64, BuildHasherDefault::default()),
+        );
+        for k in 0..64 {
+            assert!(hashcache.put(k, k).unwrap().is_none());
+        }
+        assert_eq!(hashcache.capacity(), 64);
+
+        // Evicting entries in a bucket does not affect the other bucket.
+        for k in (64..1024).step_by(2) {
+            let (evicted_key, _) = hashcache.put(k, k).unwrap().unwrap();
+            assert_eq!(evicted_key % 2, 0);
+        }
+        for k in (1..64).step_by(2) {
+            assert!(hashcache.contains(&k));
+        }
+
+        let mut thread_handles = Vec::with_capacity(2);
+        for parity in 0..2 {
+            let hashcache_clone = hashcache.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                for k in (1024 + parity..4096).step_by(2) {
+                    let (evicted_key, _) = hashcache_clone.put(k, k).unwrap().unwrap();
+                    assert_eq!(evicted_key % 2, parity);
+                    assert!(hashcache_clone.contains(&k));
                 }
+            }));
+        }
+        for handle in thread_handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(hashcache.len(), 64);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn export_and_load() {
+        let hashcache: HashCache<usize, usize, BuildHasherDefault<ParityHasher>> =
+            HashCache::with_capacity_and_hasher(0, 64, BuildHasherDefault::default());
+        for k in 0..64 {
+            assert!(hashcache.put(k, k).unwrap().is_none());
+        }
+        assert!(hashcache.get(&0).is_some());
+        assert!(hashcache.get(&1).is_some());
+
+        let snapshot: Vec<(usize, usize)> = hashcache.export().collect();
+        assert_eq!(snapshot.len(), 64);
+        let mut hottest = [snapshot[0].0, snapshot[1].0];
+        hottest.sort_unstable();
+        assert_eq!(hottest, [0, 1]);
+        let mut coldest = [snapshot[62].0, snapshot[63].0];
+        coldest.sort_unstable();
+        assert_eq!(coldest, [2, 3]);
+
+        // Colder entries placed after the snapshot are evicted by the snapshot.
+        let restored: HashCache<usize, usize, BuildHasherDefault<ParityHasher>> =
+            HashCache::with_capacity_and_hasher(0, 64, BuildHasherDefault::default());
+        restored.load_from(snapshot.iter().cloned().chain((64..128).map(|k| (k, k))));
+        assert_eq!(restored.len(), 64);
+        for (k, v) in &snapshot {
+            assert_eq!(restored.read(k, |_, v| *v), Some(*v));
+        }
+        let mut reexported: Vec<usize> = restored.export().take(2).map(|(k, _)| k).collect();
+        reexported.sort_unstable();
+        assert_eq!(reexported, [0, 1]);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn pinning() {
+        let hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        for k in 0..8 {
+            assert!(hashcache.put(k, k).unwrap().is_none());
+            assert!(hashcache.pin(&k));
+        }
+        for k in 8..4096 {
+            if let Ok(Some((evicted_key, _))) = hashcache.put(k, k) {
+                assert!(evicted_key >= 8);
             }
-            hashcache.clear();
+        }
+        for k in 0..8 {
+            assert!(hashcache.contains(&k));
+            assert!(!hashcache.pin(&k));
+            assert!(hashcache.unpin(&k));
         }
 
-        drop(hashcache);
+        // Pinned entries stay pinned when the cache is resized.
+        let hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 4096);
+        for k in 0..16 {
+            assert!(hashcache.put(k, k).is_ok());
+            assert!(hashcache.pin(&k));
+        }
+        for k in 16..4096 {
+            assert!(hashcache.put(k, k).is_ok());
+        }
+        assert!(hashcache.capacity() > 64);
+        for k in 0..16 {
+            assert!(!hashcache.pin(&k));
+            assert!(!hashcache.unpin(&(k + 16)));
+        }
+    }
 
-        assert_eq!(INST_CNT.load(Relaxed), 0);
+    #[test]
+    fn pinned_weight() {
+        let mut hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        hashcache.set_weigher(10, |_, v| *v);
+
+        assert!(hashcache.put(0, 5).unwrap().is_none());
+        assert!(hashcache.pin(&0));
+        assert_eq!(hashcache.pinned_weight(), 5);
+        assert!(hashcache.put(1, 6).unwrap().is_none());
+        assert!(hashcache.put(2, 6).unwrap().is_none());
+        assert_eq!(hashcache.len(), 2);
+        assert!(hashcache.contains(&0));
+        assert_eq!(hashcache.total_weight(), 11);
+
+        if let Some(mut o) = hashcache.get(&0) {
+            assert_eq!(o.put(7), 5);
+        }
+        assert_eq!(hashcache.pinned_weight(), 7);
+        assert_eq!(hashcache.total_weight(), 13);
+
+        assert!(hashcache.unpin(&0));
+        assert_eq!(hashcache.pinned_weight(), 0);
+        assert_eq!(hashcache.total_weight(), 7);
+        assert!(hashcache.contains(&0));
+
+        assert!(hashcache.pin(&0));
+        assert_eq!(hashcache.pinned_weight(), 7);
+        assert!(hashcache.remove(&0).is_some());
+        assert_eq!(hashcache.pinned_weight(), 0);
+        assert_eq!(hashcache.total_weight(), 0);
+
+        assert!(hashcache.put(3, 3).unwrap().is_none());
+        assert!(hashcache.pin(&3));
+        hashcache.set_weigher(10, |_, v| *v * 2);
+        assert_eq!(hashcache.pinned_weight(), 6);
+        hashcache.clear();
+        assert_eq!(hashcache.pinned_weight(), 0);
+        assert_eq!(hashcache.total_weight(), 0);
     }
 
-    #[cfg_attr(miri, ignore)]
-    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
-    async fn put_get_remove() {
-        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
-        let hashcache: Arc<HashCache<usize, R>> = Arc::new(HashCache::default());
-        for _ in 0..256 {
-            let num_tasks = 8;
-            let workload_size = 256;
-            let mut task_handles = Vec::with_capacity(num_tasks);
-            let barrier = Arc::new(AsyncBarrier::new(num_tasks));
-            for task_id in 0..num_tasks {
+    #[test]
+    fn tiny_lfu_admission() {
+        let mut hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        hashcache.set_admission_policy(AdmissionPolicy::TinyLfu);
+
+        for k in 0..8 {
+            assert!(hashcache.put(k, k).unwrap().is_none());
+        }
+        for _ in 0..10 {
+            for k in 0..8 {
+                assert!(hashcache.read(&k, |_, v| *v).is_some());
+            }
+        }
+
+        for k in 1024..1280 {
+            if let Some((evicted_key, evicted_val)) = hashcache.put(k, k).unwrap() {
+                assert_eq!(evicted_key, evicted_val);
+                assert!(evicted_key >= 1024);
+            }
+        }
+        for k in 0..8 {
+            assert!(hashcache.contains(&k));
+        }
+        assert!(hashcache.len() <= 64);
+    }
+
+    #[test]
+    fn statistics() {
+        let num_threads = 4;
+        let workload_size = 256;
+        let hashcache: Arc<HashCache<usize, usize>> = Arc::new(HashCache::with_capacity(0, 256));
+        let mut thread_handles = Vec::with_capacity(num_threads);
+        for task_id in 0..num_threads {
+            let hashcache_clone = hashcache.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                let range = (task_id * workload_size)..((task_id + 1) * workload_size);
+                for k in range.clone() {
+                    assert!(hashcache_clone.put(k, k).is_ok());
+                }
+                for k in range {
+                    let _: bool = hashcache_clone.contains(&k);
+                    assert!(hashcache_clone.get(&usize::MAX).is_none());
+                }
+            }));
+        }
+        for handle in thread_handles {
+            handle.join().unwrap();
+        }
+
+        let statistics = hashcache.statistics();
+        assert_eq!(
+            statistics.hits() + statistics.misses(),
+            num_threads * workload_size * 2
+        );
+        assert!(statistics.misses() >= num_threads * workload_size);
+        assert_eq!(statistics.insertions(), num_threads * workload_size);
+        assert_eq!(
+            statistics.insertions() - statistics.evictions(),
+            hashcache.len()
+        );
+        assert!(statistics.hit_ratio() <= 0.5);
+
+        let hashcache: HashCache<usize, Expiring<usize>> = HashCache::default();
+        assert!(hashcache
+            .put(0, Expiring::with_ttl(0, Duration::ZERO))
+            .is_ok());
+        assert!(hashcache.get_unexpired(&0).is_none());
+        let statistics = hashcache.statistics();
+        assert_eq!(statistics.hits(), 0);
+        assert_eq!(statistics.misses(), 1);
+        assert_eq!(statistics.evictions(), 1);
+
+        let hashcache: HashCache<usize, usize> = HashCache::default();
+        assert!(hashcache.put(1, 1).is_ok());
+        assert!(hashcache.remove(&0).is_none());
+        assert!(hashcache.remove_if(&1, |_| false).is_none());
+        assert_eq!(hashcache.remove(&1), Some((1, 1)));
+        let statistics = hashcache.statistics();
+        assert_eq!((statistics.hits(), statistics.misses()), (0, 0));
+        assert_eq!((statistics.insertions(), statistics.evictions()), (1, 0));
+    }
+
+    #[test]
+    fn weigher() {
+        let maximum_weight = 4096;
+        let mut hashcache: HashCache<usize, Vec<u8>> = HashCache::with_capacity(0, 1024);
+        hashcache.set_weigher(maximum_weight, |_, v| v.len());
+        let sum_weight = |hashcache: &HashCache<usize, Vec<u8>>| {
+            let mut sum = 0;
+            hashcache.retain(|_, v| {
+                sum += v.len();
+                true
+            });
+            sum
+        };
+
+        for k in 0..1024 {
+            let weight = (k * 7) % 128 + 1;
+            assert!(hashcache.put(k, vec![0; weight]).is_ok());
+            assert!(hashcache.total_weight() <= maximum_weight);
+            assert_eq!(hashcache.total_weight(), sum_weight(&hashcache));
+        }
+        assert!(hashcache.len() < 1024);
+
+        let (evicted_key, _) = hashcache
+            .put(usize::MAX, vec![0; maximum_weight + 1])
+            .unwrap()
+            .unwrap();
+        assert_eq!(evicted_key, usize::MAX);
+
+        if let Some(mut entry) = hashcache.get(&1023) {
+            entry.put(vec![0; 2048]);
+        }
+        assert!(hashcache.total_weight() <= maximum_weight);
+        assert_eq!(hashcache.total_weight(), sum_weight(&hashcache));
+        assert!(hashcache.contains(&1023));
+
+        assert!(hashcache.remove(&1023).is_some());
+        assert_eq!(hashcache.total_weight(), sum_weight(&hashcache));
+        hashcache.clear();
+        assert_eq!(hashcache.total_weight(), 0);
+
+        for k in 0..16 {
+            assert!(hashcache.put(k, vec![0; 64]).is_ok());
+        }
+        hashcache.set_weigher(512, |_, v| v.len() * 2);
+        assert!(hashcache.total_weight() <= 512);
+        assert_eq!(hashcache.total_weight(), sum_weight(&hashcache) * 2);
+    }
+
+    #[test]
+    fn weigher_entry() {
+        let num_rejected = Arc::new(AtomicUsize::new(0));
+        let num_rejected_clone = num_rejected.clone();
+        let mut hashcache: HashCache<usize, Vec<u8>> = HashCache::default();
+        hashcache.set_weigher(1024, |_, v| v.len());
+        hashcache.set_eviction_listener(move |k, v, cause| {
+            assert_eq!((*k, v.len()), (100, 4096));
+            if cause == EvictionCause::Capacity {
+                num_rejected_clone.fetch_add(1, Relaxed);
+            }
+        });
+        for k in 0..8 {
+            assert!(hashcache.put(k, vec![0; 64]).is_ok());
+        }
+        assert_eq!((hashcache.len(), hashcache.total_weight()), (8, 512));
+
+        let (evicted, mut entry) = hashcache.entry(100).or_put(vec![0; 4096]);
+        assert!(evicted.is_none());
+        assert_eq!(entry.len(), 4096);
+        assert!(!entry.pin());
+        drop(entry);
+        assert_eq!(num_rejected.load(Relaxed), 1);
+        assert!(!hashcache.contains(&100));
+        assert_eq!((hashcache.len(), hashcache.total_weight()), (8, 512));
+
+        let (_, entry) = hashcache.entry(100).or_put(vec![0; 4096]);
+        assert_eq!(entry.remove().len(), 4096);
+        assert_eq!(num_rejected.load(Relaxed), 1);
+        assert_eq!((hashcache.len(), hashcache.total_weight()), (8, 512));
+    }
+
+    #[test]
+    fn eviction_listener_reentrant() {
+        let slot: Arc<AtomicPtr<HashCache<usize, usize>>> = Arc::new(AtomicPtr::default());
+        let slot_clone = slot.clone();
+        let num_evicted = Arc::new(AtomicUsize::new(0));
+        let num_evicted_clone = num_evicted.clone();
+        let mut hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        hashcache.set_weigher(256, |_, v| *v);
+        hashcache.set_eviction_listener(move |k, _, _| {
+            let hashcache = unsafe { &*slot_clone.load(Acquire) };
+            assert!(!hashcache.contains(k));
+            num_evicted_clone.fetch_add(1, Relaxed);
+        });
+        let hashcache_ptr = Box::into_raw(Box::new(hashcache));
+        slot.store(hashcache_ptr, Release);
+        let hashcache = unsafe { &*hashcache_ptr };
+
+        for k in 0..256 {
+            assert!(hashcache.put(k, 1).is_ok());
+        }
+        assert!(num_evicted.load(Relaxed) > 0);
+        for k in 256..512 {
+            let _: (_, _) = hashcache.entry(k).or_put(1);
+        }
+        assert!(hashcache.put(1024, 200).is_ok());
+        assert!(hashcache.total_weight() <= 256);
+        if let hash_cache::Entry::Occupied(mut o) = hashcache.entry(1024) {
+            o.put(250);
+        }
+        assert!(hashcache.total_weight() <= 256);
+        assert_eq!(hashcache.remove(&1024), Some((1024, 250)));
+        hashcache.retain(|_, _| false);
+        assert!(hashcache.is_empty());
+
+        drop(unsafe { Box::from_raw(hashcache_ptr) });
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn weigher_concurrent() {
+        let num_threads = 4;
+        let workload_size = 1024;
+        let maximum_weight = 8192;
+        let mut hashcache: HashCache<usize, Vec<u8>> = HashCache::with_capacity(0, 4096);
+        hashcache.set_weigher(maximum_weight, |_, v| v.len());
+        let hashcache = Arc::new(hashcache);
+        let mut thread_handles = Vec::with_capacity(num_threads);
+        for task_id in 0..num_threads {
+            let hashcache_clone = hashcache.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                for k in (task_id * workload_size)..((task_id + 1) * workload_size) {
+                    let weight = (k * 13) % 64 + 1;
+                    assert!(hashcache_clone.put(k, vec![0; weight]).is_ok());
+                    if k % 4 == 0 {
+                        let _: Option<(usize, Vec<u8>)> = hashcache_clone.remove(&(k / 2));
+                    }
+                }
+            }));
+        }
+        for handle in thread_handles {
+            handle.join().unwrap();
+        }
+
+        let mut sum = 0;
+        hashcache.retain(|_, v| {
+            sum += v.len();
+            true
+        });
+        assert_eq!(hashcache.total_weight(), sum);
+        assert!(hashcache.put(usize::MAX, vec![0; 1]).is_ok());
+        assert!(hashcache.total_weight() <= maximum_weight);
+    }
+
+    #[test]
+    fn eviction_listener() {
+        let evicted: Arc<std::sync::Mutex<Vec<(usize, usize, EvictionCause)>>> = Arc::default();
+        let evicted_clone = evicted.clone();
+        let mut hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        hashcache.set_eviction_listener(move |k, v, cause| {
+            evicted_clone.lock().unwrap().push((*k, *v, cause));
+        });
+
+        let mut num_evicted = 0;
+        for k in 0..256 {
+            let result = if k % 2 == 0 {
+                hashcache.put(k, k).unwrap()
+            } else {
+                hashcache.entry(k).or_put(k).0
+            };
+            if let Some((evicted_key, evicted_val)) = result {
+                num_evicted += 1;
+                assert_eq!(
+                    evicted.lock().unwrap().last(),
+                    Some(&(evicted_key, evicted_val, EvictionCause::Capacity))
+                );
+            }
+        }
+        assert_eq!(evicted.lock().unwrap().len(), num_evicted);
+        evicted.lock().unwrap().clear();
+
+        let removed = hashcache.remove(&255).unwrap();
+        assert_eq!(
+            evicted.lock().unwrap().pop(),
+            Some((removed.0, removed.1, EvictionCause::Removed))
+        );
+
+        let len = hashcache.len();
+        hashcache.retain(|k, _| k % 2 == 0);
+        let num_retained = hashcache.len();
+        hashcache.clear();
+        let evicted = evicted.lock().unwrap();
+        assert_eq!(evicted.len(), len);
+        assert!(evicted[..len - num_retained]
+            .iter()
+            .all(|(k, _, _)| k % 2 == 1));
+        assert!(evicted
+            .iter()
+            .all(|(k, v, cause)| k == v && *cause == EvictionCause::Removed));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn eviction_listener_async() {
+        let num_notified: Arc<AtomicUsize> = Arc::default();
+        let num_notified_clone = num_notified.clone();
+        let mut hashcache: HashCache<usize, usize> = HashCache::with_capacity(0, 64);
+        hashcache.set_eviction_listener(move |_, _, _| {
+            num_notified_clone.fetch_add(1, Relaxed);
+        });
+
+        let mut num_evicted = 0;
+        for k in 0..256 {
+            if hashcache.put_async(k, k).await.unwrap().is_some() {
+                num_evicted += 1;
+            }
+        }
+        assert_eq!(num_notified.load(Relaxed), num_evicted);
+
+        let len = hashcache.len();
+        hashcache.clear_async().await;
+        assert_eq!(num_notified.load(Relaxed), num_evicted + len);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn get_or_insert_async() {
+        let num_tasks = 8;
+        let workload_size = 64;
+        let num_loads: Arc<AtomicUsize> = Arc::default();
+        let hashcache: Arc<HashCache<usize, usize>> = Arc::new(HashCache::with_capacity(0, 1024));
+        let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+        let mut task_handles = Vec::with_capacity(num_tasks);
+        for _ in 0..num_tasks {
+            let barrier_clone = barrier.clone();
+            let hashcache_clone = hashcache.clone();
+            let num_loads_clone = num_loads.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                barrier_clone.wait().await;
+                for k in 0..workload_size {
+                    let entry = hashcache_clone
+                        .get_or_insert_async(k, || async {
+                            num_loads_clone.fetch_add(1, Relaxed);
+                            tokio::task::yield_now().await;
+                            k
+                        })
+                        .await;
+                    assert_eq!(*entry.get(), k);
+                }
+            }));
+        }
+        for handle in futures::future::join_all(task_handles).await {
+            assert!(handle.is_ok());
+        }
+        assert_eq!(num_loads.load(Relaxed), workload_size);
+        assert_eq!(hashcache.len(), workload_size);
+
+        // A cancelled loader is taken over by another caller.
+        let pending = hashcache.get_or_insert_async(usize::MAX, std::future::pending);
+        assert!(tokio::time::timeout(Duration::from_millis(1), pending)
+            .await
+            .is_err());
+        let entry = hashcache
+            .get_or_insert_async(usize::MAX, || async { 0 })
+            .await;
+        assert_eq!(*entry.get(), 0);
+    }
+
+    #[test]
+    fn timed_expiration() {
+        let num_expired: Arc<AtomicUsize> = Arc::default();
+        let num_expired_clone = num_expired.clone();
+        let mut hashcache: HashCache<usize, Expiring<usize>> = HashCache::default();
+        hashcache.set_eviction_listener(move |_, _, cause| {
+            if cause == EvictionCause::Expired {
+                num_expired_clone.fetch_add(1, Relaxed);
+            }
+        });
+
+        let workload_size = 64;
+        for k in 0..workload_size {
+            let expiring = match k % 4 {
+                0 => Expiring::new(k),
+                1 => Expiring::with_ttl(k, Duration::ZERO),
+                2 => Expiring::with_tti(k, Duration::from_secs(3600)),
+                _ => Expiring::with_ttl_and_tti(k, Duration::from_secs(3600), Duration::ZERO),
+            };
+            assert!(hashcache.put(k, expiring).is_ok());
+        }
+
+        for k in (0..workload_size).step_by(8) {
+            assert!(hashcache.get_unexpired(&(k + 1)).is_none());
+            assert!(hashcache.get_unexpired(&(k + 3)).is_none());
+            let mut entry = hashcache.get_unexpired(&(k + 2)).unwrap();
+            assert_eq!(*entry.get().get(), k + 2);
+            *entry.get_mut().get_mut() += 1;
+        }
+        assert_eq!(num_expired.load(Relaxed), workload_size / 4);
+        assert_eq!(hashcache.read(&2, |_, t| *t.get()), Some(3));
+
+        hashcache.remove_expired();
+        assert_eq!(num_expired.load(Relaxed), workload_size / 2);
+        assert_eq!(hashcache.len(), workload_size / 2);
+        hashcache.scan(|k, t| {
+            assert!(k % 4 == 0 || k % 4 == 2);
+            assert!(!t.is_expired());
+        });
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn timed_expiration_async() {
+        let hashcache: HashCache<usize, Expiring<usize>> = HashCache::default();
+        for k in 0..64 {
+            let expiring = if k % 2 == 0 {
+                Expiring::with_tti(k, Duration::from_secs(3600))
+            } else {
+                Expiring::with_tti(k, Duration::ZERO)
+            };
+            assert!(hashcache.put_async(k, expiring).await.is_ok());
+        }
+        assert!(hashcache.get_unexpired_async(&0).await.is_some());
+        assert!(hashcache.get_unexpired_async(&1).await.is_none());
+        hashcache.remove_expired_async().await;
+        assert_eq!(hashcache.len(), 32);
+    }
+
+    #[test]
+    fn sparse_cache() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let hashcache = HashCache::<usize, R>::with_capacity(64, 64);
+        for s in 0..16 {
+            for k in s * 4..s * 4 + 4 {
+                assert!(hashcache.put(k, R::new(&INST_CNT)).is_ok());
+            }
+            hashcache.retain(|k, _| *k % 2 == 0);
+            for k in s * 4..s * 4 + 4 {
+                if hashcache.put(k, R::new(&INST_CNT)).is_err() {
+                    assert!(k % 2 == 0);
+                }
+            }
+            hashcache.clear();
+        }
+
+        drop(hashcache);
+
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+    async fn put_get_remove() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let hashcache: Arc<HashCache<usize, R>> = Arc::new(HashCache::default());
+        for _ in 0..256 {
+            let num_tasks = 8;
+            let workload_size = 256;
+            let mut task_handles = Vec::with_capacity(num_tasks);
+            let barrier = Arc::new(AsyncBarrier::new(num_tasks));
+            for task_id in 0..num_tasks {
                 let barrier_clone = barrier.clone();
                 let hashcache_clone = hashcache.clone();
                 task_handles.push(tokio::task::spawn(async move {
@@ -2524,213 +3925,1444 @@ mod treeindex_test {
                 }
             }));
         }
-        for handle in thread_handles {
-            handle.join().unwrap();
+        for handle in thread_handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(tree.len(), 0);
+        assert_eq!(tree.depth(), 0);
+    }
+
+    #[test]
+    fn string_key() {
+        let tree1: TreeIndex<String, u32> = TreeIndex::default();
+        let tree2: TreeIndex<u32, String> = TreeIndex::default();
+        let mut checker1 = BTreeSet::new();
+        let mut checker2 = BTreeSet::new();
+        let mut runner = TestRunner::default();
+        let test_size = if cfg!(miri) { 16 } else { 1024 };
+        for i in 0..test_size {
+            let prop_str = "[a-z]{1,16}".new_tree(&mut runner).unwrap();
+            let str_val = prop_str.current();
+            if tree1.insert(str_val.clone(), i).is_ok() {
+                checker1.insert((str_val.clone(), i));
+            }
+            let str_borrowed = str_val.as_str();
+            assert!(tree1.peek_with(str_borrowed, |_, _| ()).is_some());
+
+            if tree2.insert(i, str_val.clone()).is_ok() {
+                checker2.insert((i, str_val.clone()));
+            }
+        }
+        for iter in &checker1 {
+            let v = tree1.peek_with(iter.0.as_str(), |_, v| *v);
+            assert_eq!(v.unwrap(), iter.1);
+        }
+        for iter in &checker2 {
+            let v = tree2.peek_with(&iter.0, |_, v| v.clone());
+            assert_eq!(v.unwrap(), iter.1);
+        }
+    }
+
+    #[test]
+    fn scanner() {
+        let data_size = if cfg!(miri) { 128 } else { 4096 };
+        let iteration = if cfg!(miri) { 4 } else { 64 };
+        for _ in 0..iteration {
+            let tree: Arc<TreeIndex<usize, u64>> = Arc::new(TreeIndex::default());
+            let barrier = Arc::new(Barrier::new(3));
+            let inserted = Arc::new(AtomicUsize::new(0));
+            let removed = Arc::new(AtomicUsize::new(data_size));
+            let mut thread_handles = Vec::new();
+            for _ in 0..2 {
+                let tree_clone = tree.clone();
+                let barrier_clone = barrier.clone();
+                let inserted_clone = inserted.clone();
+                let removed_clone = removed.clone();
+                let thread_handle = thread::spawn(move || {
+                    // test insert
+                    for _ in 0..2 {
+                        barrier_clone.wait();
+                        let max = inserted_clone.load(Acquire);
+                        let mut prev = 0;
+                        let mut iterated = 0;
+                        let guard = Guard::new();
+                        for iter in tree_clone.iter(&guard) {
+                            assert!(
+                                prev == 0
+                                    || (*iter.0 <= max && prev + 1 == *iter.0)
+                                    || *iter.0 > prev
+                            );
+                            prev = *iter.0;
+                            iterated += 1;
+                        }
+                        assert!(iterated >= max);
+                    }
+                    // test remove
+                    for _ in 0..2 {
+                        barrier_clone.wait();
+                        let mut prev = 0;
+                        let max = removed_clone.load(Acquire);
+                        let guard = Guard::new();
+                        for iter in tree_clone.iter(&guard) {
+                            let current = *iter.0;
+                            assert!(current < max);
+                            assert!(prev + 1 == current || prev == 0);
+                            prev = current;
+                        }
+                    }
+                });
+                thread_handles.push(thread_handle);
+            }
+            // insert
+            barrier.wait();
+            for i in 0..data_size {
+                if i == data_size / 2 {
+                    barrier.wait();
+                }
+                assert!(tree.insert(i, 0).is_ok());
+                inserted.store(i, Release);
+            }
+            // remove
+            barrier.wait();
+            for i in (0..data_size).rev() {
+                if i == data_size / 2 {
+                    barrier.wait();
+                }
+                assert!(tree.remove(&i));
+                removed.store(i, Release);
+            }
+            thread_handles.into_iter().for_each(|t| t.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn range() {
+        let tree: TreeIndex<String, usize> = TreeIndex::default();
+        assert!(tree.insert("Ape".to_owned(), 0).is_ok());
+        assert!(tree.insert("Apple".to_owned(), 1).is_ok());
+        assert!(tree.insert("Banana".to_owned(), 3).is_ok());
+        assert!(tree.insert("Badezimmer".to_owned(), 2).is_ok());
+        assert_eq!(tree.range(..="Ball".to_owned(), &Guard::new()).count(), 3);
+        assert_eq!(
+            tree.range("Ape".to_owned()..="Ball".to_owned(), &Guard::new())
+                .count(),
+            3
+        );
+        assert_eq!(
+            tree.range("Apex".to_owned()..="Ball".to_owned(), &Guard::new())
+                .count(),
+            2
+        );
+        assert_eq!(
+            tree.range("Ace".to_owned()..="Ball".to_owned(), &Guard::new())
+                .count(),
+            3
+        );
+        assert_eq!(tree.range(..="Z".to_owned(), &Guard::new()).count(), 4);
+        assert_eq!(
+            tree.range("Ape".to_owned()..="Z".to_owned(), &Guard::new())
+                .count(),
+            4
+        );
+        assert_eq!(
+            tree.range("Apex".to_owned()..="Z".to_owned(), &Guard::new())
+                .count(),
+            3
+        );
+        assert_eq!(
+            tree.range("Ace".to_owned()..="Z".to_owned(), &Guard::new())
+                .count(),
+            4
+        );
+        assert_eq!(tree.range(.."Banana".to_owned(), &Guard::new()).count(), 3);
+        assert_eq!(
+            tree.range("Ape".to_owned().."Banana".to_owned(), &Guard::new())
+                .count(),
+            3
+        );
+        assert_eq!(
+            tree.range("Apex".to_owned().."Banana".to_owned(), &Guard::new())
+                .count(),
+            2
+        );
+        assert_eq!(
+            tree.range("Ace".to_owned().."Banana".to_owned(), &Guard::new())
+                .count(),
+            3
+        );
+    }
+
+    proptest! {
+        #[cfg_attr(miri, ignore)]
+        #[test]
+        fn prop_remove_range(lower in 0_usize..4096_usize, range in 0_usize..4096_usize) {
+            let remove_range = lower..lower + range;
+            let insert_range = (256_usize, 4095_usize);
+            let tree = TreeIndex::default();
+            for k in insert_range.0..=insert_range.1 {
+                prop_assert!(tree.insert(k, k).is_ok());
+            }
+            if usize::BITS == 32 {
+                prop_assert_eq!(tree.depth(), 4);
+            } else {
+                prop_assert_eq!(tree.depth(), 3);
+            }
+            tree.remove_range(remove_range.clone());
+            if remove_range.contains(&insert_range.0) && remove_range.contains(&insert_range.1) {
+                prop_assert!(tree.is_empty());
+            }
+            for (k, v) in tree.iter(&Guard::new()) {
+                prop_assert_eq!(k, v);
+                prop_assert!(!remove_range.contains(k), "{k}");
+            }
+            for k in 0_usize..4096_usize {
+                if tree.peek_with(&k, |_, _|()).is_some() {
+                    prop_assert!(!remove_range.contains(&k), "{k}");
+                }
+            }
+            for k in remove_range.clone() {
+                prop_assert!(tree.insert(k, k).is_ok());
+            }
+            let mut cnt = 0;
+            for (k, v) in tree.iter(&Guard::new()) {
+                prop_assert_eq!(k, v);
+                if remove_range.contains(k) {
+                    cnt += 1;
+                }
+            }
+            assert_eq!(cnt, range);
+        }
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod skiplist_test {
+    use super::common::R;
+    use crate::ebr::Guard;
+    use crate::skip_list::{Iter, Range};
+    use crate::SkipList;
+    use std::collections::BTreeMap;
+    use std::ops::Bound::{Excluded, Included, Unbounded};
+    use std::ops::{Bound, RangeInclusive};
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(SkipList<String, String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(Iter<'static, 'static, String, String>: UnwindSafe);
+    static_assertions::assert_impl_all!(Range<'static, 'static, String, String, String, RangeInclusive<String>>: UnwindSafe);
+    static_assertions::assert_not_impl_all!(SkipList<String, *const String>: Send, Sync);
+    static_assertions::assert_not_impl_all!(Iter<'static, 'static, String, *const String>: Send, Sync);
+
+    #[test]
+    fn insert_drop() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let skiplist: SkipList<usize, R> = SkipList::default();
+
+        let workload_size = 256;
+        for k in 0..workload_size {
+            assert!(skiplist.insert(k, R::new(&INST_CNT)).is_ok());
+        }
+        let (k, _) = skiplist.insert(0, R::new(&INST_CNT)).unwrap_err();
+        assert_eq!(k, 0);
+        assert_eq!(INST_CNT.load(Relaxed), workload_size);
+        assert_eq!(skiplist.len(), workload_size);
+        drop(skiplist);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn insert_remove() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let skiplist: SkipList<usize, R> = SkipList::default();
+
+        let workload_size = 256;
+        for k in (0..workload_size).rev() {
+            assert!(skiplist.insert(k, R::new(&INST_CNT)).is_ok());
+        }
+        assert!(skiplist
+            .iter(&Guard::new())
+            .map(|(k, _)| *k)
+            .eq(0..workload_size));
+        for k in (0..workload_size).filter(|k| k % 2 == 0) {
+            assert!(skiplist.remove(&k));
+            assert!(!skiplist.remove(&k));
+        }
+        assert_eq!(skiplist.len(), workload_size / 2);
+        assert!(skiplist
+            .iter(&Guard::new())
+            .map(|(k, _)| *k)
+            .eq((0..workload_size).filter(|k| k % 2 == 1)));
+        skiplist.clear();
+        assert!(skiplist.is_empty());
+        assert!(skiplist.iter(&Guard::new()).next().is_none());
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn range() {
+        let skiplist: SkipList<usize, usize> = SkipList::default();
+        let mut btreemap: BTreeMap<usize, usize> = BTreeMap::default();
+        for k in (0..64).map(|k| k * 3) {
+            assert!(skiplist.insert(k, k).is_ok());
+            btreemap.insert(k, k);
+        }
+
+        let guard = Guard::new();
+        let bounds = |k: usize| [Included(k), Excluded(k), Unbounded];
+        for start in 0..200 {
+            for end in start..200 {
+                for start_bound in bounds(start) {
+                    for end_bound in bounds(end) {
+                        let range: (Bound<usize>, Bound<usize>) = (start_bound, end_bound);
+                        if matches!(range, (Excluded(s), Excluded(e)) if s == e) {
+                            continue;
+                        }
+                        assert!(skiplist.range(range, &guard).eq(btreemap.range(range)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_race() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        const NUM_THREADS: usize = 8;
+        let workload_size = 1024;
+        let skiplist: Arc<SkipList<usize, R>> = Arc::new(SkipList::default());
+        let num_inserted = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for _ in 0..NUM_THREADS {
+            let skiplist_clone = skiplist.clone();
+            let num_inserted_clone = num_inserted.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for k in 0..workload_size {
+                    if skiplist_clone.insert(k, R::new(&INST_CNT)).is_ok() {
+                        num_inserted_clone.fetch_add(1, Relaxed);
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(num_inserted.load(Relaxed), workload_size);
+        assert_eq!(skiplist.len(), workload_size);
+        assert_eq!(INST_CNT.load(Relaxed), workload_size);
+        assert!(skiplist
+            .iter(&Guard::new())
+            .map(|(k, _)| *k)
+            .eq(0..workload_size));
+        drop(skiplist);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_remove_churn() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        const NUM_THREADS: usize = 8;
+        let workload_size = 64;
+        let skiplist: Arc<SkipList<usize, R>> = Arc::new(SkipList::default());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for _ in 0..NUM_THREADS {
+            let skiplist_clone = skiplist.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for _ in 0..64 {
+                    for k in 0..workload_size {
+                        let _result = skiplist_clone.insert(k, R::new(&INST_CNT));
+                    }
+                    for k in 0..workload_size {
+                        skiplist_clone.remove(&k);
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert!(skiplist.is_empty());
+        assert!(skiplist.iter(&Guard::new()).next().is_none());
+        for k in 0..workload_size {
+            assert!(skiplist.insert(k, R::new(&INST_CNT)).is_ok());
+        }
+        assert_eq!(skiplist.len(), workload_size);
+        drop(skiplist);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_remove_scan() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 4096;
+        let skiplist: Arc<SkipList<usize, usize>> = Arc::new(SkipList::default());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS + 1));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS + 1);
+        for task_id in 0..NUM_THREADS {
+            let skiplist_clone = skiplist.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let keys = (0..workload_size).map(|k| k * NUM_THREADS + task_id);
+                for k in keys.clone() {
+                    assert!(skiplist_clone.insert(k, k).is_ok());
+                    assert_eq!(skiplist_clone.peek_with(&k, |_, v| *v), Some(k));
+                }
+                for k in keys.clone().filter(|k| k % 3 == 0) {
+                    assert!(skiplist_clone.remove_if(&k, |v| *v == k));
+                    assert!(!skiplist_clone.contains(&k));
+                }
+                for k in keys {
+                    assert_eq!(skiplist_clone.contains(&k), k % 3 != 0);
+                }
+            }));
+        }
+        let skiplist_clone = skiplist.clone();
+        let barrier_clone = barrier.clone();
+        thread_handles.push(thread::spawn(move || {
+            barrier_clone.wait();
+            for _ in 0..16 {
+                let guard = Guard::new();
+                let mut last = None;
+                for (k, v) in skiplist_clone.iter(&guard) {
+                    assert_eq!(k, v);
+                    assert!(last.map_or(true, |last| last < *k));
+                    last.replace(*k);
+                }
+            }
+        }));
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let expected = (0..workload_size * NUM_THREADS).filter(|k| k % 3 != 0);
+        assert_eq!(skiplist.len(), expected.clone().count());
+        assert!(skiplist.iter(&Guard::new()).map(|(k, _)| *k).eq(expected));
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod rangemap_test {
+    use super::common::R;
+    use crate::ebr::Guard;
+    use crate::range_map::{Iter, Stab};
+    use crate::RangeMap;
+    use std::ops::Range;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(RangeMap<String, String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(Iter<'static, String, String>: UnwindSafe);
+    static_assertions::assert_impl_all!(Stab<'static, 'static, String, String, String>: UnwindSafe);
+    static_assertions::assert_not_impl_all!(RangeMap<String, *const String>: Send, Sync);
+
+    #[test]
+    fn insert_drop() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let rangemap: RangeMap<usize, R> = RangeMap::default();
+
+        let workload_size = 256;
+        for k in 0..workload_size {
+            assert!(rangemap.insert(k..k + 2, R::new(&INST_CNT)).is_ok());
+        }
+        let (range, _) = rangemap.insert(0..2, R::new(&INST_CNT)).unwrap_err();
+        assert_eq!(range, 0..2);
+        assert_eq!(INST_CNT.load(Relaxed), workload_size);
+        assert_eq!(rangemap.len(), workload_size);
+
+        let rangemap_clone = rangemap.clone();
+        rangemap.clear();
+        assert!(rangemap.is_empty());
+        assert_eq!(rangemap_clone.len(), workload_size);
+        for k in 0..workload_size {
+            assert!(rangemap_clone.remove(&(k..k + 2)));
+            assert!(!rangemap_clone.remove(&(k..k + 2)));
+        }
+        assert!(rangemap_clone.is_empty());
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn insert_many() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let rangemap: RangeMap<usize, R> = RangeMap::default();
+
+        let workload_size = 1024;
+        assert!(rangemap
+            .insert_many(
+                (0..workload_size)
+                    .rev()
+                    .map(|k| (k..k + 2, R::new(&INST_CNT)))
+            )
+            .is_ok());
+        assert_eq!(rangemap.len(), workload_size);
+
+        let rejected = rangemap
+            .insert_many(
+                [0..2, 4..4, 2048..2050, 2048..2050, 8..9]
+                    .into_iter()
+                    .map(|r| (r, R::new(&INST_CNT))),
+            )
+            .unwrap_err();
+        let mut rejected: Vec<Range<usize>> = rejected.into_iter().map(|(r, _)| r).collect();
+        rejected.sort_by_key(|r| (r.start, r.end));
+        assert_eq!(rejected, [0..2, 4..4, 2048..2050]);
+        assert_eq!(rangemap.len(), workload_size + 2);
+        assert_eq!(INST_CNT.load(Relaxed), workload_size + 2);
+
+        let guard = Guard::new();
+        assert!(rangemap
+            .iter(&guard)
+            .zip(rangemap.iter(&guard).skip(1))
+            .all(|((a, _), (b, _))| (a.start, a.end) < (b.start, b.end)));
+        assert!(rangemap
+            .stab(&8, &guard)
+            .map(|(r, _)| r)
+            .eq([&(7..9), &(8..9), &(8..10)]));
+        drop(guard);
+
+        rangemap.clear();
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn stab() {
+        let rangemap: RangeMap<usize, usize> = RangeMap::default();
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+        for v in 0..512 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let start = usize::try_from(seed % 1024).unwrap();
+            let len = usize::try_from((seed >> 32) % 64).unwrap() + 1;
+            if rangemap.insert(start..start + len, v).is_ok() {
+                ranges.push(start..start + len);
+            }
+        }
+        ranges.sort_by(|a, b| a.start.cmp(&b.start).then(a.end.cmp(&b.end)));
+        assert_eq!(rangemap.len(), ranges.len());
+
+        let guard = Guard::new();
+        assert!(rangemap.iter(&guard).map(|(r, _)| r).eq(ranges.iter()));
+        for k in 0..1100 {
+            assert!(rangemap
+                .stab(&k, &guard)
+                .map(|(r, _)| r)
+                .eq(ranges.iter().filter(|r| r.contains(&k))));
+            assert_eq!(rangemap.contains(&k), ranges.iter().any(|r| r.contains(&k)));
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_remove_stab() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 256;
+        let rangemap: Arc<RangeMap<usize, usize>> = Arc::new(RangeMap::default());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for task_id in 0..NUM_THREADS {
+            let rangemap_clone = rangemap.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let keys = (0..workload_size).map(|k| (k * NUM_THREADS + task_id) * 4);
+                for k in keys.clone() {
+                    assert!(rangemap_clone.insert(k..k + 4, k).is_ok());
+                    assert!(rangemap_clone.insert(k..k + 8, k).is_ok());
+                    let guard = Guard::new();
+                    assert!(rangemap_clone
+                        .stab(&(k + 1), &guard)
+                        .any(|(r, _)| *r == (k..k + 4)));
+                }
+                for k in keys.clone() {
+                    assert!(rangemap_clone.remove_if(&(k..k + 8), |v| *v == k));
+                }
+                for k in keys {
+                    let guard = Guard::new();
+                    assert_eq!(rangemap_clone.peek(&(k..k + 4), &guard), Some(&k));
+                    assert!(rangemap_clone.peek(&(k..k + 8), &guard).is_none());
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        assert_eq!(rangemap.len(), workload_size * NUM_THREADS);
+        let guard = Guard::new();
+        for k in 0..workload_size * NUM_THREADS {
+            assert!(rangemap
+                .stab(&(k * 4 + 3), &guard)
+                .map(|(r, _)| r.clone())
+                .eq([k * 4..k * 4 + 4]));
+        }
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod radixtree_test {
+    use super::common::R;
+    use crate::ebr::Guard;
+    use crate::radix_tree::Iter;
+    use crate::RadixTree;
+    use std::collections::BTreeMap;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(RadixTree<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(Iter<'static, String>: UnwindSafe);
+    static_assertions::assert_not_impl_all!(RadixTree<*const String>: Send, Sync);
+    static_assertions::assert_not_impl_all!(Iter<'static, *const String>: Send, Sync);
+
+    #[test]
+    fn insert_drop() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let radixtree: RadixTree<R> = RadixTree::default();
+
+        let workload_size = 256;
+        for k in 0..workload_size {
+            assert!(radixtree
+                .insert(&format!("/api/v1/users/{k}"), R::new(&INST_CNT))
+                .is_ok());
+        }
+        assert!(radixtree
+            .insert("/api/v1/users/0", R::new(&INST_CNT))
+            .is_err());
+        assert!(radixtree.insert("", R::new(&INST_CNT)).is_ok());
+        assert_eq!(INST_CNT.load(Relaxed), workload_size + 1);
+        assert_eq!(radixtree.len(), workload_size + 1);
+        drop(radixtree);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn insert_remove() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let radixtree: RadixTree<R> = RadixTree::default();
+
+        // Single-byte keys make the root node switch to the dense layout.
+        let workload_size = 256;
+        for k in (0..workload_size).rev() {
+            assert!(radixtree.insert(&[k as u8], R::new(&INST_CNT)).is_ok());
+        }
+        assert!(radixtree
+            .iter(&Guard::new())
+            .map(|(k, _)| usize::from(k[0]))
+            .eq(0..workload_size));
+        for k in (0..workload_size).filter(|k| k % 2 == 0) {
+            assert!(radixtree.remove(&[k as u8]));
+            assert!(!radixtree.remove(&[k as u8]));
+        }
+        assert_eq!(radixtree.len(), workload_size / 2);
+        assert!(radixtree
+            .iter(&Guard::new())
+            .map(|(k, _)| usize::from(k[0]))
+            .eq((0..workload_size).filter(|k| k % 2 == 1)));
+        radixtree.clear();
+        assert!(radixtree.is_empty());
+        assert!(radixtree.iter(&Guard::new()).next().is_none());
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[test]
+    fn btreemap() {
+        let radixtree: RadixTree<usize> = RadixTree::default();
+        let mut btreemap: BTreeMap<Vec<u8>, usize> = BTreeMap::default();
+
+        // Keys are drawn from a small alphabet in order for them to share prefixes.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for i in 0..4096 {
+            let len = (random() % 8) as usize;
+            let key: Vec<u8> = (0..len).map(|_| b"abc"[(random() % 3) as usize]).collect();
+            if random() % 3 == 0 {
+                assert_eq!(
+                    radixtree.remove_if(&key, |v| v % 2 == 0),
+                    btreemap.get(&key).map_or(false, |v| v % 2 == 0)
+                );
+                if btreemap.get(&key).map_or(false, |v| v % 2 == 0) {
+                    btreemap.remove(&key);
+                }
+            } else {
+                assert_eq!(
+                    radixtree.insert(&key, i).is_ok(),
+                    !btreemap.contains_key(&key)
+                );
+                btreemap.entry(key).or_insert(i);
+            }
+            assert_eq!(radixtree.len(), btreemap.len());
+        }
+
+        let guard = Guard::new();
+        assert!(radixtree
+            .iter(&guard)
+            .eq(btreemap.iter().map(|(k, v)| (k.clone(), v))));
+        for (k, v) in &btreemap {
+            assert_eq!(radixtree.peek(k, &guard), Some(v));
+        }
+    }
+
+    #[test]
+    fn prefix() {
+        let radixtree: RadixTree<usize> = RadixTree::default();
+        let mut btreemap: BTreeMap<Vec<u8>, usize> = BTreeMap::default();
+
+        // Every string over `ab` up to the length of `4`.
+        let strings: Vec<Vec<u8>> = (0..=4)
+            .flat_map(|len| {
+                (0..1_usize << len)
+                    .map(move |bits| (0..len).map(|i| b"ab"[(bits >> i) & 1]).collect())
+            })
+            .collect();
+        for (i, key) in strings.iter().enumerate().filter(|(i, _)| i % 3 != 1) {
+            assert!(radixtree.insert(key, i).is_ok());
+            btreemap.insert(key.clone(), i);
+        }
+
+        let guard = Guard::new();
+        for prefix in &strings {
+            assert!(radixtree.iter_prefix(prefix, &guard).eq(btreemap
+                .iter()
+                .filter(|(k, _)| k.starts_with(prefix))
+                .map(|(k, v)| (k.clone(), v))));
+            let longest = (0..=prefix.len())
+                .rev()
+                .find_map(|len| btreemap.get_key_value(&prefix[..len]));
+            assert_eq!(
+                radixtree.longest_prefix(prefix, &guard),
+                longest.map(|(k, v)| (k.as_slice(), v))
+            );
+        }
+        assert_eq!(radixtree.iter_prefix("c", &guard).count(), 0);
+        assert_eq!(radixtree.iter_prefix("", &guard).count(), radixtree.len());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_race() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        const NUM_THREADS: usize = 8;
+        let workload_size = 1024;
+        let radixtree: Arc<RadixTree<R>> = Arc::new(RadixTree::default());
+        let num_inserted = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for _ in 0..NUM_THREADS {
+            let radixtree_clone = radixtree.clone();
+            let num_inserted_clone = num_inserted.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for k in 0..workload_size {
+                    let key = format!("https://example.com/{k}");
+                    if radixtree_clone.insert(&key, R::new(&INST_CNT)).is_ok() {
+                        num_inserted_clone.fetch_add(1, Relaxed);
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(num_inserted.load(Relaxed), workload_size);
+        assert_eq!(radixtree.len(), workload_size);
+        assert_eq!(radixtree.iter(&Guard::new()).count(), workload_size);
+        drop(radixtree);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_remove_churn() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        const NUM_THREADS: usize = 8;
+        let workload_size = 64;
+        let radixtree: Arc<RadixTree<R>> = Arc::new(RadixTree::default());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for _ in 0..NUM_THREADS {
+            let radixtree_clone = radixtree.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for _ in 0..64 {
+                    for k in 0..workload_size {
+                        let _result = radixtree_clone.insert(&format!("{k}"), R::new(&INST_CNT));
+                    }
+                    for k in 0..workload_size {
+                        radixtree_clone.remove(&format!("{k}"));
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert!(radixtree.is_empty());
+        assert!(radixtree.iter(&Guard::new()).next().is_none());
+        for k in 0..workload_size {
+            assert!(radixtree.insert(&format!("{k}"), R::new(&INST_CNT)).is_ok());
+        }
+        assert_eq!(radixtree.len(), workload_size);
+        drop(radixtree);
+
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_remove_scan() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 4096;
+        let radixtree: Arc<RadixTree<u64>> = Arc::new(RadixTree::default());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS + 1));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS + 1);
+        for task_id in 0..NUM_THREADS as u64 {
+            let radixtree_clone = radixtree.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                // Big-endian keys are ordered the same way as the integers.
+                let keys = (0..workload_size).map(|k| k * NUM_THREADS as u64 + task_id);
+                for k in keys.clone() {
+                    assert!(radixtree_clone.insert(&k.to_be_bytes(), k).is_ok());
+                    assert_eq!(
+                        radixtree_clone.peek_with(&k.to_be_bytes(), |_, v| *v),
+                        Some(k)
+                    );
+                }
+                for k in keys.clone().filter(|k| k % 3 == 0) {
+                    assert!(radixtree_clone.remove_if(&k.to_be_bytes(), |v| *v == k));
+                    assert!(!radixtree_clone.contains(&k.to_be_bytes()));
+                }
+                for k in keys {
+                    assert_eq!(radixtree_clone.contains(&k.to_be_bytes()), k % 3 != 0);
+                }
+            }));
+        }
+        let radixtree_clone = radixtree.clone();
+        let barrier_clone = barrier.clone();
+        thread_handles.push(thread::spawn(move || {
+            barrier_clone.wait();
+            for _ in 0..16 {
+                let guard = Guard::new();
+                let mut last = None;
+                for (k, v) in radixtree_clone.iter(&guard) {
+                    assert_eq!(k, v.to_be_bytes());
+                    assert!(last.map_or(true, |last| last < *v));
+                    last.replace(*v);
+                }
+            }
+        }));
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let expected = (0..workload_size * NUM_THREADS as u64).filter(|k| k % 3 != 0);
+        assert_eq!(radixtree.len(), expected.clone().count());
+        assert!(radixtree.iter(&Guard::new()).map(|(_, v)| *v).eq(expected));
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod linkedlist_test {
+    use crate::ebr::{AtomicShared, Guard, Shared};
+    use crate::{CountedHead, LinkedCursor, LinkedList};
+    use std::sync::atomic::Ordering::{Relaxed, Release};
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    #[derive(Debug, Default)]
+    struct L(AtomicShared<L>, usize);
+    impl LinkedList for L {
+        fn link_ref(&self) -> &AtomicShared<L> {
+            &self.0
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn cursor() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+
+        let head: Arc<L> = Arc::new(L::default());
+        let guard = Guard::new();
+        for v in (0..workload_size).rev() {
+            assert!(head
+                .push_back(
+                    Shared::new(L(AtomicShared::null(), v)),
+                    false,
+                    Release,
+                    &guard
+                )
+                .is_ok());
+        }
+        drop(guard);
+
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                let mut inserted = Vec::new();
+                let mut cursor = LinkedCursor::new(&*head_clone, &guard);
+                while let Some(entry) = cursor.move_next() {
+                    if entry.1 >= workload_size {
+                        continue;
+                    } else if entry.1 % NUM_TASKS == task_id {
+                        assert!(cursor.remove_current());
+                    } else if entry.1 % NUM_TASKS == (task_id + 1) % NUM_TASKS {
+                        // The entry may be removed by another thread in the meantime.
+                        let new_entry =
+                            Shared::new(L(AtomicShared::null(), workload_size + entry.1));
+                        if let Err(new_entry) = cursor.insert_after(new_entry) {
+                            assert!(head_clone
+                                .push_back(new_entry, false, Release, &guard)
+                                .is_ok());
+                        }
+                        inserted.push(workload_size + entry.1);
+                    }
+                }
+                inserted
+            }));
+        }
+        let mut inserted = Vec::new();
+        for t in thread_handles {
+            inserted.append(&mut t.join().unwrap());
+        }
+        inserted.sort_unstable();
+
+        let guard = Guard::new();
+        let mut cursor = LinkedCursor::new(&*head, &guard);
+        let mut remaining = Vec::new();
+        while let Some(entry) = cursor.move_next() {
+            remaining.push(entry.1);
+        }
+        remaining.sort_unstable();
+        assert!(!inserted.is_empty());
+        assert_eq!(remaining, inserted);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn insert_sorted() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 256;
+
+        let head: Arc<L> = Arc::new(L::default());
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                for k in 0..workload_size {
+                    let k = if task_id % 2 == 0 {
+                        k
+                    } else {
+                        workload_size - 1 - k
+                    };
+                    let entry = Shared::new(L(AtomicShared::null(), k * NUM_TASKS + task_id));
+                    let inserted = head_clone
+                        .insert_sorted_list(entry, |a, b| a.1.cmp(&b.1), Release, &guard)
+                        .unwrap();
+                    if k % 3 == 0 {
+                        assert!(inserted.as_ref().unwrap().delete_self(Release));
+                    }
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let guard = Guard::new();
+        let mut cursor = LinkedCursor::new(&*head, &guard);
+        let mut remaining = Vec::new();
+        while let Some(entry) = cursor.move_next() {
+            remaining.push(entry.1);
+        }
+        assert!(remaining
+            .into_iter()
+            .eq((0..workload_size * NUM_TASKS).filter(|v| (v / NUM_TASKS) % 3 != 0)));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn remove_if() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+
+        let head: Arc<L> = Arc::new(L::default());
+        let guard = Guard::new();
+        for v in 0..workload_size {
+            assert!(head
+                .push_back(
+                    Shared::new(L(AtomicShared::null(), v)),
+                    false,
+                    Release,
+                    &guard
+                )
+                .is_ok());
+        }
+        drop(guard);
+
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                let removed_even = head_clone.remove_if_list(|e| e.1 % 2 == 0, Release, &guard);
+                let removed_own =
+                    head_clone.remove_if_list(|e| e.1 % 16 == task_id * 2 + 1, Release, &guard);
+                assert_eq!(removed_own, workload_size / 16);
+                removed_even
+            }));
+        }
+        let mut removed = 0;
+        for t in thread_handles {
+            removed += t.join().unwrap();
+        }
+        assert_eq!(removed, workload_size / 2);
+
+        let guard = Guard::new();
+        let mut cursor = LinkedCursor::new(&*head, &guard);
+        let mut remaining = Vec::new();
+        while let Some(entry) = cursor.move_next() {
+            remaining.push(entry.1);
+        }
+        assert_eq!(remaining.len(), workload_size / 4);
+        assert!(remaining.into_iter().eq((0..workload_size)
+            .rev()
+            .filter(|v| v % 2 == 1 && v % 16 >= NUM_TASKS * 2)));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn counted_head() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 1024;
+
+        let head: Arc<CountedHead<L>> = Arc::new(CountedHead::default());
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                for seq in 0..workload_size {
+                    let entry = Shared::new(L(AtomicShared::null(), seq * NUM_TASKS + task_id));
+                    if seq % 2 == 0 {
+                        assert!(head_clone
+                            .push_back(entry.clone(), false, Release, &guard)
+                            .is_ok());
+                        if seq % 4 == 0 {
+                            assert!(head_clone.delete(&entry, Release));
+                        }
+                    } else {
+                        assert!(head_clone
+                            .insert_sorted(entry, |a, b| a.1.cmp(&b.1), Release, &guard)
+                            .is_ok());
+                    }
+                }
+                head_clone.remove_if(
+                    |e| e.1 % NUM_TASKS == task_id && e.1 % 3 == 0,
+                    Release,
+                    &guard,
+                );
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let guard = Guard::new();
+        let num_entries = head.iter(&guard).count();
+        assert_eq!(head.len(), num_entries);
+        assert_eq!(head.remove_if(|_| true, Release, &guard), num_entries);
+        assert!(head.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn append() {
+        const NUM_TASKS: usize = 4;
+        const CHAIN_LEN: usize = 16;
+        let workload_size = 256;
+
+        let head: Arc<L> = Arc::new(L::default());
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                for seq in 0..workload_size {
+                    let base = (seq * NUM_TASKS + task_id) * CHAIN_LEN;
+                    let last = Shared::new(L(AtomicShared::null(), base + CHAIN_LEN - 1));
+                    let mut first = last.clone();
+                    for v in (base..base + CHAIN_LEN - 1).rev() {
+                        first = Shared::new(L(AtomicShared::from(first), v));
+                    }
+                    assert!(head_clone.append_list(first, Release, &guard).is_ok());
+
+                    // Delete the entry at the join point for the next `append`.
+                    assert!(last.delete_self(Release));
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let guard = Guard::new();
+        let mut cursor = LinkedCursor::new(&*head, &guard);
+        let mut remaining = Vec::new();
+        while let Some(entry) = cursor.move_next() {
+            remaining.push(entry.1);
+        }
+        for chain in remaining.chunks(CHAIN_LEN - 1) {
+            assert!(chain.windows(2).all(|w| w[0] + 1 == w[1]));
+        }
+        remaining.sort_unstable();
+        assert!(remaining
+            .into_iter()
+            .eq((0..workload_size * NUM_TASKS * CHAIN_LEN)
+                .filter(|v| v % CHAIN_LEN != CHAIN_LEN - 1)));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn iter() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+
+        let head: Arc<L> = Arc::new(L::default());
+        let guard = Guard::new();
+        for v in (0..workload_size).rev() {
+            assert!(head
+                .push_back(
+                    Shared::new(L(AtomicShared::null(), v)),
+                    false,
+                    Release,
+                    &guard
+                )
+                .is_ok());
+        }
+        drop(guard);
+
+        let barrier = Arc::new(Barrier::new(NUM_TASKS * 2));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS * 2);
+        for task_id in 0..NUM_TASKS {
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                for entry in head_clone.iter_list(&guard) {
+                    if entry.1 % (NUM_TASKS * 2) == task_id * 2 {
+                        assert!(entry.delete_self(Release));
+                    }
+                }
+            }));
+            let head_clone = head.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let guard = Guard::new();
+                let mut num_odd = 0;
+                let mut last = None;
+                for entry in head_clone.iter_list(&guard) {
+                    assert!(last.map_or(true, |last| last < entry.1));
+                    last.replace(entry.1);
+                    if entry.1 % 2 == 1 {
+                        num_odd += 1;
+                    }
+                }
+                assert_eq!(num_odd, workload_size / 2);
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
         }
-        assert_eq!(tree.len(), 0);
-        assert_eq!(tree.depth(), 0);
+
+        let guard = Guard::new();
+        assert!(head
+            .iter_list(&guard)
+            .map(|e| e.1)
+            .eq((0..workload_size).filter(|v| v % 2 == 1)));
     }
 
     #[test]
-    fn string_key() {
-        let tree1: TreeIndex<String, u32> = TreeIndex::default();
-        let tree2: TreeIndex<u32, String> = TreeIndex::default();
-        let mut checker1 = BTreeSet::new();
-        let mut checker2 = BTreeSet::new();
-        let mut runner = TestRunner::default();
-        let test_size = if cfg!(miri) { 16 } else { 1024 };
-        for i in 0..test_size {
-            let prop_str = "[a-z]{1,16}".new_tree(&mut runner).unwrap();
-            let str_val = prop_str.current();
-            if tree1.insert(str_val.clone(), i).is_ok() {
-                checker1.insert((str_val.clone(), i));
-            }
-            let str_borrowed = str_val.as_str();
-            assert!(tree1.peek_with(str_borrowed, |_, _| ()).is_some());
+    fn reset_link() {
+        let workload_size = 16;
+        let head_a = L::default();
+        let head_b = L::default();
+        let mut entries: Vec<Shared<L>> = (0..workload_size)
+            .map(|v| Shared::new(L(AtomicShared::null(), v)))
+            .collect();
 
-            if tree2.insert(i, str_val.clone()).is_ok() {
-                checker2.insert((i, str_val.clone()));
-            }
+        let guard = Guard::new();
+        for entry in entries.iter().rev() {
+            assert!(head_a
+                .push_back(entry.clone(), false, Release, &guard)
+                .is_ok());
         }
-        for iter in &checker1 {
-            let v = tree1.peek_with(iter.0.as_str(), |_, v| *v);
-            assert_eq!(v.unwrap(), iter.1);
+        for entry in &entries {
+            assert!(entry.delete_self(Release));
+            assert!(!entry.delete_self(Release));
         }
-        for iter in &checker2 {
-            let v = tree2.peek_with(&iter.0, |_, v| v.clone());
-            assert_eq!(v.unwrap(), iter.1);
+        assert_eq!(head_a.iter_list(&guard).count(), 0);
+        drop(guard);
+
+        for entry in &mut entries {
+            let entry_mut = unsafe { entry.get_mut().unwrap() };
+            entry_mut.reset_link();
+            assert!(!entry.is_deleted(Relaxed));
         }
+
+        let guard = Guard::new();
+        for entry in &entries {
+            assert!(head_b
+                .push_back(entry.clone(), false, Release, &guard)
+                .is_ok());
+        }
+        assert!(head_b
+            .iter_list(&guard)
+            .map(|e| e.1)
+            .eq((0..workload_size).rev()));
+        assert_eq!(head_a.iter_list(&guard).count(), 0);
     }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod bitset_test {
+    use crate::bit_set::Iter;
+    use crate::ebr::Guard;
+    use crate::BitSet;
+    use std::collections::BTreeSet;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(BitSet: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(Iter<'static, 'static>: UnwindSafe);
 
     #[test]
-    fn scanner() {
-        let data_size = if cfg!(miri) { 128 } else { 4096 };
-        let iteration = if cfg!(miri) { 4 } else { 64 };
-        for _ in 0..iteration {
-            let tree: Arc<TreeIndex<usize, u64>> = Arc::new(TreeIndex::default());
-            let barrier = Arc::new(Barrier::new(3));
-            let inserted = Arc::new(AtomicUsize::new(0));
-            let removed = Arc::new(AtomicUsize::new(data_size));
-            let mut thread_handles = Vec::new();
-            for _ in 0..2 {
-                let tree_clone = tree.clone();
-                let barrier_clone = barrier.clone();
-                let inserted_clone = inserted.clone();
-                let removed_clone = removed.clone();
-                let thread_handle = thread::spawn(move || {
-                    // test insert
-                    for _ in 0..2 {
-                        barrier_clone.wait();
-                        let max = inserted_clone.load(Acquire);
-                        let mut prev = 0;
-                        let mut iterated = 0;
-                        let guard = Guard::new();
-                        for iter in tree_clone.iter(&guard) {
-                            assert!(
-                                prev == 0
-                                    || (*iter.0 <= max && prev + 1 == *iter.0)
-                                    || *iter.0 > prev
-                            );
-                            prev = *iter.0;
-                            iterated += 1;
-                        }
-                        assert!(iterated >= max);
-                    }
-                    // test remove
-                    for _ in 0..2 {
-                        barrier_clone.wait();
-                        let mut prev = 0;
-                        let max = removed_clone.load(Acquire);
-                        let guard = Guard::new();
-                        for iter in tree_clone.iter(&guard) {
-                            let current = *iter.0;
-                            assert!(current < max);
-                            assert!(prev + 1 == current || prev == 0);
-                            prev = current;
-                        }
-                    }
-                });
-                thread_handles.push(thread_handle);
-            }
-            // insert
-            barrier.wait();
-            for i in 0..data_size {
-                if i == data_size / 2 {
-                    barrier.wait();
-                }
-                assert!(tree.insert(i, 0).is_ok());
-                inserted.store(i, Release);
-            }
-            // remove
-            barrier.wait();
-            for i in (0..data_size).rev() {
-                if i == data_size / 2 {
-                    barrier.wait();
-                }
-                assert!(tree.remove(&i));
-                removed.store(i, Release);
+    fn set_reset() {
+        let bitset = BitSet::default();
+        let mut btreeset: BTreeSet<usize> = BTreeSet::default();
+
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut random = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+        for _ in 0..4096 {
+            let index = random() % 4000;
+            if random() % 3 == 0 {
+                assert_eq!(bitset.reset(index), btreeset.remove(&index));
+            } else {
+                assert_eq!(bitset.set(index), !btreeset.insert(index));
             }
-            thread_handles.into_iter().for_each(|t| t.join().unwrap());
+            assert_eq!(bitset.count_ones(), btreeset.len());
         }
+
+        let guard = Guard::new();
+        assert!(bitset.iter(&guard).eq(btreeset.iter().copied()));
+        for index in 0..4100 {
+            assert_eq!(bitset.test(index), btreeset.contains(&index));
+            assert_eq!(bitset.rank(index), btreeset.range(..index).count());
+        }
+        assert_eq!(bitset.rank(usize::MAX), btreeset.len());
+        assert!(!bitset.test(usize::MAX));
+        assert!(!bitset.reset(usize::MAX));
+
+        let bitset_clone = bitset.clone();
+        assert_eq!(bitset_clone, bitset);
+        bitset.clear();
+        assert_eq!(bitset.count_ones(), 0);
+        assert!(bitset.iter(&guard).next().is_none());
+        assert_ne!(bitset_clone, bitset);
     }
 
     #[test]
-    fn range() {
-        let tree: TreeIndex<String, usize> = TreeIndex::default();
-        assert!(tree.insert("Ape".to_owned(), 0).is_ok());
-        assert!(tree.insert("Apple".to_owned(), 1).is_ok());
-        assert!(tree.insert("Banana".to_owned(), 3).is_ok());
-        assert!(tree.insert("Badezimmer".to_owned(), 2).is_ok());
-        assert_eq!(tree.range(..="Ball".to_owned(), &Guard::new()).count(), 3);
-        assert_eq!(
-            tree.range("Ape".to_owned()..="Ball".to_owned(), &Guard::new())
-                .count(),
-            3
-        );
-        assert_eq!(
-            tree.range("Apex".to_owned()..="Ball".to_owned(), &Guard::new())
-                .count(),
-            2
-        );
-        assert_eq!(
-            tree.range("Ace".to_owned()..="Ball".to_owned(), &Guard::new())
-                .count(),
-            3
-        );
-        assert_eq!(tree.range(..="Z".to_owned(), &Guard::new()).count(), 4);
-        assert_eq!(
-            tree.range("Ape".to_owned()..="Z".to_owned(), &Guard::new())
-                .count(),
-            4
-        );
-        assert_eq!(
-            tree.range("Apex".to_owned()..="Z".to_owned(), &Guard::new())
-                .count(),
-            3
-        );
-        assert_eq!(
-            tree.range("Ace".to_owned()..="Z".to_owned(), &Guard::new())
-                .count(),
-            4
-        );
-        assert_eq!(tree.range(.."Banana".to_owned(), &Guard::new()).count(), 3);
-        assert_eq!(
-            tree.range("Ape".to_owned().."Banana".to_owned(), &Guard::new())
-                .count(),
-            3
-        );
-        assert_eq!(
-            tree.range("Apex".to_owned().."Banana".to_owned(), &Guard::new())
-                .count(),
-            2
-        );
-        assert_eq!(
-            tree.range("Ace".to_owned().."Banana".to_owned(), &Guard::new())
-                .count(),
-            3
-        );
+    fn capacity() {
+        let bitset = BitSet::new();
+        assert_eq!(bitset.capacity(), 0);
+        for index in [0, 511, 512, 1535, 1536, 1_000_000] {
+            assert!(!bitset.set(index));
+            assert!(bitset.capacity() > index);
+        }
+        assert_eq!(bitset.iter(&Guard::new()).count(), 6);
+
+        for capacity in [1, 512, 513, 100_000] {
+            let bitset = BitSet::with_capacity(capacity);
+            assert!(bitset.capacity() >= capacity);
+            assert!(bitset.capacity() < capacity * 4 + 512);
+        }
     }
 
-    proptest! {
-        #[cfg_attr(miri, ignore)]
-        #[test]
-        fn prop_remove_range(lower in 0_usize..4096_usize, range in 0_usize..4096_usize) {
-            let remove_range = lower..lower + range;
-            let insert_range = (256_usize, 4095_usize);
-            let tree = TreeIndex::default();
-            for k in insert_range.0..=insert_range.1 {
-                prop_assert!(tree.insert(k, k).is_ok());
-            }
-            if usize::BITS == 32 {
-                prop_assert_eq!(tree.depth(), 4);
-            } else {
-                prop_assert_eq!(tree.depth(), 3);
-            }
-            tree.remove_range(remove_range.clone());
-            if remove_range.contains(&insert_range.0) && remove_range.contains(&insert_range.1) {
-                prop_assert!(tree.is_empty());
-            }
-            for (k, v) in tree.iter(&Guard::new()) {
-                prop_assert_eq!(k, v);
-                prop_assert!(!remove_range.contains(k), "{k}");
-            }
-            for k in 0_usize..4096_usize {
-                if tree.peek_with(&k, |_, _|()).is_some() {
-                    prop_assert!(!remove_range.contains(&k), "{k}");
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn set_race() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 1 << 16;
+        let bitset = Arc::new(BitSet::new());
+        let num_set = Arc::new(AtomicUsize::new(0));
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for _ in 0..NUM_THREADS {
+            let bitset_clone = bitset.clone();
+            let num_set_clone = num_set.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for index in 0..workload_size {
+                    if !bitset_clone.set(index) {
+                        num_set_clone.fetch_add(1, Relaxed);
+                    }
                 }
-            }
-            for k in remove_range.clone() {
-                prop_assert!(tree.insert(k, k).is_ok());
-            }
-            let mut cnt = 0;
-            for (k, v) in tree.iter(&Guard::new()) {
-                prop_assert_eq!(k, v);
-                if remove_range.contains(k) {
-                    cnt += 1;
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(num_set.load(Relaxed), workload_size);
+        assert_eq!(bitset.count_ones(), workload_size);
+        assert!(bitset.iter(&Guard::new()).eq(0..workload_size));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn set_reset_grow() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 1 << 14;
+        let bitset = Arc::new(BitSet::new());
+        let barrier = Arc::new(Barrier::new(NUM_THREADS + 1));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS + 1);
+        for task_id in 0..NUM_THREADS {
+            let bitset_clone = bitset.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let indexes = (0..workload_size).map(|i| i * NUM_THREADS + task_id);
+                for index in indexes.clone() {
+                    assert!(!bitset_clone.set(index));
+                    assert!(bitset_clone.test(index));
+                }
+                for index in indexes.clone().filter(|i| i % 3 == 0) {
+                    assert!(bitset_clone.reset(index));
+                }
+                for index in indexes {
+                    assert_eq!(bitset_clone.test(index), index % 3 != 0);
+                }
+            }));
+        }
+        let bitset_clone = bitset.clone();
+        let barrier_clone = barrier.clone();
+        thread_handles.push(thread::spawn(move || {
+            barrier_clone.wait();
+            for _ in 0..16 {
+                let guard = Guard::new();
+                let mut last = None;
+                for index in bitset_clone.iter(&guard) {
+                    assert!(last.map_or(true, |last| last < index));
+                    last.replace(index);
                 }
             }
-            assert_eq!(cnt, range);
+        }));
+        for t in thread_handles {
+            assert!(t.join().is_ok());
         }
+
+        let expected = (0..workload_size * NUM_THREADS).filter(|i| i % 3 != 0);
+        assert_eq!(bitset.count_ones(), expected.clone().count());
+        assert!(bitset.iter(&Guard::new()).eq(expected));
     }
 }
 
@@ -2914,7 +5546,55 @@ mod bag_test {
             assert!(bag7.pop().is_none());
             assert!(bag7.is_empty());
         }
-        assert_eq!(INST_CNT.load(Relaxed), 0);
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn scan_pop_many() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 1024;
+        let bag: Arc<Bag<usize, 7>> = Arc::new(Bag::new());
+        let num_pushed = Arc::new(AtomicUsize::new(0));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS * 2);
+        for task_id in 0..NUM_TASKS {
+            let bag_clone = bag.clone();
+            let num_pushed_clone = num_pushed.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                for seq in 0..workload_size {
+                    bag_clone.push(task_id * workload_size + seq);
+                    num_pushed_clone.fetch_add(1, Relaxed);
+                }
+                Vec::new()
+            }));
+            let bag_clone = bag.clone();
+            let num_pushed_clone = num_pushed.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                let mut popped = Vec::new();
+                while num_pushed_clone.load(Relaxed) < NUM_TASKS * workload_size {
+                    let mut num_scanned = 0;
+                    bag_clone.scan(|v| {
+                        assert!(*v < NUM_TASKS * workload_size);
+                        num_scanned += 1;
+                    });
+                    assert!(num_scanned <= NUM_TASKS * workload_size);
+                    if task_id % 2 == 0 {
+                        popped.append(&mut bag_clone.pop_many(7));
+                    } else {
+                        bag_clone.pop_all((), |(), v| popped.push(v));
+                    }
+                }
+                popped
+            }));
+        }
+        let mut popped = Vec::new();
+        for t in thread_handles {
+            popped.append(&mut t.join().unwrap());
+        }
+        popped.append(&mut bag.pop_many(usize::MAX));
+        popped.sort_unstable();
+        assert!(popped.into_iter().eq(0..NUM_TASKS * workload_size));
+        assert!(bag.is_empty());
     }
 }
 
@@ -2922,15 +5602,19 @@ mod bag_test {
 #[cfg(test)]
 mod queue_test {
     use crate::ebr::Guard;
+    use crate::queue::BoundedQueue;
     use crate::Queue;
     use std::panic::UnwindSafe;
     use std::sync::atomic::AtomicUsize;
     use std::sync::atomic::Ordering::Relaxed;
     use std::sync::{Arc, Barrier};
     use std::thread;
+    use std::time::Duration;
 
     static_assertions::assert_impl_all!(Queue<String>: Send, Sync, UnwindSafe);
     static_assertions::assert_not_impl_all!(Queue<*const String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(BoundedQueue<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_not_impl_all!(BoundedQueue<*const String>: Send, Sync, UnwindSafe);
 
     struct R(&'static AtomicUsize, usize, usize);
     impl R {
@@ -2965,6 +5649,472 @@ mod queue_test {
         assert!(queue_clone.pop().is_none());
     }
 
+    #[test]
+    fn from_iter_extend() {
+        let mut queue: Queue<usize> = (0..4).collect();
+        assert_eq!(queue.len(), 4);
+        queue.extend(4..8);
+        queue.extend(std::iter::empty());
+        assert_eq!(queue.len(), 8);
+        assert!(queue.iter(&Guard::new()).copied().eq(0..8));
+        assert_eq!(queue.pop().map(|e| **e), Some(0));
+    }
+
+    #[test]
+    fn into_iter() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let queue: Queue<R> = Queue::default();
+        for seq in 0..16 {
+            queue.push(R::new(&INST_CNT, 0, seq));
+        }
+        let mut expected = 0;
+        for mut entry in queue {
+            let r = unsafe { entry.get_mut().unwrap().take_inner() };
+            assert_eq!(r.2, expected);
+            expected += 1;
+        }
+        assert_eq!(expected, 16);
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+
+        let queue: Queue<usize> = (0..16).collect();
+        assert_eq!(queue.pop().map(|e| **e), Some(0));
+        assert!(queue.into_iter().map(|e| **e).eq(1..16));
+    }
+
+    #[test]
+    fn remove_if() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let queue: Queue<R> = Queue::default();
+        for seq in 0..16 {
+            queue.push(R::new(&INST_CNT, 0, seq));
+        }
+        assert_eq!(queue.remove_if(|r| r.2 % 3 != 1), 11);
+        assert_eq!(queue.remove_if(|r| r.2 % 3 != 1), 0);
+        assert_eq!(queue.len(), 5);
+        assert!(queue.iter(&Guard::new()).map(|r| r.2).eq([1, 4, 7, 10, 13]));
+        queue.push(R::new(&INST_CNT, 0, 16));
+        assert_eq!(queue.remove_if(|r| r.2 >= 13), 2);
+        queue.push(R::new(&INST_CNT, 0, 17));
+        assert!(queue.iter(&Guard::new()).map(|r| r.2).eq([1, 4, 7, 10, 17]));
+        assert_eq!(queue.remove_if(|_| true), 5);
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        queue.push(R::new(&INST_CNT, 0, 18));
+        assert_eq!(queue.pop().map(|r| r.2), Some(18));
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+
+        let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+        for seq in 0..4 {
+            assert!(queue.push(seq).is_ok());
+        }
+        assert!(queue.push(4).is_err());
+        assert_eq!(queue.remove_if(|v| *v < 2), 2);
+        assert_eq!(queue.len(), 2);
+        assert!(queue.push(4).is_ok());
+        assert!(queue.push(5).is_ok());
+        assert!(queue.push(6).is_err());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn remove_if_concurrent() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+
+        let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+        let num_removed: Arc<AtomicUsize> = Arc::new(AtomicUsize::default());
+        let barrier = Arc::new(Barrier::new(NUM_TASKS * 2));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS * 2);
+        for task_id in 0..NUM_TASKS {
+            let queue_clone = queue.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let mut popped = Vec::new();
+                for seq in 0..workload_size {
+                    queue_clone.push(task_id * workload_size + seq);
+                    if seq % 4 == 0 {
+                        if let Some(entry) = queue_clone.pop() {
+                            popped.push(**entry);
+                        }
+                    }
+                }
+                popped
+            }));
+            let queue_clone = queue.clone();
+            let num_removed_clone = num_removed.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let mut removed = Vec::new();
+                for _ in 0..workload_size / 64 {
+                    let mut removed_now = Vec::new();
+                    let num_removed = queue_clone.remove_if(|v| {
+                        if *v % NUM_TASKS == task_id && *v % 3 == 0 {
+                            removed_now.push(*v);
+                            true
+                        } else {
+                            false
+                        }
+                    });
+                    assert!(num_removed <= removed_now.len());
+                    num_removed_clone.fetch_add(num_removed, Relaxed);
+                    removed.append(&mut removed_now);
+                    thread::yield_now();
+                }
+                removed
+            }));
+        }
+        let mut popped_or_removed = Vec::with_capacity(NUM_TASKS * workload_size);
+        for t in thread_handles {
+            popped_or_removed.append(&mut t.join().unwrap());
+        }
+        popped_or_removed.sort_unstable();
+        popped_or_removed.dedup();
+        let num_popped_or_removed = popped_or_removed.len();
+        while let Some(entry) = queue.pop() {
+            popped_or_removed.push(**entry);
+        }
+        popped_or_removed.sort_unstable();
+        assert!(popped_or_removed
+            .into_iter()
+            .eq(0..NUM_TASKS * workload_size));
+        assert!(num_removed.load(Relaxed) <= num_popped_or_removed);
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn len() {
+        const NUM_TASKS: usize = 4;
+        let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+        let workload_size = 1024;
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let queue_clone = queue.clone();
+            thread_handles.push(thread::spawn(move || {
+                for seq in 0..workload_size {
+                    queue_clone.push(seq);
+                    if task_id % 2 == 0 && seq % 2 == 0 {
+                        assert!(queue_clone.pop().is_some());
+                    }
+                    assert!(queue_clone.len() <= NUM_TASKS * workload_size);
+                    assert!(queue_clone.push_if(seq, |_| false).is_err());
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        let expected = NUM_TASKS * workload_size - (NUM_TASKS / 2) * (workload_size / 2);
+        assert_eq!(queue.len(), expected);
+        assert_eq!(queue.iter(&Guard::new()).count(), expected);
+        assert_eq!(queue.clone().len(), expected);
+        while queue.pop().is_some() {}
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn scan() {
+        let queue: Queue<usize> = Queue::default();
+        for i in 0..16 {
+            queue.push(i);
+        }
+        assert!(queue.pop().is_some());
+
+        let mut scanned = Vec::new();
+        queue.scan(|v| scanned.push(*v));
+        assert_eq!(scanned, (1..16).collect::<Vec<_>>());
+
+        let guard = Guard::new();
+        assert!(queue.iter(&guard).copied().eq(1..16));
+        assert_eq!(queue.len(), 15);
+        assert_eq!(queue.pop().map(|e| **e), Some(1));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn batch() {
+        const NUM_TASKS: usize = 4;
+        let queue: Arc<Queue<(usize, usize)>> = Arc::new(Queue::default());
+        let workload_size = 256;
+        let batch_size = 8;
+        let barrier = Arc::new(Barrier::new(NUM_TASKS));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let queue_clone = queue.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for seq in 0..workload_size {
+                    queue_clone.push_many((0..batch_size).map(|i| (task_id, seq * batch_size + i)));
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(queue.len(), NUM_TASKS * workload_size * batch_size);
+
+        // Entries pushed in a single batch stay adjacent.
+        let guard = Guard::new();
+        let entries = queue.iter(&guard).copied().collect::<Vec<_>>();
+        for batch in entries.chunks(batch_size) {
+            assert!(batch.iter().all(|e| e.0 == batch[0].0));
+            assert!(batch.windows(2).all(|w| w[0].1 + 1 == w[1].1));
+        }
+
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        let num_popped = Arc::new(AtomicUsize::default());
+        for _ in 0..NUM_TASKS {
+            let queue_clone = queue.clone();
+            let barrier_clone = barrier.clone();
+            let num_popped_clone = num_popped.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let mut last = [None; NUM_TASKS];
+                loop {
+                    let popped = queue_clone.pop_many(5);
+                    if popped.is_empty() {
+                        break;
+                    }
+                    for e in &popped {
+                        let (task_id, seq) = ***e;
+                        assert!(last[task_id].map_or(true, |last| last < seq));
+                        last[task_id].replace(seq);
+                    }
+                    num_popped_clone.fetch_add(popped.len(), Relaxed);
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(
+            num_popped.load(Relaxed),
+            NUM_TASKS * workload_size * batch_size
+        );
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+
+        queue.push_many(std::iter::empty());
+        assert!(queue.is_empty());
+        assert!(queue.pop_many(0).is_empty());
+    }
+
+    #[test]
+    fn bounded() {
+        let queue: BoundedQueue<usize> = BoundedQueue::new(4);
+        for i in 0..4 {
+            assert_eq!(**queue.push(i).unwrap(), i);
+        }
+        assert_eq!(queue.len(), 4);
+        assert_eq!(queue.push(4).unwrap_err(), 4);
+        assert_eq!(queue.pop().map(|e| **e), Some(0));
+        assert!(queue.push(4).is_ok());
+        assert_eq!(queue.len(), 4);
+        for i in 1..5 {
+            assert_eq!(queue.pop().map(|e| **e), Some(i));
+        }
+        assert!(queue.pop().is_none());
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+
+        let queue: BoundedQueue<usize> = BoundedQueue::new(0);
+        assert!(queue.push(0).is_err());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn bounded_async() {
+        const NUM_PRODUCERS: usize = 4;
+        const CAPACITY: usize = 8;
+        let workload_size = 1024;
+
+        let queue: Arc<BoundedQueue<usize>> = Arc::new(BoundedQueue::new(CAPACITY));
+        let mut task_handles = Vec::with_capacity(NUM_PRODUCERS);
+        for task_id in 0..NUM_PRODUCERS {
+            let queue_clone = queue.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                for seq in 0..workload_size {
+                    let entry = queue_clone.push_async(task_id * workload_size + seq).await;
+                    assert_eq!(**entry, task_id * workload_size + seq);
+                }
+            }));
+        }
+
+        let mut last = [None; NUM_PRODUCERS];
+        let mut num_popped = 0;
+        while num_popped != NUM_PRODUCERS * workload_size {
+            assert!(queue.len() <= CAPACITY);
+            if let Some(entry) = queue.pop() {
+                let (task_id, seq) = (**entry / workload_size, **entry % workload_size);
+                assert!(last[task_id].map_or(true, |last| last < seq));
+                last[task_id].replace(seq);
+                num_popped += 1;
+            } else {
+                tokio::task::yield_now().await;
+            }
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        assert!(queue.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn pop_async() {
+        const NUM_CONSUMERS: usize = 4;
+        let workload_size = 1024;
+
+        let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+        let num_popped: Arc<AtomicUsize> = Arc::new(AtomicUsize::default());
+        let mut task_handles = Vec::with_capacity(NUM_CONSUMERS);
+        for _ in 0..NUM_CONSUMERS {
+            let queue_clone = queue.clone();
+            let num_popped_clone = num_popped.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                let mut last = None;
+                for _ in 0..workload_size {
+                    let popped = **queue_clone.pop_async().await;
+                    assert!(last.map_or(true, |last| last < popped));
+                    last.replace(popped);
+                    num_popped_clone.fetch_add(1, Relaxed);
+                }
+            }));
+        }
+        for seq in 0..NUM_CONSUMERS * workload_size {
+            queue.push(seq);
+            if seq % 64 == 0 {
+                tokio::task::yield_now().await;
+            }
+        }
+        for r in futures::future::join_all(task_handles).await {
+            assert!(r.is_ok());
+        }
+        assert_eq!(num_popped.load(Relaxed), NUM_CONSUMERS * workload_size);
+        assert!(queue.is_empty());
+
+        let queue: Arc<BoundedQueue<usize>> = Arc::new(BoundedQueue::new(4));
+        let queue_clone = queue.clone();
+        let consumer = tokio::task::spawn(async move {
+            for seq in 0..workload_size {
+                assert_eq!(**queue_clone.pop_async().await, seq);
+            }
+        });
+        for seq in 0..workload_size {
+            queue.push_async(seq).await;
+        }
+        assert!(consumer.await.is_ok());
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn pop_if() {
+        const NUM_CONSUMERS: usize = 4;
+        let workload_size = 4096;
+
+        let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+        let now: Arc<AtomicUsize> = Arc::new(AtomicUsize::default());
+        let num_popped: Arc<AtomicUsize> = Arc::new(AtomicUsize::default());
+        queue.push_many(0..workload_size);
+
+        let mut thread_handles = Vec::with_capacity(NUM_CONSUMERS);
+        for _ in 0..NUM_CONSUMERS {
+            let queue_clone = queue.clone();
+            let now_clone = now.clone();
+            let num_popped_clone = num_popped.clone();
+            thread_handles.push(thread::spawn(move || {
+                let mut last = None;
+                while num_popped_clone.load(Relaxed) < workload_size {
+                    match queue_clone.pop_if(|e| **e <= now_clone.load(Relaxed)) {
+                        Ok(Some(popped)) => {
+                            assert!(**popped <= now_clone.load(Relaxed));
+                            assert!(last.map_or(true, |last| last < **popped));
+                            last.replace(**popped);
+                            num_popped_clone.fetch_add(1, Relaxed);
+                        }
+                        Ok(None) => thread::yield_now(),
+                        Err(oldest) => {
+                            assert!(**oldest >= last.map_or(0, |last| last + 1));
+                            thread::yield_now();
+                        }
+                    }
+                }
+            }));
+        }
+        while now.load(Relaxed) < workload_size {
+            now.fetch_add(1, Relaxed);
+            if now.load(Relaxed) % 64 == 0 {
+                thread::yield_now();
+            }
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert_eq!(num_popped.load(Relaxed), workload_size);
+        assert!(queue.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn pop_wait() {
+        const NUM_CONSUMERS: usize = 4;
+        let workload_size = 1024;
+
+        let queue: Arc<Queue<usize>> = Arc::new(Queue::default());
+        assert!(queue.pop_wait(Duration::from_millis(1)).is_none());
+
+        let mut thread_handles = Vec::with_capacity(NUM_CONSUMERS);
+        for _ in 0..NUM_CONSUMERS {
+            let queue_clone = queue.clone();
+            thread_handles.push(thread::spawn(move || {
+                let mut last = None;
+                for _ in 0..workload_size {
+                    let popped = **queue_clone.pop_wait(Duration::MAX).unwrap();
+                    assert!(last.map_or(true, |last| last < popped));
+                    last.replace(popped);
+                }
+            }));
+        }
+        for seq in 0..NUM_CONSUMERS * workload_size {
+            queue.push(seq);
+            if seq % 64 == 0 {
+                thread::yield_now();
+            }
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert!(queue.is_empty());
+
+        let queue_clone = queue.clone();
+        let consumer = thread::spawn(move || {
+            let mut popped = 0;
+            while popped < workload_size {
+                if let Some(entry) = queue_clone.pop_wait(Duration::from_micros(10)) {
+                    assert_eq!(**entry, popped);
+                    popped += 1;
+                }
+            }
+        });
+        for seq in 0..workload_size {
+            queue.push(seq);
+            if seq % 16 == 0 {
+                thread::sleep(Duration::from_micros(20));
+            }
+        }
+        assert!(consumer.join().is_ok());
+        assert!(queue.is_empty());
+    }
+
     #[test]
     fn pop_all() {
         const NUM_ENTRIES: usize = 256;
@@ -3119,6 +6269,8 @@ mod queue_test {
 mod stack_test {
     use crate::ebr::Guard;
     use crate::Stack;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
     use std::{panic::UnwindSafe, sync::Arc};
     use tokio::sync::Barrier as AsyncBarrier;
 
@@ -3153,6 +6305,168 @@ mod stack_test {
         assert!(stack_clone.pop().is_none());
     }
 
+    #[test]
+    fn from_iter_extend() {
+        let mut stack: Stack<usize> = (0..4).collect();
+        assert_eq!(stack.len(), 4);
+        stack.extend(4..8);
+        assert_eq!(stack.len(), 8);
+        assert!(stack.iter(&Guard::new()).copied().eq((0..8).rev()));
+        assert_eq!(stack.pop().map(|e| **e), Some(7));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn len() {
+        const NUM_TASKS: usize = 4;
+        let stack: Arc<Stack<usize>> = Arc::new(Stack::default());
+        let workload_size = 1024;
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let stack_clone = stack.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                for seq in 0..workload_size {
+                    stack_clone.push(seq);
+                    if task_id % 2 == 0 && seq % 2 == 0 {
+                        assert!(stack_clone.pop().is_some());
+                    }
+                    assert!(stack_clone.push_if(seq, |_| false).is_err());
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        let expected = NUM_TASKS * workload_size - (NUM_TASKS / 2) * (workload_size / 2);
+        assert_eq!(stack.len(), expected);
+        assert_eq!(stack.iter(&Guard::new()).count(), expected);
+        assert_eq!(stack.clone().len(), expected);
+
+        let popped = stack.pop_all();
+        assert_eq!(stack.len(), 0);
+        assert!(stack.is_empty());
+        assert_eq!(popped.len(), expected);
+        assert!(popped.pop().is_some());
+        assert_eq!(popped.len(), expected - 1);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn len_pop_all() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 200;
+        let stack: Arc<Stack<usize>> = Arc::new(Stack::default());
+        let num_pushed = Arc::new(AtomicUsize::new(0));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let stack_clone = stack.clone();
+            let num_pushed_clone = num_pushed.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                for seq in 0..workload_size {
+                    for _ in 0..8 {
+                        stack_clone.push(seq);
+                        num_pushed_clone.fetch_add(1, Relaxed);
+                    }
+                    match task_id % 3 {
+                        0 => {
+                            let popped = stack_clone.pop_all();
+                            assert!(popped.len() <= num_pushed_clone.load(Relaxed));
+                            while popped.pop().is_some() {}
+                            assert_eq!(popped.len(), 0);
+                        }
+                        _ => {
+                            for _ in 0..4 {
+                                stack_clone.pop();
+                            }
+                        }
+                    }
+                    assert!(stack_clone.len() <= num_pushed_clone.load(Relaxed));
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+        assert!(stack.len() <= num_pushed.load(Relaxed));
+        assert!(stack.len() <= stack.iter(&Guard::new()).count());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn pop_all_iter() {
+        const NUM_TASKS: usize = 4;
+        let stack: Arc<Stack<R>> = Arc::new(Stack::default());
+        let workload_size = 1024;
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS);
+        for task_id in 0..NUM_TASKS {
+            let stack_clone = stack.clone();
+            thread_handles.push(std::thread::spawn(move || {
+                let mut last = vec![None; NUM_TASKS];
+                let mut num_popped = 0;
+                for seq in 0..workload_size {
+                    stack_clone.push(R::new(task_id, seq));
+                    if seq % 64 == 0 {
+                        // Entries are yielded newest first, and are newer than any entry
+                        // popped earlier.
+                        let mut newest = last.clone();
+                        let mut prev: Vec<Option<usize>> = vec![None; NUM_TASKS];
+                        for entry in stack_clone.pop_all() {
+                            assert!(prev[entry.0].map_or(true, |prev| prev > entry.1));
+                            assert!(last[entry.0].map_or(true, |last| last < entry.1));
+                            if prev[entry.0].is_none() {
+                                newest[entry.0].replace(entry.1);
+                            }
+                            prev[entry.0].replace(entry.1);
+                            num_popped += 1;
+                        }
+                        last = newest;
+                    }
+                }
+                num_popped
+            }));
+        }
+        let mut num_popped = 0;
+        for t in thread_handles {
+            num_popped += t.join().unwrap();
+        }
+        num_popped += stack.pop_all().into_iter().count();
+        assert_eq!(num_popped, NUM_TASKS * workload_size);
+        assert!(stack.is_empty());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn pop_async() {
+        const NUM_CONSUMERS: usize = 4;
+        let workload_size = 1024;
+
+        let stack: Arc<Stack<usize>> = Arc::new(Stack::default());
+        let mut task_handles = Vec::with_capacity(NUM_CONSUMERS);
+        for _ in 0..NUM_CONSUMERS {
+            let stack_clone = stack.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                let mut popped = Vec::with_capacity(workload_size);
+                for _ in 0..workload_size {
+                    popped.push(**stack_clone.pop_async().await);
+                }
+                popped
+            }));
+        }
+        for seq in 0..NUM_CONSUMERS * workload_size {
+            stack.push(seq);
+            if seq % 64 == 0 {
+                tokio::task::yield_now().await;
+            }
+        }
+        let mut popped = Vec::with_capacity(NUM_CONSUMERS * workload_size);
+        for r in futures::future::join_all(task_handles).await {
+            popped.append(&mut r.unwrap());
+        }
+        popped.sort_unstable();
+        assert!(popped.into_iter().eq(0..NUM_CONSUMERS * workload_size));
+        assert!(stack.is_empty());
+    }
+
     #[cfg_attr(miri, ignore)]
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn iterator() {
@@ -3284,6 +6598,539 @@ mod stack_test {
     }
 }
 
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod workstealingdeque_test {
+    use crate::work_stealing_deque::Stealer;
+    use crate::WorkStealingDeque;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(WorkStealingDeque<String>: Send, UnwindSafe);
+    static_assertions::assert_not_impl_all!(WorkStealingDeque<String>: Sync);
+    static_assertions::assert_impl_all!(Stealer<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_not_impl_all!(WorkStealingDeque<*const String>: Send);
+    static_assertions::assert_not_impl_all!(Stealer<*const String>: Send, Sync);
+
+    struct R(&'static AtomicUsize, usize);
+    impl R {
+        fn new(cnt: &'static AtomicUsize, seq: usize) -> R {
+            cnt.fetch_add(1, Relaxed);
+            R(cnt, seq)
+        }
+    }
+    impl Drop for R {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Relaxed);
+        }
+    }
+
+    #[test]
+    fn reclaim() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let deque: WorkStealingDeque<R> = WorkStealingDeque::with_capacity(2);
+        let stealer = deque.stealer();
+        for seq in 0..64 {
+            deque.push(R::new(&INST_CNT, seq));
+        }
+        assert_eq!(deque.len(), 64);
+        assert_eq!(deque.capacity(), 64);
+        assert_eq!(stealer.steal().map(|r| r.1), Some(0));
+        assert_eq!(deque.pop().map(|r| r.1), Some(63));
+        assert_eq!(INST_CNT.load(Relaxed), 62);
+        drop(deque);
+        assert_eq!(stealer.len(), 62);
+        assert_eq!(stealer.steal().map(|r| r.1), Some(1));
+        drop(stealer);
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn steal() {
+        const NUM_STEALERS: usize = 4;
+        let workload_size = 65536;
+        let deque: WorkStealingDeque<usize> = WorkStealingDeque::with_capacity(4);
+        let barrier = Arc::new(Barrier::new(NUM_STEALERS + 1));
+        let num_pushed = Arc::new(AtomicUsize::new(0));
+        let mut thread_handles = Vec::with_capacity(NUM_STEALERS);
+        for _ in 0..NUM_STEALERS {
+            let stealer = deque.stealer();
+            let barrier_clone = barrier.clone();
+            let num_pushed_clone = num_pushed.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let mut stolen = Vec::new();
+                let mut last = None;
+                loop {
+                    let done = num_pushed_clone.load(Relaxed) == workload_size;
+                    if let Some(val) = stealer.steal() {
+                        assert!(last.map_or(true, |last| last < val));
+                        last.replace(val);
+                        stolen.push(val);
+                    } else if done {
+                        break;
+                    }
+                }
+                stolen
+            }));
+        }
+
+        barrier.wait();
+        let mut popped = Vec::new();
+        for seq in 0..workload_size {
+            deque.push(seq);
+            if seq % 3 == 0 {
+                popped.extend(deque.pop());
+            }
+        }
+        num_pushed.store(workload_size, Relaxed);
+        while let Some(val) = deque.pop() {
+            popped.push(val);
+        }
+        for t in thread_handles {
+            popped.append(&mut t.join().unwrap());
+        }
+        popped.sort_unstable();
+        assert!(popped.into_iter().eq(0..workload_size));
+        assert!(deque.is_empty());
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod mpmc_test {
+    use super::common::R;
+    use crate::ebr::Guard;
+    use crate::mpmc::{self, Receiver, Sender};
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::thread;
+    use std::time::Duration;
+
+    static_assertions::assert_impl_all!(Sender<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(Receiver<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_not_impl_all!(Sender<*const String>: Send, Sync);
+    static_assertions::assert_not_impl_all!(Receiver<*const String>: Send, Sync);
+
+    #[test]
+    fn close() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let (sender, receiver) = mpmc::channel();
+        let receiver_clone = receiver.clone();
+        for _ in 0..16 {
+            assert!(sender.send(R::new(&INST_CNT)).is_ok());
+        }
+        assert_eq!(receiver.len(), 16);
+        assert!(receiver.try_recv().is_some());
+
+        drop(receiver);
+        assert!(!sender.is_closed());
+        drop(receiver_clone);
+        assert!(sender.is_closed());
+        assert!(sender.send(R::new(&INST_CNT)).is_err());
+        assert_eq!(INST_CNT.load(Relaxed), 15);
+
+        drop(sender);
+        while INST_CNT.load(Relaxed) != 0 {
+            Guard::new().accelerate();
+            thread::yield_now();
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn recv() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+        let (sender, receiver) = mpmc::channel();
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS * 2);
+        for task_id in 0..NUM_TASKS {
+            let sender_clone = sender.clone();
+            thread_handles.push(thread::spawn(move || {
+                for seq in 0..workload_size {
+                    assert!(sender_clone.send(task_id * workload_size + seq).is_ok());
+                }
+                Vec::new()
+            }));
+            let receiver_clone = receiver.clone();
+            thread_handles.push(thread::spawn(move || {
+                let mut received = Vec::new();
+                while let Some(val) = receiver_clone.recv() {
+                    received.push(val);
+                }
+                received
+            }));
+        }
+        drop(sender);
+        drop(receiver);
+        let mut received = Vec::new();
+        for t in thread_handles {
+            received.append(&mut t.join().unwrap());
+        }
+        received.sort_unstable();
+        assert!(received.into_iter().eq(0..NUM_TASKS * workload_size));
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn send_close() {
+        const NUM_TASKS: usize = 4;
+        for _ in 0..64 {
+            let (sender, receiver) = mpmc::channel();
+            let mut sender_handles = Vec::with_capacity(NUM_TASKS);
+            let mut receiver_handles = Vec::with_capacity(NUM_TASKS);
+            for _ in 0..NUM_TASKS {
+                let sender_clone = sender.clone();
+                sender_handles.push(thread::spawn(move || {
+                    let mut num_sent = 0;
+                    while num_sent < 4096 && sender_clone.send(num_sent).is_ok() {
+                        num_sent += 1;
+                    }
+                    num_sent
+                }));
+                let receiver_clone = receiver.clone();
+                receiver_handles.push(thread::spawn(move || {
+                    let mut num_received = 0;
+                    while receiver_clone.recv().is_some() {
+                        num_received += 1;
+                    }
+                    num_received
+                }));
+            }
+            thread::sleep(Duration::from_micros(100));
+            sender.close();
+            let num_sent: usize = sender_handles.into_iter().map(|t| t.join().unwrap()).sum();
+            let num_received: usize = receiver_handles
+                .into_iter()
+                .map(|t| t.join().unwrap())
+                .sum();
+            assert_eq!(num_sent, num_received);
+            assert!(receiver.is_empty());
+        }
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn recv_async() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 4096;
+        let (sender, receiver) = mpmc::channel();
+        let mut task_handles = Vec::with_capacity(NUM_TASKS);
+        for _ in 0..NUM_TASKS {
+            let receiver_clone = receiver.clone();
+            task_handles.push(tokio::task::spawn(async move {
+                let mut last = None;
+                let mut num_received = 0;
+                while let Some(val) = receiver_clone.recv_async().await {
+                    assert!(last.map_or(true, |last| last < val));
+                    last.replace(val);
+                    num_received += 1;
+                }
+                num_received
+            }));
+        }
+        drop(receiver);
+        for seq in 0..NUM_TASKS * workload_size {
+            assert!(sender.send(seq).is_ok());
+            if seq % 64 == 0 {
+                tokio::task::yield_now().await;
+            }
+        }
+        sender.close();
+        let mut num_received = 0;
+        for r in futures::future::join_all(task_handles).await {
+            num_received += r.unwrap();
+        }
+        assert_eq!(num_received, NUM_TASKS * workload_size);
+        assert!(sender.is_empty());
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod ringbuffer_test {
+    use crate::RingBuffer;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::Relaxed;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(RingBuffer<String>: Send, Sync, UnwindSafe);
+    static_assertions::assert_not_impl_all!(RingBuffer<*const String>: Send, Sync);
+
+    struct R(&'static AtomicUsize, usize);
+    impl R {
+        fn new(cnt: &'static AtomicUsize, seq: usize) -> R {
+            cnt.fetch_add(1, Relaxed);
+            R(cnt, seq)
+        }
+    }
+    impl Drop for R {
+        fn drop(&mut self) {
+            self.0.fetch_sub(1, Relaxed);
+        }
+    }
+
+    #[test]
+    fn reclaim() {
+        static INST_CNT: AtomicUsize = AtomicUsize::new(0);
+        let ring_buffer: RingBuffer<R> = RingBuffer::new(7);
+        for seq in 0..64 {
+            if seq % 3 == 0 {
+                drop(ring_buffer.pop());
+            }
+            assert!(INST_CNT.load(Relaxed) <= 7);
+            drop(ring_buffer.push_overwrite(R::new(&INST_CNT, seq)));
+        }
+        assert!(ring_buffer.is_full());
+        assert!(ring_buffer.push(R::new(&INST_CNT, 64)).is_err());
+        assert_eq!(ring_buffer.pop().map(|r| r.1), Some(57));
+        assert_eq!(INST_CNT.load(Relaxed), 6);
+        drop(ring_buffer);
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+
+        let ring_buffer: RingBuffer<R> = RingBuffer::new(1);
+        for seq in 0..16 {
+            assert!(ring_buffer.push(R::new(&INST_CNT, seq)).is_ok());
+            assert!(ring_buffer.push(R::new(&INST_CNT, seq)).is_err());
+            assert!(ring_buffer.is_full());
+            assert_eq!(ring_buffer.pop().map(|r| r.1), Some(seq));
+            assert!(ring_buffer.pop().is_none());
+        }
+        assert!(ring_buffer.push_overwrite(R::new(&INST_CNT, 16)).is_none());
+        assert_eq!(
+            ring_buffer
+                .push_overwrite(R::new(&INST_CNT, 17))
+                .map(|r| r.1),
+            Some(16)
+        );
+        drop(ring_buffer);
+        assert_eq!(INST_CNT.load(Relaxed), 0);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn mpmc() {
+        const NUM_TASKS: usize = 4;
+        let workload_size = 16384;
+        let ring_buffer: Arc<RingBuffer<(usize, usize)>> = Arc::new(RingBuffer::new(13));
+        let barrier = Arc::new(Barrier::new(NUM_TASKS * 2));
+        let mut thread_handles = Vec::with_capacity(NUM_TASKS * 2);
+        for task_id in 0..NUM_TASKS {
+            let ring_buffer_clone = ring_buffer.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                for seq in 0..workload_size {
+                    let mut val = (task_id, seq);
+                    while let Err(returned) = ring_buffer_clone.push(val) {
+                        val = returned;
+                        thread::yield_now();
+                    }
+                }
+                Vec::new()
+            }));
+            let ring_buffer_clone = ring_buffer.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let mut popped = Vec::with_capacity(workload_size);
+                let mut last = [None; NUM_TASKS];
+                while popped.len() < workload_size {
+                    if let Some((task_id, seq)) = ring_buffer_clone.pop() {
+                        assert!(last[task_id].map_or(true, |last| last < seq));
+                        last[task_id].replace(seq);
+                        popped.push(task_id * workload_size + seq);
+                    } else {
+                        thread::yield_now();
+                    }
+                    assert!(ring_buffer_clone.len() <= 13);
+                }
+                popped
+            }));
+        }
+        let mut popped = Vec::with_capacity(NUM_TASKS * workload_size);
+        for t in thread_handles {
+            popped.append(&mut t.join().unwrap());
+        }
+        popped.sort_unstable();
+        assert!(popped.into_iter().eq(0..NUM_TASKS * workload_size));
+        assert!(ring_buffer.is_empty());
+    }
+}
+
+#[cfg(not(feature = "loom"))]
+#[cfg(test)]
+mod sketch_test {
+    use crate::{CountMinSketch, HyperLogLog};
+    use std::collections::hash_map::RandomState;
+    use std::panic::UnwindSafe;
+    use std::sync::atomic::AtomicU8;
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    static_assertions::assert_impl_all!(CountMinSketch: Send, Sync, UnwindSafe);
+    static_assertions::assert_impl_all!(HyperLogLog: Send, Sync, UnwindSafe);
+
+    #[test]
+    fn count_min() {
+        // `ε = e / 2048` and `δ = e^(-4)`.
+        let sketch = CountMinSketch::new(2048, 4);
+        let workload_size = 1024;
+        for k in 0..workload_size {
+            sketch.add(&k, k % 16 + 1);
+        }
+        let total: usize = (0..workload_size).map(|k| k % 16 + 1).sum();
+        let mut num_exceeding = 0;
+        for k in 0..workload_size {
+            let estimate = sketch.estimate(&k);
+            assert!(estimate >= k % 16 + 1);
+            if estimate > k % 16 + 1 + total * 3 / 2048 {
+                num_exceeding += 1;
+            }
+        }
+        assert!(num_exceeding < workload_size / 32);
+
+        let sketch_clone = sketch.clone();
+        sketch.halve();
+        for k in 0..workload_size {
+            assert!(sketch.estimate(&k) >= (k % 16 + 1) / 2);
+            assert_eq!(sketch.estimate(&k), sketch_clone.estimate(&k) / 2);
+        }
+        sketch.clear();
+        assert!((0..workload_size).all(|k| sketch.estimate(&k) == 0));
+    }
+
+    #[test]
+    fn count_min_saturating_halving() {
+        let sketch: CountMinSketch<RandomState, AtomicU8> =
+            CountMinSketch::with_counter(256, 4, RandomState::new()).with_halving(2048);
+        let workload_size = 64;
+        for k in 0..workload_size {
+            sketch.add(&k, 15);
+        }
+        assert!((0..workload_size).all(|k| sketch.estimate(&k) >= 15));
+        sketch.add(&workload_size, 1000);
+        assert_eq!(sketch.estimate(&workload_size), 255);
+
+        // The sample size is reached, therefore the counters have been halved; keys sharing all
+        // their counters with others may have had estimates above `15`.
+        let estimates: Vec<_> = (0..workload_size).map(|k| sketch.estimate(&k)).collect();
+        sketch.add(&workload_size, 88);
+        assert_eq!(sketch.estimate(&workload_size), 127);
+        assert!((0..workload_size).all(|k| sketch.estimate(&k) >= 7));
+        assert!((0..workload_size).all(|k| sketch.estimate(&k) == estimates[k] / 2));
+
+        // The number of counts since the last halving is halved as well.
+        for _ in 0..1023 {
+            sketch.increment(&workload_size);
+        }
+        assert_eq!(sketch.estimate(&workload_size), 255);
+        sketch.increment(&workload_size);
+        assert_eq!(sketch.estimate(&workload_size), 127);
+
+        sketch.clear();
+        assert!((0..=workload_size).all(|k| sketch.estimate(&k) == 0));
+    }
+
+    #[test]
+    fn count_min_halving_large_count() {
+        // A count far exceeding the sample size must not disable halving.
+        let sketch = CountMinSketch::new(1024, 4).with_halving(100);
+        sketch.add("a", 1000);
+        assert!(sketch.estimate("a") < 100);
+        for _ in 0..10_000 {
+            sketch.increment("b");
+        }
+        assert!(sketch.estimate("b") < 200);
+        assert!(sketch.estimate("a") < 100);
+
+        let sketch = CountMinSketch::new(1024, 4).with_halving(1);
+        sketch.add("a", 1000);
+        for _ in 0..10_000 {
+            sketch.increment("b");
+        }
+        assert!(sketch.estimate("a") <= 1);
+        assert!(sketch.estimate("b") <= 1);
+    }
+
+    #[test]
+    fn hyperloglog() {
+        let hyperloglog = HyperLogLog::new(12);
+        assert_eq!(hyperloglog.estimate(), 0);
+        for workload_size in [10, 100, 1000, 10_000, 100_000] {
+            hyperloglog.clear();
+            for _ in 0..2 {
+                for k in 0..workload_size {
+                    hyperloglog.insert(&k);
+                }
+            }
+            // The standard error is about 1.6%.
+            let estimate = hyperloglog.estimate();
+            assert!(
+                estimate.abs_diff(workload_size) <= workload_size / 12 + 1,
+                "{estimate} {workload_size}"
+            );
+        }
+        assert_eq!(hyperloglog.clone().estimate(), hyperloglog.estimate());
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn update_merge() {
+        const NUM_THREADS: usize = 8;
+        let workload_size = 8192;
+        let build_hasher = RandomState::new();
+        let sketches: Arc<Vec<(CountMinSketch, HyperLogLog)>> = Arc::new(
+            (0..2)
+                .map(|_| {
+                    (
+                        CountMinSketch::with_hasher(1024, 4, build_hasher.clone()),
+                        HyperLogLog::with_hasher(14, build_hasher.clone()),
+                    )
+                })
+                .collect(),
+        );
+        let barrier = Arc::new(Barrier::new(NUM_THREADS));
+        let mut thread_handles = Vec::with_capacity(NUM_THREADS);
+        for task_id in 0..NUM_THREADS {
+            let sketches_clone = sketches.clone();
+            let barrier_clone = barrier.clone();
+            thread_handles.push(thread::spawn(move || {
+                barrier_clone.wait();
+                let (sketch, hyperloglog) = &sketches_clone[task_id % 2];
+                for k in 0..workload_size {
+                    sketch.increment(&(k % 64));
+                    hyperloglog.insert(&(k * NUM_THREADS + task_id));
+                }
+            }));
+        }
+        for t in thread_handles {
+            assert!(t.join().is_ok());
+        }
+
+        let (sketch, hyperloglog) = &sketches[0];
+        let (other_sketch, other_hyperloglog) = &sketches[1];
+        for k in 0..64_usize {
+            assert!(sketch.estimate(&k) >= workload_size * NUM_THREADS / 2 / 64);
+        }
+        sketch.merge(other_sketch);
+        hyperloglog.merge(other_hyperloglog);
+        for k in 0..64_usize {
+            assert!(sketch.estimate(&k) >= workload_size * NUM_THREADS / 64);
+        }
+        let estimate = hyperloglog.estimate();
+        let expected = workload_size * NUM_THREADS;
+        assert!(estimate.abs_diff(expected) <= expected / 20);
+    }
+}
+
 #[cfg(not(feature = "loom"))]
 #[cfg(test)]
 mod random_failure_test {
@@ -3461,9 +7308,12 @@ mod random_failure_test {
 #[cfg(feature = "serde")]
 #[cfg(test)]
 mod serde_test {
-    use crate::{HashCache, HashIndex, HashMap, HashSet, TreeIndex};
+    use crate::ebr::Guard;
+    use crate::{Bag, HashCache, HashIndex, HashMap, HashSet, Queue, Stack, TreeIndex};
 
-    use serde_test::{assert_tokens, Token};
+    use serde::de::value::{Error, SeqDeserializer};
+    use serde::Deserialize;
+    use serde_test::{assert_de_tokens, assert_ser_tokens, assert_tokens, Token};
 
     #[test]
     fn hashmap() {
@@ -3490,6 +7340,25 @@ mod serde_test {
         );
     }
 
+    #[test]
+    fn hashset_deserialize() {
+        let hashset: HashSet<u64> = HashSet::new();
+        assert_tokens(&hashset, &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+
+        assert!(hashset.insert(3).is_ok());
+        assert!(hashset.insert(5).is_ok());
+        assert_de_tokens(
+            &hashset,
+            &[
+                Token::Seq { len: None },
+                Token::U64(5),
+                Token::U64(3),
+                Token::U64(5),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
     #[test]
     fn hashindex() {
         let hashindex: HashIndex<u64, i16> = HashIndex::new();
@@ -3543,4 +7412,64 @@ mod serde_test {
             ],
         );
     }
+
+    #[test]
+    fn queue() {
+        let queue: Queue<u64> = Queue::default();
+        queue.push(2);
+        queue.push(3);
+        assert_ser_tokens(
+            &queue,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U64(2),
+                Token::U64(3),
+                Token::SeqEnd,
+            ],
+        );
+
+        let deserialized =
+            Queue::<u64>::deserialize(SeqDeserializer::<_, Error>::new([2_u64, 3].into_iter()))
+                .unwrap();
+        assert!(deserialized.iter(&Guard::new()).copied().eq([2, 3]));
+    }
+
+    #[test]
+    fn stack() {
+        let stack: Stack<u64> = Stack::default();
+        stack.push(2);
+        stack.push(3);
+        assert_ser_tokens(
+            &stack,
+            &[
+                Token::Seq { len: Some(2) },
+                Token::U64(3),
+                Token::U64(2),
+                Token::SeqEnd,
+            ],
+        );
+
+        let deserialized =
+            Stack::<u64>::deserialize(SeqDeserializer::<_, Error>::new([3_u64, 2].into_iter()))
+                .unwrap();
+        assert!(deserialized.iter(&Guard::new()).copied().eq([3, 2]));
+    }
+
+    #[test]
+    fn bag() {
+        let bag: Bag<u64> = Bag::default();
+        bag.push(2);
+        assert_ser_tokens(
+            &bag,
+            &[Token::Seq { len: Some(1) }, Token::U64(2), Token::SeqEnd],
+        );
+
+        let deserialized = Bag::<u64, 4>::deserialize(SeqDeserializer::<_, Error>::new(
+            [2_u64, 3, 5, 7, 11, 13].into_iter(),
+        ))
+        .unwrap();
+        let mut entries = deserialized.pop_many(usize::MAX);
+        entries.sort_unstable();
+        assert_eq!(entries, [2, 3, 5, 7, 11, 13]);
+    }
 }
@@ -2,11 +2,12 @@ use crate::ebr::Guard;
 use crate::maybe_std::yield_now;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::atomic::AtomicUsize;
-use std::sync::atomic::Ordering::{AcqRel, Relaxed};
+use std::sync::atomic::Ordering::{AcqRel, Relaxed, SeqCst};
+use std::sync::atomic::{fence, AtomicUsize};
 use std::sync::{Condvar, Mutex};
 use std::task::{Context, Poll, Waker};
 use std::thread;
+use std::time::Instant;
 
 /// `ASYNC` is a flag indicating that the referenced instance corresponds to an asynchronous
 /// operation.
@@ -27,6 +28,25 @@ impl WaitQueue {
     /// Waits for the condition to be met or signaled.
     #[inline]
     pub(crate) fn wait_sync<T, F: FnOnce() -> Result<T, ()>>(&self, f: F) -> Result<T, ()> {
+        self.wait_sync_internal(f, None)
+    }
+
+    /// Waits for the condition to be met or signaled until the deadline.
+    #[inline]
+    pub(crate) fn wait_sync_until<T, F: FnOnce() -> Result<T, ()>>(
+        &self,
+        f: F,
+        deadline: Instant,
+    ) -> Result<T, ()> {
+        self.wait_sync_internal(f, Some(deadline))
+    }
+
+    /// Waits for the condition to be met or signaled, optionally until the deadline.
+    fn wait_sync_internal<T, F: FnOnce() -> Result<T, ()>>(
+        &self,
+        f: F,
+        deadline: Option<Instant>,
+    ) -> Result<T, ()> {
         if cfg!(miri) || cfg!(feature = "loom") {
             yield_now();
             return f();
@@ -46,13 +66,27 @@ impl WaitQueue {
             entry_mut.next.store(current, Relaxed);
         }
 
+        // Pairs with the fence in `signal_if_waiting`: either the closure observes the resource,
+        // or the signaling thread observes the entry.
+        fence(SeqCst);
+
         // Execute the closure.
         let result = f();
         if result.is_ok() {
             self.signal();
         }
 
-        entry_mut.wait();
+        if let Some(deadline) = deadline {
+            if !entry_mut.wait_until(deadline) {
+                // `entry` must be pulled out of the `WaitQueue` before being dropped: the
+                // signal is either sent by this thread or by another thread that has already
+                // taken it out of the `WaitQueue`.
+                self.signal();
+                entry_mut.wait();
+            }
+        } else {
+            entry_mut.wait();
+        }
         result
     }
 
@@ -86,6 +120,10 @@ impl WaitQueue {
             async_wait.next.store(current, Relaxed);
         }
 
+        // Pairs with the fence in `signal_if_waiting`: either the closure observes the resource,
+        // or the signaling thread observes the entry.
+        fence(SeqCst);
+
         // Execute the closure.
         if let Ok(result) = f() {
             self.signal();
@@ -101,6 +139,19 @@ impl WaitQueue {
         Err(())
     }
 
+    /// Signals the threads in the wait queue if there are any.
+    ///
+    /// It only reads the wait queue when no threads are waiting, therefore it is suitable for
+    /// frequently executed code paths; the resource has to be made available before the method
+    /// is invoked.
+    #[inline]
+    pub(crate) fn signal_if_waiting(&self) {
+        fence(SeqCst);
+        if self.wait_queue.load(Relaxed) != 0 {
+            self.signal();
+        }
+    }
+
     /// Signals the threads in the wait queue.
     #[inline]
     pub(crate) fn signal(&self) {
@@ -309,6 +360,27 @@ impl SyncWait {
         }
     }
 
+    /// Waits for a signal until the deadline.
+    ///
+    /// Returns `false` if the deadline has passed without receiving a signal.
+    fn wait_until(&self, deadline: Instant) -> bool {
+        #[allow(clippy::mutex_atomic)]
+        let mut completed = unsafe { self.mutex.lock().unwrap_unchecked() };
+        while !*completed {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+            completed = unsafe {
+                self.condvar
+                    .wait_timeout(completed, deadline - now)
+                    .unwrap_unchecked()
+                    .0
+            };
+        }
+        true
+    }
+
     /// Sends a signal.
     fn signal(&self) {
         #[allow(clippy::mutex_atomic)]
@@ -369,6 +441,39 @@ mod test {
             .for_each(|t| assert!(t.join().is_ok()));
     }
 
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn wait_queue_signal_if_waiting() {
+        let num_rounds = 4096;
+        let wait_queue = Arc::new(WaitQueue::default());
+        let data = Arc::new(AtomicUsize::new(0));
+        wait_queue.signal_if_waiting();
+        assert_eq!(wait_queue.wait_queue.load(Relaxed), 0);
+
+        let wait_queue_clone = wait_queue.clone();
+        let data_clone = data.clone();
+        let task_handle = std::thread::spawn(move || {
+            for round in 1..=num_rounds {
+                while wait_queue_clone
+                    .wait_sync(|| {
+                        if data_clone.load(Relaxed) >= round {
+                            Ok(())
+                        } else {
+                            Err(())
+                        }
+                    })
+                    .is_err()
+                {}
+            }
+        });
+
+        for _ in 0..num_rounds {
+            data.fetch_add(1, Relaxed);
+            wait_queue.signal_if_waiting();
+        }
+        assert!(task_handle.join().is_ok());
+    }
+
     #[cfg_attr(miri, ignore)]
     #[tokio::test(flavor = "multi_thread", worker_threads = 16)]
     async fn wait_queue_async() {
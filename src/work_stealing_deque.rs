@@ -0,0 +1,478 @@
+//! [`WorkStealingDeque`] is a lock-free concurrent work-stealing double-ended queue.
+
+use super::ebr::{AtomicShared, Guard, Shared, Tag};
+use std::cell::{Cell, UnsafeCell};
+use std::fmt::{self, Debug};
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+use std::panic::UnwindSafe;
+use std::ptr;
+use std::sync::atomic::Ordering::{self, Acquire, Relaxed, Release, SeqCst};
+use std::sync::atomic::{fence, AtomicIsize};
+use std::sync::Arc;
+
+/// [`WorkStealingDeque`] is a lock-free concurrent work-stealing double-ended queue.
+///
+/// [`WorkStealingDeque`] is an implementation of the Chase-Lev work-stealing deque. The owner of a
+/// [`WorkStealingDeque`] pushes and pops instances at one end, and other threads steal instances
+/// from the other end through [`Stealer`] handles. A [`WorkStealingDeque`] cannot be shared among
+/// threads, whereas a [`Stealer`] can.
+///
+/// The underlying array grows when it becomes full, and retired arrays are reclaimed by
+/// [`sdd`](https://crates.io/crates/sdd).
+pub struct WorkStealingDeque<T> {
+    /// The state shared with [`Stealer`] instances.
+    inner: Arc<Inner<T>>,
+
+    /// Makes [`WorkStealingDeque`] `!Sync`.
+    _not_sync: PhantomData<Cell<()>>,
+}
+
+/// [`Stealer`] steals instances from the other end of a [`WorkStealingDeque`].
+pub struct Stealer<T> {
+    /// The state shared with the [`WorkStealingDeque`].
+    inner: Arc<Inner<T>>,
+}
+
+/// The default capacity of the underlying array.
+const DEFAULT_CAPACITY: usize = 32;
+
+/// The state of a [`WorkStealingDeque`].
+struct Inner<T> {
+    /// The index of the oldest instance, which stealers take.
+    top: AtomicIsize,
+
+    /// The index next to the newest instance, which the owner pushes to and pops from.
+    bottom: AtomicIsize,
+
+    /// The circular array storing instances.
+    buffer: AtomicShared<Buffer<T>>,
+}
+
+/// The circular array of a [`WorkStealingDeque`].
+///
+/// [`Buffer`] never drops the instances; the owner of the instances is determined by `top` and
+/// `bottom`.
+struct Buffer<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> WorkStealingDeque<T> {
+    /// Creates an empty [`WorkStealingDeque`] with the specified capacity.
+    ///
+    /// The capacity is rounded up to the next power of two, and the [`WorkStealingDeque`] grows
+    /// beyond the capacity if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::with_capacity(1000);
+    /// assert!(deque.capacity() >= 1000);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let buffer = unsafe { Shared::new_unchecked(Buffer::new(capacity)) };
+        Self {
+            inner: Arc::new(Inner {
+                top: AtomicIsize::new(0),
+                bottom: AtomicIsize::new(0),
+                buffer: AtomicShared::from(buffer),
+            }),
+            _not_sync: PhantomData,
+        }
+    }
+
+    /// Pushes an instance of `T` to the owner's end.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    ///
+    /// deque.push(11);
+    /// assert_eq!(deque.len(), 1);
+    /// ```
+    #[inline]
+    pub fn push(&self, val: T) {
+        let guard = Guard::new();
+        let bottom = self.inner.bottom.load(Relaxed);
+        let top = self.inner.top.load(Acquire);
+        let mut buffer = self.inner.buffer(Relaxed, &guard);
+        if Self::distance(top, bottom) >= buffer.capacity() {
+            buffer = self.grow(top, bottom, buffer, &guard);
+        }
+        unsafe {
+            buffer.write(bottom, val);
+        }
+        fence(Release);
+        self.inner.bottom.store(bottom.wrapping_add(1), Relaxed);
+    }
+
+    /// Pops the newest instance at the owner's end.
+    ///
+    /// Returns `None` if the [`WorkStealingDeque`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    ///
+    /// deque.push(37);
+    /// deque.push(3);
+    ///
+    /// assert_eq!(deque.pop(), Some(3));
+    /// assert_eq!(deque.pop(), Some(37));
+    /// assert!(deque.pop().is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn pop(&self) -> Option<T> {
+        let guard = Guard::new();
+        let bottom = self.inner.bottom.load(Relaxed).wrapping_sub(1);
+        let buffer = self.inner.buffer(Relaxed, &guard);
+        self.inner.bottom.store(bottom, Relaxed);
+        fence(SeqCst);
+        let top = self.inner.top.load(Relaxed);
+        let remaining = bottom.wrapping_sub(top);
+        if remaining < 0 {
+            // The deque is empty.
+            self.inner.bottom.store(bottom.wrapping_add(1), Relaxed);
+            return None;
+        }
+        if remaining > 0 {
+            // Stealers cannot reach the newest instance.
+            return Some(unsafe { buffer.read(bottom) });
+        }
+
+        // The last instance is contended with stealers.
+        let popped = self
+            .inner
+            .top
+            .compare_exchange(top, top.wrapping_add(1), SeqCst, Relaxed)
+            .is_ok();
+        self.inner.bottom.store(bottom.wrapping_add(1), Relaxed);
+        if popped {
+            Some(unsafe { buffer.read(bottom) })
+        } else {
+            None
+        }
+    }
+
+    /// Creates a new [`Stealer`] of the [`WorkStealingDeque`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// let stealer = deque.stealer();
+    ///
+    /// deque.push(1);
+    /// deque.push(2);
+    ///
+    /// let thread = std::thread::spawn(move || stealer.steal());
+    /// assert_eq!(thread.join().unwrap(), Some(1));
+    /// assert_eq!(deque.pop(), Some(2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Returns the number of instances in the [`WorkStealingDeque`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// assert_eq!(deque.len(), 0);
+    ///
+    /// deque.push(7);
+    /// deque.push(11);
+    /// assert_eq!(deque.len(), 2);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the [`WorkStealingDeque`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// assert!(deque.is_empty());
+    ///
+    /// deque.push(7);
+    /// assert!(!deque.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the underlying array.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::with_capacity(4);
+    /// assert_eq!(deque.capacity(), 4);
+    ///
+    /// for v in 0..5 {
+    ///     deque.push(v);
+    /// }
+    /// assert_eq!(deque.capacity(), 8);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.inner.buffer(Relaxed, &Guard::new()).capacity()
+    }
+
+    /// Returns the number of slots between `top` and `bottom`.
+    #[allow(clippy::cast_sign_loss)]
+    fn distance(top: isize, bottom: isize) -> usize {
+        bottom.wrapping_sub(top).max(0) as usize
+    }
+
+    /// Replaces the underlying array with a twice larger one.
+    fn grow<'g>(
+        &self,
+        top: isize,
+        bottom: isize,
+        old_buffer: &Buffer<T>,
+        guard: &'g Guard,
+    ) -> &'g Buffer<T> {
+        let new_buffer = unsafe { Shared::new_unchecked(Buffer::new(old_buffer.capacity() * 2)) };
+        let mut index = top;
+        while index != bottom {
+            unsafe {
+                new_buffer.write(index, old_buffer.read(index));
+            }
+            index = index.wrapping_add(1);
+        }
+        let new_buffer_ref = new_buffer.get_guarded_ref(guard);
+
+        // Stealers may still be reading the old array; it is reclaimed once they are done.
+        self.inner
+            .buffer
+            .swap((Some(new_buffer), Tag::None), Release);
+        new_buffer_ref
+    }
+}
+
+impl<T> Debug for WorkStealingDeque<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WorkStealingDeque")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T> Default for WorkStealingDeque<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl<T: UnwindSafe> UnwindSafe for WorkStealingDeque<T> {}
+
+impl<T> Stealer<T> {
+    /// Steals the oldest instance from the [`WorkStealingDeque`].
+    ///
+    /// Returns `None` if the [`WorkStealingDeque`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// let stealer = deque.stealer();
+    ///
+    /// deque.push(37);
+    /// deque.push(3);
+    ///
+    /// assert_eq!(stealer.steal(), Some(37));
+    /// assert_eq!(stealer.steal(), Some(3));
+    /// assert!(stealer.steal().is_none());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn steal(&self) -> Option<T> {
+        let guard = Guard::new();
+        loop {
+            let top = self.inner.top.load(Acquire);
+            fence(SeqCst);
+            let bottom = self.inner.bottom.load(Acquire);
+            if bottom.wrapping_sub(top) <= 0 {
+                return None;
+            }
+
+            // The slot may be overwritten once `top` is updated by others, therefore the instance
+            // is only valid when the following `compare_exchange` succeeds.
+            let buffer = self.inner.buffer(Acquire, &guard);
+            let val = unsafe { buffer.read_volatile(top) };
+            if self
+                .inner
+                .top
+                .compare_exchange(top, top.wrapping_add(1), SeqCst, Relaxed)
+                .is_ok()
+            {
+                return Some(unsafe { val.assume_init() });
+            }
+        }
+    }
+
+    /// Returns the number of instances in the [`WorkStealingDeque`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// let stealer = deque.stealer();
+    ///
+    /// deque.push(7);
+    /// assert_eq!(stealer.len(), 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns `true` if the [`WorkStealingDeque`] is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use scc::WorkStealingDeque;
+    ///
+    /// let deque: WorkStealingDeque<usize> = WorkStealingDeque::default();
+    /// let stealer = deque.stealer();
+    /// assert!(stealer.is_empty());
+    ///
+    /// deque.push(7);
+    /// assert!(!stealer.is_empty());
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Stealer<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Stealer").field("len", &self.len()).finish()
+    }
+}
+
+impl<T: UnwindSafe> UnwindSafe for Stealer<T> {}
+
+impl<T> Inner<T> {
+    /// Returns a reference to the current array.
+    fn buffer<'g>(&self, order: Ordering, guard: &'g Guard) -> &'g Buffer<T> {
+        unsafe { self.buffer.load(order, guard).as_ref().unwrap_unchecked() }
+    }
+
+    /// Returns the number of instances.
+    fn len(&self) -> usize {
+        let bottom = self.bottom.load(Relaxed);
+        let top = self.top.load(Relaxed);
+        WorkStealingDeque::<T>::distance(top, bottom)
+    }
+}
+
+impl<T> Drop for Inner<T> {
+    #[inline]
+    fn drop(&mut self) {
+        let guard = Guard::new();
+        let buffer = self.buffer(Relaxed, &guard);
+        let mut index = self.top.load(Relaxed);
+        let bottom = self.bottom.load(Relaxed);
+        while bottom.wrapping_sub(index) > 0 {
+            unsafe {
+                drop(buffer.read(index));
+            }
+            index = index.wrapping_add(1);
+        }
+    }
+}
+
+impl<T> Buffer<T> {
+    /// Creates a new [`Buffer`] with at least the specified capacity.
+    fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1).next_power_of_two();
+        Self {
+            slots: (0..capacity)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+        }
+    }
+
+    /// Returns the capacity.
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns a pointer to the slot for the index.
+    #[allow(clippy::cast_sign_loss)]
+    fn slot(&self, index: isize) -> *mut MaybeUninit<T> {
+        self.slots[(index as usize) & (self.slots.len() - 1)].get()
+    }
+
+    /// Writes an instance into the slot.
+    unsafe fn write(&self, index: isize, val: T) {
+        (*self.slot(index)).as_mut_ptr().write(val);
+    }
+
+    /// Moves the instance out of the slot.
+    unsafe fn read(&self, index: isize) -> T {
+        (*self.slot(index)).as_ptr().read()
+    }
+
+    /// Copies the slot that the owner may be overwriting concurrently.
+    unsafe fn read_volatile(&self, index: isize) -> MaybeUninit<T> {
+        ptr::read_volatile(self.slot(index))
+    }
+}
+
+unsafe impl<T: Send> Send for Buffer<T> {}
+unsafe impl<T: Send> Sync for Buffer<T> {}